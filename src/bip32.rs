@@ -0,0 +1,683 @@
+//! [BIP32](https://github.com/bitcoin/bips/blob/master/bip-0032.mediawiki) hierarchical
+//! deterministic key derivation on secp256k1, starting from a [`Seed`][Seed].
+//!
+//! Only available with the `bip32` cargo feature.
+//!
+//! [Seed]: ../seed/struct.Seed.html
+//!
+
+use crate::derivation::{
+    fingerprint, hash160, hmac_sha512, ChildNumber, DerivationPath, MAX_SEED_BYTES, MIN_SEED_BYTES,
+};
+use crate::error::{Error, ErrorKind};
+use crate::seed::Seed;
+use k256::elliptic_curve::sec1::ToEncodedPoint;
+use k256::elliptic_curve::PrimeField;
+use k256::{FieldBytes, NonZeroScalar, ProjectivePoint, PublicKey, Scalar, SecretKey};
+use std::fmt;
+use std::str::FromStr;
+
+/// HMAC key used to derive the master key from a seed
+const MASTER_HMAC_KEY: &[u8] = b"Bitcoin seed";
+
+/// Length of a serialized extended key before Base58Check encoding, in bytes
+const SERIALIZED_KEY_BYTES: usize = 78;
+
+/// The version bytes that start serialized extended private and public keys
+///
+/// They pick the network a key is meant for, and give the Base58Check encoding its familiar
+/// `xprv`/`xpub` or `tprv`/`tpub` prefix. Other wallets define their own versions, which can be
+/// used with [`ExtendedKeyVersion::new()`][ExtendedKeyVersion::new()].
+///
+/// [ExtendedKeyVersion::new()]: ./struct.ExtendedKeyVersion.html#method.new
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ExtendedKeyVersion {
+    private: u32,
+    public: u32,
+}
+
+impl ExtendedKeyVersion {
+    /// Bitcoin mainnet, serialized keys start with `xprv` and `xpub`
+    pub const MAINNET: ExtendedKeyVersion = ExtendedKeyVersion {
+        private: 0x0488_ADE4,
+        public: 0x0488_B21E,
+    };
+
+    /// Bitcoin testnet, serialized keys start with `tprv` and `tpub`
+    pub const TESTNET: ExtendedKeyVersion = ExtendedKeyVersion {
+        private: 0x0435_8394,
+        public: 0x0435_87CF,
+    };
+
+    /// Create custom version bytes for private and public keys
+    pub fn new(private: u32, public: u32) -> ExtendedKeyVersion {
+        ExtendedKeyVersion { private, public }
+    }
+
+    /// Get the version bytes of private keys
+    pub fn private(self) -> u32 {
+        self.private
+    }
+
+    /// Get the version bytes of public keys
+    pub fn public(self) -> u32 {
+        self.public
+    }
+}
+
+/// A secp256k1 private key together with the chain code needed to derive its children
+///
+/// The master key is derived from a [`Seed`][Seed] with
+/// [`ExtendedPrivateKey::from_seed()`][ExtendedPrivateKey::from_seed()].
+///
+/// # Example
+///
+/// ```
+/// use keyphrase::{ExtendedPrivateKey, KeyPhrase, Language, Seed};
+///
+/// let phrase = "park remain person kitchen mule spell knee armed position rail grid ankle";
+/// let keyphrase = KeyPhrase::from_phrase(phrase, Language::English).unwrap();
+/// let seed = Seed::new(&keyphrase, "");
+///
+/// let master = ExtendedPrivateKey::from_seed(&seed).unwrap();
+/// let account = master.derive_path(&"m/44'/0'/0'".parse().unwrap()).unwrap();
+///
+/// assert_eq!(account.depth(), 3);
+/// assert_eq!(account.parent_fingerprint(), master
+///     .derive_path(&"m/44'/0'".parse().unwrap())
+///     .unwrap()
+///     .fingerprint());
+/// ```
+///
+/// [Seed]: ../seed/struct.Seed.html
+/// [ExtendedPrivateKey::from_seed()]: ./struct.ExtendedPrivateKey.html#method.from_seed
+#[derive(Clone)]
+pub struct ExtendedPrivateKey {
+    private_key: SecretKey,
+    chain_code: [u8; 32],
+    depth: u8,
+    parent_fingerprint: [u8; 4],
+    child_number: ChildNumber,
+}
+
+impl ExtendedPrivateKey {
+    /// Derive the master key from a [`Seed`][Seed]
+    ///
+    /// [Seed]: ../seed/struct.Seed.html
+    pub fn from_seed(seed: &Seed) -> Result<ExtendedPrivateKey, Error> {
+        ExtendedPrivateKey::new_master(seed.as_bytes())
+    }
+
+    /// Derive the master key from raw seed bytes
+    ///
+    /// BIP32 seeds are between 128 and 512 bits long, other lengths return an `Error` of kind
+    /// `ErrorKind::InvalidSeedLength`.
+    pub fn new_master(seed: &[u8]) -> Result<ExtendedPrivateKey, Error> {
+        if seed.len() < MIN_SEED_BYTES || seed.len() > MAX_SEED_BYTES {
+            return Err(ErrorKind::InvalidSeedLength(seed.len()).into());
+        }
+
+        let (key, chain_code): ([u8; 32], [u8; 32]) = hmac_sha512(MASTER_HMAC_KEY, &[seed]);
+
+        // The odds of hitting an invalid key are below 1 in 2^127
+        let private_key: SecretKey =
+            SecretKey::from_slice(&key).map_err(|_| ErrorKind::InvalidChildKey(0))?;
+
+        Ok(ExtendedPrivateKey {
+            private_key,
+            chain_code,
+            depth: 0,
+            parent_fingerprint: [0; 4],
+            child_number: ChildNumber::from(0),
+        })
+    }
+
+    /// Create an extended private key from its parts
+    ///
+    /// Returns an `Error` of kind `ErrorKind::InvalidExtendedKey` if `private_key` is not a valid
+    /// secp256k1 private key, or if a master key (`depth` 0) has a parent fingerprint or child
+    /// number.
+    pub fn new(
+        private_key: &[u8; 32],
+        chain_code: [u8; 32],
+        depth: u8,
+        parent_fingerprint: [u8; 4],
+        child_number: ChildNumber,
+    ) -> Result<ExtendedPrivateKey, Error> {
+        check_master(depth, parent_fingerprint, child_number)?;
+
+        let private_key: SecretKey =
+            SecretKey::from_slice(private_key).map_err(|_| ErrorKind::InvalidExtendedKey)?;
+
+        Ok(ExtendedPrivateKey {
+            private_key,
+            chain_code,
+            depth,
+            parent_fingerprint,
+            child_number,
+        })
+    }
+
+    /// Parse a Base58Check serialized key with the private key version bytes of `version`
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use keyphrase::{ExtendedKeyVersion, ExtendedPrivateKey};
+    ///
+    /// let xprv = "xprv9s21ZrQH143K3QTDL4LXw2F7HEK3wJUD2nW2nRk4stbPy6cq3jPPqjiChkVvvNKmPGJxWUtg6LnF5kejMRNNU3TGtRBeJgk33yuGBxrMPHi";
+    /// let key = ExtendedPrivateKey::from_base58(xprv, ExtendedKeyVersion::MAINNET).unwrap();
+    ///
+    /// assert_eq!(key.to_base58(ExtendedKeyVersion::MAINNET), xprv);
+    /// assert!(key.to_base58(ExtendedKeyVersion::TESTNET).starts_with("tprv"));
+    /// ```
+    pub fn from_base58(
+        encoded: &str,
+        version: ExtendedKeyVersion,
+    ) -> Result<ExtendedPrivateKey, Error> {
+        let raw: RawKey = RawKey::decode(encoded)?;
+
+        if raw.version != version.private {
+            return Err(ErrorKind::InvalidKeyVersion(raw.version).into());
+        }
+
+        if raw.key[0] != 0 {
+            return Err(ErrorKind::InvalidExtendedKey.into());
+        }
+
+        let mut private_key = [0u8; 32];
+
+        private_key.copy_from_slice(&raw.key[1..]);
+
+        ExtendedPrivateKey::new(
+            &private_key,
+            raw.chain_code,
+            raw.depth,
+            raw.parent_fingerprint,
+            raw.child_number,
+        )
+    }
+
+    /// Serialize with the private key version bytes of `version` and encode as Base58Check
+    pub fn to_base58(&self, version: ExtendedKeyVersion) -> String {
+        let mut key = [0u8; 33];
+
+        key[1..].copy_from_slice(&self.private_key_bytes());
+
+        RawKey {
+            version: version.private,
+            depth: self.depth,
+            parent_fingerprint: self.parent_fingerprint,
+            child_number: self.child_number,
+            chain_code: self.chain_code,
+            key,
+        }
+        .encode()
+    }
+
+    /// Derive a child key
+    ///
+    /// In the unlikely case that the derived key is invalid this returns an `Error` of kind
+    /// `ErrorKind::InvalidChildKey`, and BIP32 says to proceed with the next index.
+    pub fn derive_child(&self, child: ChildNumber) -> Result<ExtendedPrivateKey, Error> {
+        let depth: u8 = self
+            .depth
+            .checked_add(1)
+            .ok_or(ErrorKind::MaxDepthExceeded)?;
+
+        let (tweak, chain_code): ([u8; 32], [u8; 32]) = if child.is_hardened() {
+            let private_key: [u8; 32] = self.private_key_bytes();
+
+            hmac_sha512(
+                &self.chain_code,
+                &[&[0], &private_key, &u32::from(child).to_be_bytes()],
+            )
+        } else {
+            hmac_sha512(
+                &self.chain_code,
+                &[&self.public_key_bytes(), &u32::from(child).to_be_bytes()],
+            )
+        };
+
+        let invalid = || ErrorKind::InvalidChildKey(u32::from(child));
+
+        let tweak: Scalar = parse_scalar(&tweak).ok_or_else(invalid)?;
+        let key: Scalar = tweak + self.private_key.to_nonzero_scalar().as_ref();
+        let key: NonZeroScalar = Option::from(NonZeroScalar::new(key)).ok_or_else(invalid)?;
+
+        Ok(ExtendedPrivateKey {
+            private_key: SecretKey::from(key),
+            chain_code,
+            depth,
+            parent_fingerprint: self.fingerprint(),
+            child_number: child,
+        })
+    }
+
+    /// Derive the descendant key at the end of a [`DerivationPath`][DerivationPath]
+    ///
+    /// The path is taken to start at this key, so it should normally be called on the master key.
+    ///
+    /// [DerivationPath]: ./struct.DerivationPath.html
+    pub fn derive_path(&self, path: &DerivationPath) -> Result<ExtendedPrivateKey, Error> {
+        path.iter().try_fold(
+            self.clone(),
+            |key: ExtendedPrivateKey, child: &ChildNumber| key.derive_child(*child),
+        )
+    }
+
+    /// Get the matching [`ExtendedPublicKey`][ExtendedPublicKey]
+    ///
+    /// [ExtendedPublicKey]: ./struct.ExtendedPublicKey.html
+    pub fn extended_public_key(&self) -> ExtendedPublicKey {
+        ExtendedPublicKey {
+            public_key: self.private_key.public_key(),
+            chain_code: self.chain_code,
+            depth: self.depth,
+            parent_fingerprint: self.parent_fingerprint,
+            child_number: self.child_number,
+        }
+    }
+
+    /// Get the 32 byte private key
+    pub fn private_key_bytes(&self) -> [u8; 32] {
+        self.private_key.to_bytes().into()
+    }
+
+    /// Get the 33 byte compressed public key
+    pub fn public_key_bytes(&self) -> [u8; 33] {
+        compress(&self.private_key.public_key())
+    }
+
+    /// Get the chain code
+    pub fn chain_code(&self) -> &[u8; 32] {
+        &self.chain_code
+    }
+
+    /// Get the number of derivation steps from the master key, `0` for the master key itself
+    pub fn depth(&self) -> u8 {
+        self.depth
+    }
+
+    /// Get the fingerprint of the parent key, all zeros for the master key
+    pub fn parent_fingerprint(&self) -> [u8; 4] {
+        self.parent_fingerprint
+    }
+
+    /// Get the child number this key was derived with, `0` for the master key
+    pub fn child_number(&self) -> ChildNumber {
+        self.child_number
+    }
+
+    /// Get the key identifier, the HASH160 of the compressed public key
+    pub fn identifier(&self) -> [u8; 20] {
+        hash160(&self.public_key_bytes())
+    }
+
+    /// Get the key fingerprint, the first 4 bytes of the identifier
+    pub fn fingerprint(&self) -> [u8; 4] {
+        fingerprint(&self.identifier())
+    }
+}
+
+impl FromStr for ExtendedPrivateKey {
+    type Err = Error;
+
+    /// Parse a Base58Check serialized key with mainnet (`xprv`) or testnet (`tprv`) version bytes
+    ///
+    /// There is deliberately no matching `Display` implementation, so the private key doesn't end
+    /// up in logs by accident. Use [`ExtendedPrivateKey::to_base58()`][ExtendedPrivateKey::to_base58()]
+    /// instead.
+    ///
+    /// [ExtendedPrivateKey::to_base58()]: ./struct.ExtendedPrivateKey.html#method.to_base58
+    fn from_str(encoded: &str) -> Result<ExtendedPrivateKey, Error> {
+        ExtendedPrivateKey::from_base58(encoded, ExtendedKeyVersion::MAINNET)
+            .or_else(|_| ExtendedPrivateKey::from_base58(encoded, ExtendedKeyVersion::TESTNET))
+    }
+}
+
+impl fmt::Debug for ExtendedPrivateKey {
+    /// Only prints public information, the private key and chain code are left out
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("ExtendedPrivateKey")
+            .field("fingerprint", &self.fingerprint())
+            .field("depth", &self.depth)
+            .field("parent_fingerprint", &self.parent_fingerprint)
+            .field("child_number", &self.child_number)
+            .finish()
+    }
+}
+
+/// A secp256k1 public key together with the chain code needed to derive its normal children
+///
+/// Get one from an [`ExtendedPrivateKey`][ExtendedPrivateKey] with
+/// [`ExtendedPrivateKey::extended_public_key()`][ExtendedPrivateKey::extended_public_key()].
+///
+/// [ExtendedPrivateKey]: ./struct.ExtendedPrivateKey.html
+/// [ExtendedPrivateKey::extended_public_key()]: ./struct.ExtendedPrivateKey.html#method.extended_public_key
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExtendedPublicKey {
+    public_key: PublicKey,
+    chain_code: [u8; 32],
+    depth: u8,
+    parent_fingerprint: [u8; 4],
+    child_number: ChildNumber,
+}
+
+impl ExtendedPublicKey {
+    /// Create an extended public key from its parts
+    ///
+    /// Returns an `Error` of kind `ErrorKind::InvalidExtendedKey` if `public_key` is not a valid
+    /// compressed secp256k1 public key, or if a master key (`depth` 0) has a parent fingerprint or
+    /// child number.
+    pub fn new(
+        public_key: &[u8; 33],
+        chain_code: [u8; 32],
+        depth: u8,
+        parent_fingerprint: [u8; 4],
+        child_number: ChildNumber,
+    ) -> Result<ExtendedPublicKey, Error> {
+        check_master(depth, parent_fingerprint, child_number)?;
+
+        let public_key: PublicKey =
+            PublicKey::from_sec1_bytes(public_key).map_err(|_| ErrorKind::InvalidExtendedKey)?;
+
+        Ok(ExtendedPublicKey {
+            public_key,
+            chain_code,
+            depth,
+            parent_fingerprint,
+            child_number,
+        })
+    }
+
+    /// Parse a Base58Check serialized key with the public key version bytes of `version`
+    pub fn from_base58(
+        encoded: &str,
+        version: ExtendedKeyVersion,
+    ) -> Result<ExtendedPublicKey, Error> {
+        let raw: RawKey = RawKey::decode(encoded)?;
+
+        if raw.version != version.public {
+            return Err(ErrorKind::InvalidKeyVersion(raw.version).into());
+        }
+
+        ExtendedPublicKey::new(
+            &raw.key,
+            raw.chain_code,
+            raw.depth,
+            raw.parent_fingerprint,
+            raw.child_number,
+        )
+    }
+
+    /// Serialize with the public key version bytes of `version` and encode as Base58Check
+    pub fn to_base58(&self, version: ExtendedKeyVersion) -> String {
+        RawKey {
+            version: version.public,
+            depth: self.depth,
+            parent_fingerprint: self.parent_fingerprint,
+            child_number: self.child_number,
+            chain_code: self.chain_code,
+            key: self.public_key_bytes(),
+        }
+        .encode()
+    }
+
+    /// Derive a normal child key
+    ///
+    /// Hardened children can't be derived from a public key, asking for one returns an `Error` of
+    /// kind `ErrorKind::HardenedPublicDerivation`.
+    pub fn derive_child(&self, child: ChildNumber) -> Result<ExtendedPublicKey, Error> {
+        if child.is_hardened() {
+            return Err(ErrorKind::HardenedPublicDerivation.into());
+        }
+
+        let depth: u8 = self
+            .depth
+            .checked_add(1)
+            .ok_or(ErrorKind::MaxDepthExceeded)?;
+
+        let (tweak, chain_code): ([u8; 32], [u8; 32]) = hmac_sha512(
+            &self.chain_code,
+            &[&self.public_key_bytes(), &u32::from(child).to_be_bytes()],
+        );
+
+        let invalid = || ErrorKind::InvalidChildKey(u32::from(child));
+
+        let tweak: Scalar = parse_scalar(&tweak).ok_or_else(invalid)?;
+        let point: ProjectivePoint =
+            ProjectivePoint::GENERATOR * tweak + self.public_key.to_projective();
+        let public_key: PublicKey =
+            PublicKey::from_affine(point.to_affine()).map_err(|_| invalid())?;
+
+        Ok(ExtendedPublicKey {
+            public_key,
+            chain_code,
+            depth,
+            parent_fingerprint: self.fingerprint(),
+            child_number: child,
+        })
+    }
+
+    /// Derive the descendant key at the end of a [`DerivationPath`][DerivationPath]
+    ///
+    /// The path is taken to start at this key, and can only contain normal child numbers.
+    ///
+    /// [DerivationPath]: ./struct.DerivationPath.html
+    pub fn derive_path(&self, path: &DerivationPath) -> Result<ExtendedPublicKey, Error> {
+        path.iter().try_fold(
+            self.clone(),
+            |key: ExtendedPublicKey, child: &ChildNumber| key.derive_child(*child),
+        )
+    }
+
+    /// Get the 33 byte compressed public key
+    pub fn public_key_bytes(&self) -> [u8; 33] {
+        compress(&self.public_key)
+    }
+
+    /// Get the chain code
+    pub fn chain_code(&self) -> &[u8; 32] {
+        &self.chain_code
+    }
+
+    /// Get the number of derivation steps from the master key, `0` for the master key itself
+    pub fn depth(&self) -> u8 {
+        self.depth
+    }
+
+    /// Get the fingerprint of the parent key, all zeros for the master key
+    pub fn parent_fingerprint(&self) -> [u8; 4] {
+        self.parent_fingerprint
+    }
+
+    /// Get the child number this key was derived with, `0` for the master key
+    pub fn child_number(&self) -> ChildNumber {
+        self.child_number
+    }
+
+    /// Get the key identifier, the HASH160 of the compressed public key
+    pub fn identifier(&self) -> [u8; 20] {
+        hash160(&self.public_key_bytes())
+    }
+
+    /// Get the key fingerprint, the first 4 bytes of the identifier
+    pub fn fingerprint(&self) -> [u8; 4] {
+        fingerprint(&self.identifier())
+    }
+}
+
+impl fmt::Display for ExtendedPublicKey {
+    /// Encode as Base58Check with mainnet (`xpub`) version bytes
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.to_base58(ExtendedKeyVersion::MAINNET))
+    }
+}
+
+impl FromStr for ExtendedPublicKey {
+    type Err = Error;
+
+    /// Parse a Base58Check serialized key with mainnet (`xpub`) or testnet (`tpub`) version bytes
+    fn from_str(encoded: &str) -> Result<ExtendedPublicKey, Error> {
+        ExtendedPublicKey::from_base58(encoded, ExtendedKeyVersion::MAINNET)
+            .or_else(|_| ExtendedPublicKey::from_base58(encoded, ExtendedKeyVersion::TESTNET))
+    }
+}
+
+/// The 78 byte serialization format shared by extended private and public keys
+///
+/// Private keys are stored in `key` with a leading `0x00` byte, public keys in compressed form.
+struct RawKey {
+    version: u32,
+    depth: u8,
+    parent_fingerprint: [u8; 4],
+    child_number: ChildNumber,
+    chain_code: [u8; 32],
+    key: [u8; 33],
+}
+
+impl RawKey {
+    fn encode(&self) -> String {
+        let mut bytes: Vec<u8> = Vec::with_capacity(SERIALIZED_KEY_BYTES);
+
+        bytes.extend_from_slice(&self.version.to_be_bytes());
+        bytes.push(self.depth);
+        bytes.extend_from_slice(&self.parent_fingerprint);
+        bytes.extend_from_slice(&u32::from(self.child_number).to_be_bytes());
+        bytes.extend_from_slice(&self.chain_code);
+        bytes.extend_from_slice(&self.key);
+
+        bs58::encode(bytes).with_check().into_string()
+    }
+
+    fn decode(encoded: &str) -> Result<RawKey, Error> {
+        let bytes: Vec<u8> = bs58::decode(encoded)
+            .with_check(None)
+            .into_vec()
+            .map_err(|_| ErrorKind::InvalidBase58Check)?;
+
+        if bytes.len() != SERIALIZED_KEY_BYTES {
+            return Err(ErrorKind::InvalidExtendedKey.into());
+        }
+
+        let mut version = [0u8; 4];
+        let mut parent_fingerprint = [0u8; 4];
+        let mut child_number = [0u8; 4];
+        let mut chain_code = [0u8; 32];
+        let mut key = [0u8; 33];
+
+        version.copy_from_slice(&bytes[0..4]);
+        parent_fingerprint.copy_from_slice(&bytes[5..9]);
+        child_number.copy_from_slice(&bytes[9..13]);
+        chain_code.copy_from_slice(&bytes[13..45]);
+        key.copy_from_slice(&bytes[45..78]);
+
+        Ok(RawKey {
+            version: u32::from_be_bytes(version),
+            depth: bytes[4],
+            parent_fingerprint,
+            child_number: ChildNumber::from(u32::from_be_bytes(child_number)),
+            chain_code,
+            key,
+        })
+    }
+}
+
+/// Master keys have no parent, so their parent fingerprint and child number must be zero
+fn check_master(
+    depth: u8,
+    parent_fingerprint: [u8; 4],
+    child_number: ChildNumber,
+) -> Result<(), Error> {
+    if depth == 0 && (parent_fingerprint != [0; 4] || u32::from(child_number) != 0) {
+        return Err(ErrorKind::InvalidExtendedKey.into());
+    }
+
+    Ok(())
+}
+
+/// Parse 32 big endian bytes as a scalar, `None` if they are not below the curve order
+fn parse_scalar(bytes: &[u8; 32]) -> Option<Scalar> {
+    Option::from(Scalar::from_repr(FieldBytes::from(*bytes)))
+}
+
+fn compress(public_key: &PublicKey) -> [u8; 33] {
+    let mut bytes = [0u8; 33];
+
+    bytes.copy_from_slice(public_key.to_encoded_point(true).as_bytes());
+
+    bytes
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn hardened_public_derivation_fails() {
+        let master: ExtendedPrivateKey = ExtendedPrivateKey::new_master(&[7; 32]).unwrap();
+        let public: ExtendedPublicKey = master.extended_public_key();
+
+        assert!(public
+            .derive_child(ChildNumber::hardened(0).unwrap())
+            .is_err());
+    }
+
+    #[test]
+    fn invalid_seed_length() {
+        assert!(ExtendedPrivateKey::new_master(&[0; 15]).is_err());
+        assert!(ExtendedPrivateKey::new_master(&[0; 65]).is_err());
+    }
+
+    #[test]
+    fn reject_invalid_serialization() {
+        let master: ExtendedPrivateKey = ExtendedPrivateKey::new_master(&[7; 32]).unwrap();
+        let xprv: String = master.to_base58(ExtendedKeyVersion::MAINNET);
+        let bytes: Vec<u8> = bs58::decode(&xprv).with_check(None).into_vec().unwrap();
+
+        let reencode = |edit: &dyn Fn(&mut Vec<u8>)| -> String {
+            let mut bytes: Vec<u8> = bytes.clone();
+            edit(&mut bytes);
+            bs58::encode(bytes).with_check().into_string()
+        };
+
+        assert!(xprv.parse::<ExtendedPrivateKey>().is_ok());
+
+        // Broken checksum
+        let mut broken: String = xprv.clone();
+        broken.replace_range(10..11, if &xprv[10..11] == "a" { "b" } else { "a" });
+        assert!(broken.parse::<ExtendedPrivateKey>().is_err());
+
+        // Truncated payload
+        assert!(reencode(&|b: &mut Vec<u8>| {
+            b.pop();
+        })
+        .parse::<ExtendedPrivateKey>()
+        .is_err());
+
+        // Master key with a parent fingerprint
+        assert!(reencode(&|b: &mut Vec<u8>| b[5] = 1)
+            .parse::<ExtendedPrivateKey>()
+            .is_err());
+
+        // Private key without its leading zero byte
+        assert!(reencode(&|b: &mut Vec<u8>| b[45] = 1)
+            .parse::<ExtendedPrivateKey>()
+            .is_err());
+
+        // Private key equal to zero
+        assert!(
+            reencode(&|b: &mut Vec<u8>| b[46..].iter_mut().for_each(|x| *x = 0))
+                .parse::<ExtendedPrivateKey>()
+                .is_err()
+        );
+
+        // Private key data under public key version bytes
+        assert!(
+            reencode(&|b: &mut Vec<u8>| b[..4].copy_from_slice(&[0x04, 0x88, 0xb2, 0x1e]))
+                .parse::<ExtendedPublicKey>()
+                .is_err()
+        );
+    }
+}
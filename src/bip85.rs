@@ -0,0 +1,237 @@
+//! [BIP85](https://github.com/bitcoin/bips/blob/master/bip-0085.mediawiki) deterministic
+//! entropy, for deriving independent child keyphrases, hex secrets and passwords from a single
+//! master key.
+//!
+//! Each application derives a hardened BIP32 key under `m/83696968'`, and the entropy is the
+//! HMAC-SHA512 of that private key. Knowing a child reveals nothing about the master key or the
+//! other children.
+//!
+//! Only available with the `bip85` cargo feature.
+//!
+
+use crate::bip32::ExtendedPrivateKey;
+use crate::derivation::{ChildNumber, DerivationPath};
+use crate::error::{Error, ErrorKind};
+use crate::keyphrase::KeyPhrase;
+use crate::keyphrase_type::KeyPhraseType;
+use crate::language::Language;
+use crate::seed::Seed;
+use base64::Engine;
+use hmac::{Hmac, Mac};
+use sha2::Sha512;
+
+/// Purpose of every BIP85 derivation path, "BIPE" in ASCII digits
+const PURPOSE: u32 = 83_696_968;
+
+/// Application number of the BIP39 application
+const APP_BIP39: u32 = 39;
+
+/// Application number of the HEX application
+const APP_HEX: u32 = 128_169;
+
+/// Application number of the PWD BASE64 application
+const APP_PASSWORD_BASE64: u32 = 707_764;
+
+/// HMAC key used to turn a derived private key into entropy
+const ENTROPY_HMAC_KEY: &[u8] = b"bip-entropy-from-k";
+
+/// Shortest output of the HEX application, in bytes
+const MIN_HEX_BYTES: usize = 16;
+
+/// Longest output of the HEX application, in bytes
+const MAX_HEX_BYTES: usize = 64;
+
+/// Shortest output of the PWD BASE64 application, in characters
+const MIN_PASSWORD_LENGTH: usize = 20;
+
+/// Longest output of the PWD BASE64 application, in characters
+const MAX_PASSWORD_LENGTH: usize = 86;
+
+impl ExtendedPrivateKey {
+    /// Derive a child [`KeyPhrase`][KeyPhrase] with the BIP85 BIP39 application
+    ///
+    /// The path is `m/83696968'/39'/{language}'/{words}'/{index}'`, taken to start at this key.
+    /// Custom languages have no BIP85 language code and return an `Error` of kind
+    /// `ErrorKind::UnsupportedLanguage`.
+    ///
+    /// [KeyPhrase]: ../keyphrase/struct.KeyPhrase.html
+    pub fn derive_child_keyphrase(
+        &self,
+        index: u32,
+        keyphrase_type: KeyPhraseType,
+        lang: Language,
+    ) -> Result<KeyPhrase, Error> {
+        let entropy: [u8; 64] = self.derive_entropy(&[
+            APP_BIP39,
+            language_code(&lang)?,
+            keyphrase_type.word_count() as u32,
+            index,
+        ])?;
+
+        KeyPhrase::from_entropy(&entropy[..keyphrase_type.entropy_bits() / 8], lang)
+    }
+
+    /// Derive `length` bytes of hex encoded entropy with the BIP85 HEX application
+    ///
+    /// The path is `m/83696968'/128169'/{length}'/{index}'`, taken to start at this key. `length`
+    /// must be between 16 and 64, other values return an `Error` of kind
+    /// `ErrorKind::InvalidBip85Length`.
+    pub fn derive_child_hex(&self, index: u32, length: usize) -> Result<String, Error> {
+        if !(MIN_HEX_BYTES..=MAX_HEX_BYTES).contains(&length) {
+            return Err(ErrorKind::InvalidBip85Length(length).into());
+        }
+
+        let entropy: [u8; 64] = self.derive_entropy(&[APP_HEX, length as u32, index])?;
+
+        Ok(entropy[..length]
+            .iter()
+            .map(|byte: &u8| format!("{:02x}", byte))
+            .collect())
+    }
+
+    /// Derive a password of `length` characters with the BIP85 PWD BASE64 application
+    ///
+    /// The path is `m/83696968'/707764'/{length}'/{index}'`, taken to start at this key. `length`
+    /// must be between 20 and 86, other values return an `Error` of kind
+    /// `ErrorKind::InvalidBip85Length`.
+    pub fn derive_child_password(&self, index: u32, length: usize) -> Result<String, Error> {
+        if !(MIN_PASSWORD_LENGTH..=MAX_PASSWORD_LENGTH).contains(&length) {
+            return Err(ErrorKind::InvalidBip85Length(length).into());
+        }
+
+        let entropy: [u8; 64] =
+            self.derive_entropy(&[APP_PASSWORD_BASE64, length as u32, index])?;
+        let mut password: String = base64::engine::general_purpose::STANDARD.encode(&entropy[..]);
+
+        password.truncate(length);
+
+        Ok(password)
+    }
+
+    /// Derive the 64 bytes of entropy at `m/83696968'` followed by the hardened `indices`
+    fn derive_entropy(&self, indices: &[u32]) -> Result<[u8; 64], Error> {
+        let path: DerivationPath = std::iter::once(PURPOSE)
+            .chain(indices.iter().cloned())
+            .map(ChildNumber::hardened)
+            .collect::<Result<Vec<ChildNumber>, Error>>()?
+            .into();
+
+        let key: ExtendedPrivateKey = self.derive_path(&path)?;
+
+        let mut mac =
+            Hmac::<Sha512>::new_varkey(ENTROPY_HMAC_KEY).expect("HMAC accepts keys of any length");
+        mac.input(&key.private_key_bytes());
+
+        let mut entropy = [0u8; 64];
+
+        entropy.copy_from_slice(&mac.result().code());
+
+        Ok(entropy)
+    }
+}
+
+impl Seed {
+    /// Derive a child [`KeyPhrase`][KeyPhrase] from the BIP32 master key of this seed with BIP85
+    ///
+    /// Every `index` gives an unrelated phrase, and the same seed always gives the same phrase for
+    /// the same `index`, type and language.
+    ///
+    /// Only available with the `bip85` cargo feature.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use keyphrase::{KeyPhrase, KeyPhraseType, Language, Seed};
+    ///
+    /// let phrase = "park remain person kitchen mule spell knee armed position rail grid ankle";
+    /// let keyphrase = KeyPhrase::from_phrase(phrase, Language::English).unwrap();
+    /// let seed = Seed::new(&keyphrase, "");
+    ///
+    /// let child: KeyPhrase = seed
+    ///     .derive_child_keyphrase(0, KeyPhraseType::Words24, Language::English)
+    ///     .unwrap();
+    ///
+    /// assert_eq!(child.phrase().split(' ').count(), 24);
+    /// ```
+    ///
+    /// [KeyPhrase]: ./keyphrase/struct.KeyPhrase.html
+    pub fn derive_child_keyphrase(
+        &self,
+        index: u32,
+        keyphrase_type: KeyPhraseType,
+        lang: Language,
+    ) -> Result<KeyPhrase, Error> {
+        ExtendedPrivateKey::from_seed(self)?.derive_child_keyphrase(index, keyphrase_type, lang)
+    }
+
+    /// Derive `length` bytes of hex encoded entropy from the BIP32 master key of this seed with
+    /// BIP85
+    ///
+    /// Only available with the `bip85` cargo feature.
+    pub fn derive_child_hex(&self, index: u32, length: usize) -> Result<String, Error> {
+        ExtendedPrivateKey::from_seed(self)?.derive_child_hex(index, length)
+    }
+
+    /// Derive a base64 password of `length` characters from the BIP32 master key of this seed
+    /// with BIP85
+    ///
+    /// Only available with the `bip85` cargo feature.
+    pub fn derive_child_password(&self, index: u32, length: usize) -> Result<String, Error> {
+        ExtendedPrivateKey::from_seed(self)?.derive_child_password(index, length)
+    }
+}
+
+/// The BIP85 code of a word list language
+fn language_code(lang: &Language) -> Result<u32, Error> {
+    let code: u32 = match lang {
+        Language::English => 0,
+        #[cfg(feature = "japanese")]
+        Language::Japanese => 1,
+        #[cfg(feature = "korean")]
+        Language::Korean => 2,
+        #[cfg(feature = "spanish")]
+        Language::Spanish => 3,
+        #[cfg(feature = "chinese-simplified")]
+        Language::ChineseSimplified => 4,
+        #[cfg(feature = "chinese-traditional")]
+        Language::ChineseTraditional => 5,
+        #[cfg(feature = "french")]
+        Language::French => 6,
+        #[cfg(feature = "italian")]
+        Language::Italian => 7,
+        #[cfg(feature = "czech")]
+        Language::Czech => 8,
+        #[cfg(feature = "portuguese")]
+        Language::Portuguese => 9,
+        Language::Custom(_) => return Err(ErrorKind::UnsupportedLanguage(lang.to_string()).into()),
+    };
+
+    Ok(code)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn reject_invalid_lengths() {
+        let master: ExtendedPrivateKey = ExtendedPrivateKey::new_master(&[7; 32]).unwrap();
+
+        assert!(master.derive_child_hex(0, 15).is_err());
+        assert!(master.derive_child_hex(0, 65).is_err());
+        assert_eq!(master.derive_child_hex(0, 16).unwrap().len(), 32);
+
+        assert!(master.derive_child_password(0, 19).is_err());
+        assert!(master.derive_child_password(0, 87).is_err());
+        assert_eq!(master.derive_child_password(0, 86).unwrap().len(), 86);
+    }
+
+    #[test]
+    fn reject_hardened_index() {
+        let master: ExtendedPrivateKey = ExtendedPrivateKey::new_master(&[7; 32]).unwrap();
+
+        assert!(master
+            .derive_child_keyphrase(1 << 31, KeyPhraseType::Words12, Language::English)
+            .is_err());
+    }
+}
@@ -0,0 +1,230 @@
+//! Encode arbitrary binary data as words from a [`Language`][Language] word list, and back
+//!
+//! Unlike a [`KeyPhrase`][KeyPhrase], the data can have any length up to 2047 bytes, which makes
+//! this suited for API tokens, short public keys or pairing secrets that have to be read out or
+//! typed in. It must not be used to back up a wallet, use a [`KeyPhrase`][KeyPhrase] for that.
+//!
+//! Every word holds 11 bits. The first word is the length of the data in bytes, followed by the
+//! data itself, a checksum and zero bits padding out the last word. The checksum is the first bits
+//! of the SHA256 hash of the length, as two big endian bytes, and the data. It is 11 bits long
+//! by default, and can be from 0 to 16 bits with [`encode_with_checksum()`][encode_with_checksum()],
+//! as long as the same length is given to [`decode_with_checksum()`][decode_with_checksum()].
+//!
+//! # Example
+//!
+//! ```
+//! use keyphrase::{codec, Language};
+//!
+//! let words: String = codec::encode(b"hello", Language::English).unwrap();
+//!
+//! assert_eq!(words, "absent half clock brand ten clown");
+//! assert_eq!(codec::decode(&words, Language::English).unwrap(), b"hello");
+//! ```
+//!
+//! [Language]: ../enum.Language.html
+//! [KeyPhrase]: ../struct.KeyPhrase.html
+//! [encode_with_checksum()]: ./fn.encode_with_checksum.html
+//! [decode_with_checksum()]: ./fn.decode_with_checksum.html
+
+use crate::crypto::sha256_first_bytes;
+use crate::error::{Error, ErrorKind};
+use crate::language::{Language, WordList, WordMap};
+use crate::util::{checksum, BitWriter, Bits11, IterExt};
+
+/// Checksum length used by [`encode()`][encode()] and [`decode()`][decode()], in bits
+///
+/// [encode()]: ./fn.encode.html
+/// [decode()]: ./fn.decode.html
+pub const DEFAULT_CHECKSUM_BITS: u8 = 11;
+
+/// Longest checksum, in bits
+pub const MAX_CHECKSUM_BITS: u8 = 16;
+
+/// Longest data that can be encoded, in bytes, the largest length the first word can hold
+pub const MAX_DATA_LENGTH: usize = 2047;
+
+/// Encode `data` as words, with an 11 bit checksum
+///
+/// Returns an `Error` of kind `ErrorKind::InvalidDataLength` if the data is longer than
+/// 2047 bytes.
+pub fn encode(data: &[u8], lang: Language) -> Result<String, Error> {
+    encode_with_checksum(data, lang, DEFAULT_CHECKSUM_BITS)
+}
+
+/// Encode `data` as words, with a checksum of `checksum_bits` from 0 to 16
+///
+/// Returns an `Error` of kind `ErrorKind::InvalidChecksumLength` for longer checksums, and of
+/// kind `ErrorKind::InvalidDataLength` if the data is longer than 2047 bytes.
+///
+/// # Example
+///
+/// ```
+/// use keyphrase::{codec, Language};
+///
+/// let token: &[u8] = &[0xDE, 0xAD, 0xBE, 0xEF];
+///
+/// let words: String = codec::encode_with_checksum(token, Language::English, 0).unwrap();
+///
+/// assert_eq!(words, "above team hospital rookie");
+/// assert_eq!(codec::decode_with_checksum(&words, Language::English, 0).unwrap(), token);
+/// ```
+pub fn encode_with_checksum(
+    data: &[u8],
+    lang: Language,
+    checksum_bits: u8,
+) -> Result<String, Error> {
+    check_checksum_bits(checksum_bits)?;
+
+    if data.len() > MAX_DATA_LENGTH {
+        return Err(ErrorKind::InvalidDataLength(data.len()).into());
+    }
+
+    let wordlist: &WordList = lang.wordlist();
+    let checksum: [u8; 4] = checksum_tail(data, checksum_bits).to_be_bytes();
+
+    // The checksum and padding take up at most 26 bits after the data, covered by the tail
+    let words: String = Some(Bits11::from(data.len() as u16))
+        .into_iter()
+        .chain(
+            data.iter()
+                .chain(&checksum)
+                .bits()
+                .take(body_word_count(data.len(), checksum_bits)),
+        )
+        .map(|bits: Bits11| wordlist.get_word(bits))
+        .join(" ");
+
+    Ok(words)
+}
+
+/// Decode words made by [`encode()`][encode()] back into the data
+///
+/// Words can be separated by any whitespace. Returns an `Error` of kind `ErrorKind::InvalidWord`
+/// for a word that is not in the word list, `ErrorKind::InvalidWordLength` if the number of words
+/// does not match the length in the first word, and `ErrorKind::InvalidChecksum` if the checksum
+/// or padding is wrong.
+///
+/// [encode()]: ./fn.encode.html
+pub fn decode(words: &str, lang: Language) -> Result<Vec<u8>, Error> {
+    decode_with_checksum(words, lang, DEFAULT_CHECKSUM_BITS)
+}
+
+/// Decode words made by [`encode_with_checksum()`][encode_with_checksum()] with the same
+/// `checksum_bits` back into the data
+///
+/// Fails like [`decode()`][decode()], and with an `Error` of kind
+/// `ErrorKind::InvalidChecksumLength` for checksums longer than 16 bits.
+///
+/// [encode_with_checksum()]: ./fn.encode_with_checksum.html
+/// [decode()]: ./fn.decode.html
+pub fn decode_with_checksum(
+    words: &str,
+    lang: Language,
+    checksum_bits: u8,
+) -> Result<Vec<u8>, Error> {
+    check_checksum_bits(checksum_bits)?;
+
+    let wordmap: &WordMap = lang.wordmap();
+
+    let words: Vec<Bits11> = words
+        .split_whitespace()
+        .enumerate()
+        .map(|(position, word): (usize, &str)| wordmap.get_bits_at(position, word))
+        .collect::<Result<_, _>>()?;
+
+    let (length, body): (usize, &[Bits11]) = match words.split_first() {
+        Some((length, body)) => (u16::from(*length) as usize, body),
+        None => return Err(ErrorKind::InvalidWordLength(0).into()),
+    };
+
+    if body.len() != body_word_count(length, checksum_bits) {
+        return Err(ErrorKind::InvalidWordLength(words.len()).into());
+    }
+
+    let mut bits = BitWriter::with_capacity(body.len() * 11);
+
+    for word in body {
+        bits.push(*word);
+    }
+
+    let mut data: Vec<u8> = bits.into_bytes();
+
+    // Line the checksum and padding up with the tail made when encoding
+    data.resize(length + 4, 0);
+
+    let tail: Vec<u8> = data.split_off(length);
+    let tail: u32 = u32::from_be_bytes([tail[0], tail[1], tail[2], tail[3]]);
+
+    if tail != checksum_tail(&data, checksum_bits) {
+        return Err(ErrorKind::InvalidChecksum.into());
+    }
+
+    Ok(data)
+}
+
+fn check_checksum_bits(checksum_bits: u8) -> Result<(), Error> {
+    if checksum_bits > MAX_CHECKSUM_BITS {
+        Err(ErrorKind::InvalidChecksumLength(checksum_bits).into())
+    } else {
+        Ok(())
+    }
+}
+
+/// Number of words after the length holding the data, checksum and padding
+fn body_word_count(length: usize, checksum_bits: u8) -> usize {
+    (length * 8 + checksum_bits as usize).div_ceil(11)
+}
+
+/// The checksum of `data` in the top bits of a `u32`, followed by zero bits
+fn checksum_tail(data: &[u8], checksum_bits: u8) -> u32 {
+    if checksum_bits == 0 {
+        return 0;
+    }
+
+    let mut input: Vec<u8> = Vec::with_capacity(2 + data.len());
+
+    input.extend_from_slice(&(data.len() as u16).to_be_bytes());
+    input.extend_from_slice(data);
+
+    (checksum(sha256_first_bytes(&input), checksum_bits) as u32) << (32 - checksum_bits)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn word_count_follows_length() {
+        for length in 0..64 {
+            let data: Vec<u8> = (0..length as u8).collect();
+
+            for checksum_bits in 0..=MAX_CHECKSUM_BITS {
+                let words: String =
+                    encode_with_checksum(&data, Language::English, checksum_bits).unwrap();
+
+                assert_eq!(
+                    words.split(' ').count(),
+                    1 + body_word_count(length, checksum_bits)
+                );
+                assert_eq!(
+                    decode_with_checksum(&words, Language::English, checksum_bits).unwrap(),
+                    data
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn reject_invalid_lengths() {
+        let error: Error = encode(&[0; MAX_DATA_LENGTH + 1], Language::English).unwrap_err();
+        assert!(matches!(error.kind(), ErrorKind::InvalidDataLength(2048)));
+
+        let error: Error = encode_with_checksum(b"", Language::English, 17).unwrap_err();
+        assert!(matches!(error.kind(), ErrorKind::InvalidChecksumLength(17)));
+
+        let error: Error = decode("", Language::English).unwrap_err();
+        assert!(matches!(error.kind(), ErrorKind::InvalidWordLength(0)));
+
+        assert!(encode(&[0; MAX_DATA_LENGTH], Language::English).is_ok());
+    }
+}
@@ -0,0 +1,596 @@
+//! [BIP93](https://github.com/bitcoin/bips/blob/master/bip-0093.mediawiki) codex32 shares.
+//!
+//! codex32 strings are bech32-like strings protected by a BCH checksum, simple enough to be
+//! created, checked and recovered by hand with paper computers. The secret itself is the share
+//! with index `s`. With a threshold of `k`, any `k` shares with the same identifier recover it,
+//! or any other share, by Lagrange interpolation over GF(32).
+//!
+
+use crate::crypto::gen_random_bytes;
+use crate::error::{Error, ErrorKind};
+use crate::keyphrase::KeyPhrase;
+use crate::language::Language;
+use std::collections::BTreeMap;
+use std::fmt;
+use std::str::FromStr;
+
+/// Human readable part and separator every codex32 string starts with
+const PREFIX: &str = "ms1";
+
+/// Characters of the bech32 alphabet, in the order of their values
+const CHARSET: &[u8; 32] = b"qpzry9x8gf2tvdw0s3jn54khce6mua7l";
+
+/// Share indices handed out by [`Codex32Share::split()`][Codex32Share::split()], in order
+///
+/// [Codex32Share::split()]: ./struct.Codex32Share.html#method.split
+const SHARE_INDICES: &[u8; 31] = b"acdefghjklmnpqrtuvwxyz023456789";
+
+/// Share index of the secret
+const SECRET_INDEX: char = 's';
+
+/// Number of characters holding the threshold, identifier and share index
+const HEADER_CHARS: usize = 6;
+
+/// Number of characters in the identifier
+const IDENTIFIER_CHARS: usize = 4;
+
+/// Largest threshold, a single digit
+const MAX_THRESHOLD: u8 = 9;
+
+/// Shortest secret that can be encoded, in bytes
+const MIN_SECRET_BYTES: usize = 16;
+
+/// Longest secret that can be encoded, in bytes
+const MAX_SECRET_BYTES: usize = 64;
+
+/// Most padding bits after the last byte of the payload
+const MAX_PADDING_BITS: usize = 4;
+
+/// Longest data part protected by the short checksum
+const MAX_SHORT_LENGTH: usize = 93;
+
+/// Shortest data part protected by the long checksum
+const MIN_LONG_LENGTH: usize = 96;
+
+/// Longest data part protected by the long checksum
+const MAX_LONG_LENGTH: usize = 124;
+
+/// Reduction polynomial of GF(32), `x^5 + x^3 + 1`
+const GF32_MODULUS: u16 = 0b10_1001;
+
+/// Residue the checksum computation starts from
+const INITIAL_RESIDUE: u128 = 0x0231_81B3;
+
+/// A BCH code over GF(32) used as codex32 checksum
+struct Checksum {
+    generator: [u128; 5],
+    target: u128,
+    length: usize,
+}
+
+/// Checksum of data parts up to 93 characters long
+const SHORT_CHECKSUM: Checksum = Checksum {
+    generator: [
+        0x1_9DC5_00CE_73FD_E210,
+        0x1_BFAE_00DE_F77F_E529,
+        0x1_FBD9_20FF_FE7B_EE52,
+        0x1_7396_40BD_EEE3_FDAD,
+        0x0_7729_A039_CFC7_5F5A,
+    ],
+    target: 0x1_0CE0_795C_2FD1_E62A,
+    length: 13,
+};
+
+/// Checksum of data parts of 96 characters and longer
+const LONG_CHECKSUM: Checksum = Checksum {
+    generator: [
+        0x3D5_9D27_3535_EA62_D897,
+        0x7A9_BECB_6361_C6C5_1507,
+        0x543_F9B7_E6C3_8D8A_2A0E,
+        0x0C5_77EA_ECCF_1990_D13C,
+        0x188_7F74_F8DC_71B1_0651,
+    ],
+    target: 0x433_81E5_70BF_4798_AB26,
+    length: 15,
+};
+
+impl Checksum {
+    /// The checksum that follows `len` characters of threshold, identifier, index and payload
+    fn for_content(len: usize) -> &'static Checksum {
+        if len + SHORT_CHECKSUM.length > MAX_SHORT_LENGTH {
+            &LONG_CHECKSUM
+        } else {
+            &SHORT_CHECKSUM
+        }
+    }
+
+    fn polymod(&self, values: &[u8]) -> u128 {
+        let top_shift: usize = 5 * (self.length - 1);
+        let mut residue: u128 = INITIAL_RESIDUE;
+
+        for value in values {
+            let top: u128 = residue >> top_shift;
+
+            residue = ((residue & ((1 << top_shift) - 1)) << 5) ^ u128::from(*value);
+
+            for (i, gen) in self.generator.iter().enumerate() {
+                if (top >> i) & 1 != 0 {
+                    residue ^= gen;
+                }
+            }
+        }
+
+        residue
+    }
+
+    fn create(&self, content: &[u8]) -> Vec<u8> {
+        let mut values: Vec<u8> = content.to_vec();
+
+        values.resize(content.len() + self.length, 0);
+
+        let residue: u128 = self.polymod(&values) ^ self.target;
+
+        (0..self.length)
+            .rev()
+            .map(|i: usize| ((residue >> (5 * i)) & 31) as u8)
+            .collect()
+    }
+
+    fn verify(&self, values: &[u8]) -> bool {
+        self.polymod(values) == self.target
+    }
+}
+
+/// A single codex32 string, either the secret itself or one of its shares
+///
+/// # Example
+///
+/// ```
+/// use keyphrase::Codex32Share;
+///
+/// let secret = [0x42; 16];
+///
+/// // Any two of three shares recover the secret
+/// let shares = Codex32Share::split(&secret, 2, "cash", 3).unwrap();
+///
+/// let strings: Vec<String> = shares.iter().map(|share| share.to_string()).collect();
+/// assert!(strings[0].starts_with("ms12casha"));
+///
+/// let parsed: Vec<Codex32Share> = strings.iter().map(|string| string.parse().unwrap()).collect();
+///
+/// assert_eq!(Codex32Share::combine(&parsed[1..]).unwrap(), secret);
+/// assert!(Codex32Share::combine(&parsed[..1]).is_err());
+/// ```
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub struct Codex32Share {
+    /// Values of the data part, the checksum included
+    values: Vec<u8>,
+}
+
+impl Codex32Share {
+    /// Encode `secret` on its own as an unshared codex32 secret, with a threshold of `0`
+    ///
+    /// The `identifier` is 4 characters of the bech32 alphabet, used to tell backups apart.
+    /// The secret must be 16 to 64 bytes long, which holds for the entropy of every
+    /// [`KeyPhrase`][KeyPhrase].
+    ///
+    /// [KeyPhrase]: ../keyphrase/struct.KeyPhrase.html
+    pub fn new_secret(secret: &[u8], identifier: &str) -> Result<Codex32Share, Error> {
+        Codex32Share::encode(secret, 0, identifier)
+    }
+
+    /// Split `secret` into `count` shares, `threshold` of which are needed to recover it
+    ///
+    /// The threshold must be 2 to 9 and there can be at most 31 shares. The first
+    /// `threshold - 1` shares are random, the others are derived from them and the secret.
+    pub fn split(
+        secret: &[u8],
+        threshold: u8,
+        identifier: &str,
+        count: u8,
+    ) -> Result<Vec<Codex32Share>, Error> {
+        if !(2..=MAX_THRESHOLD).contains(&threshold)
+            || count < threshold
+            || count as usize > SHARE_INDICES.len()
+        {
+            return Err(ErrorKind::InvalidThreshold(threshold, count).into());
+        }
+
+        let secret_share: Codex32Share = Codex32Share::encode(secret, threshold, identifier)?;
+        let payload_len: usize = secret_share.payload().len();
+
+        let header: Vec<u8> = secret_share.values[..HEADER_CHARS - 1].to_vec();
+        let mut points: Vec<Codex32Share> = vec![secret_share];
+
+        for index in &SHARE_INDICES[..threshold as usize - 1] {
+            let payload: Vec<u8> = gen_random_bytes(payload_len)
+                .into_iter()
+                .map(|byte: u8| byte & 31)
+                .collect();
+
+            points.push(Codex32Share::from_parts(
+                &header,
+                char_value(*index as char).unwrap(),
+                &payload,
+            ));
+        }
+
+        SHARE_INDICES[..count as usize]
+            .iter()
+            .map(|index: &u8| Codex32Share::interpolate(&points, *index as char))
+            .collect()
+    }
+
+    /// Derive the share with the given index from `threshold` shares
+    ///
+    /// The index `s` gives the secret. Other indices give new shares for the same secret, which
+    /// can replace lost ones.
+    pub fn interpolate(shares: &[Codex32Share], index: char) -> Result<Codex32Share, Error> {
+        let target: u8 = char_value(index).ok_or(ErrorKind::InvalidShareIndex(index))?;
+        let first: &Codex32Share = shares.first().ok_or(ErrorKind::NotEnoughShares(1, 0))?;
+
+        let mut points: BTreeMap<u8, &Codex32Share> = BTreeMap::new();
+
+        for share in shares {
+            if share.values.len() != first.values.len()
+                || share.values[..HEADER_CHARS - 1] != first.values[..HEADER_CHARS - 1]
+            {
+                return Err(ErrorKind::MismatchedShares.into());
+            }
+
+            match points.insert(share.values[HEADER_CHARS - 1], share) {
+                Some(other) if other.values != share.values => {
+                    return Err(ErrorKind::MismatchedShares.into());
+                }
+                _ => {}
+            }
+        }
+
+        if let Some(share) = points.get(&target) {
+            return Ok((*share).clone());
+        }
+
+        let threshold: u8 = first.threshold();
+
+        if threshold == 0 {
+            return Err(ErrorKind::InvalidShareIndex(index).into());
+        }
+
+        if points.len() < threshold as usize {
+            return Err(ErrorKind::NotEnoughShares(threshold, points.len()).into());
+        }
+
+        let points: Vec<(u8, &Codex32Share)> =
+            points.into_iter().take(threshold as usize).collect();
+
+        let mut values: Vec<u8> = vec![0; first.values.len()];
+
+        for (i, (x_i, share)) in points.iter().enumerate() {
+            let mut numerator: u8 = 1;
+            let mut denominator: u8 = 1;
+
+            for (j, (x_j, _)) in points.iter().enumerate() {
+                if i != j {
+                    numerator = gf32_mul(numerator, target ^ x_j);
+                    denominator = gf32_mul(denominator, x_i ^ x_j);
+                }
+            }
+
+            let basis: u8 = gf32_mul(numerator, gf32_inv(denominator));
+
+            for (value, share_value) in values.iter_mut().zip(&share.values) {
+                *value ^= gf32_mul(basis, *share_value);
+            }
+        }
+
+        Ok(Codex32Share { values })
+    }
+
+    /// Recover the secret from enough shares, or from the unshared secret
+    pub fn combine(shares: &[Codex32Share]) -> Result<Vec<u8>, Error> {
+        Ok(Codex32Share::interpolate(shares, SECRET_INDEX)?.data())
+    }
+
+    /// Parse a codex32 string
+    ///
+    /// Both all lowercase and all uppercase strings are accepted. Returns an `Error` of kind
+    /// `ErrorKind::InvalidShareChecksum` if the string was not written down correctly.
+    pub fn from_string(string: &str) -> Result<Codex32Share, Error> {
+        if string.chars().any(char::is_lowercase) && string.chars().any(char::is_uppercase) {
+            return Err(ErrorKind::MixedCaseCodex32.into());
+        }
+
+        match string.get(..PREFIX.len()) {
+            Some(prefix) if prefix.eq_ignore_ascii_case(PREFIX) => {}
+            _ => return Err(ErrorKind::InvalidCodex32Prefix.into()),
+        }
+
+        let values: Vec<u8> = string[PREFIX.len()..]
+            .chars()
+            .map(|c: char| char_value(c).ok_or(ErrorKind::InvalidCodex32Character(c)))
+            .collect::<Result<Vec<u8>, ErrorKind>>()?;
+
+        let checksum: &Checksum = match values.len() {
+            len if len <= MAX_SHORT_LENGTH => &SHORT_CHECKSUM,
+            len if (MIN_LONG_LENGTH..=MAX_LONG_LENGTH).contains(&len) => &LONG_CHECKSUM,
+            len => return Err(ErrorKind::InvalidCodex32Length(len).into()),
+        };
+
+        let payload_bits: usize = values.len().saturating_sub(HEADER_CHARS + checksum.length) * 5;
+
+        if payload_bits % 8 > MAX_PADDING_BITS
+            || !(MIN_SECRET_BYTES..=MAX_SECRET_BYTES).contains(&(payload_bits / 8))
+        {
+            return Err(ErrorKind::InvalidCodex32Length(values.len()).into());
+        }
+
+        if !checksum.verify(&values) {
+            return Err(ErrorKind::InvalidShareChecksum.into());
+        }
+
+        let share = Codex32Share { values };
+        let threshold: char = CHARSET[share.values[0] as usize] as char;
+
+        match threshold.to_digit(10) {
+            Some(0) if share.share_index() != SECRET_INDEX => {
+                Err(ErrorKind::InvalidShareIndex(share.share_index()).into())
+            }
+            Some(digit) if digit != 1 => Ok(share),
+            _ => Err(ErrorKind::InvalidCodex32Threshold(threshold).into()),
+        }
+    }
+
+    /// Get the number of shares needed to recover the secret, `0` for an unshared secret
+    pub fn threshold(&self) -> u8 {
+        CHARSET[self.values[0] as usize] - b'0'
+    }
+
+    /// Get the identifier shared by the secret and all of its shares
+    pub fn identifier(&self) -> String {
+        self.values[1..=IDENTIFIER_CHARS]
+            .iter()
+            .map(|value: &u8| CHARSET[*value as usize] as char)
+            .collect()
+    }
+
+    /// Get the index of this share, `s` for the secret
+    pub fn share_index(&self) -> char {
+        CHARSET[self.values[HEADER_CHARS - 1] as usize] as char
+    }
+
+    /// Get the payload as bytes, without the padding bits
+    ///
+    /// For the share with index `s` this is the secret.
+    pub fn data(&self) -> Vec<u8> {
+        let mut bytes: Vec<u8> = Vec::with_capacity(self.payload().len() * 5 / 8);
+        let mut acc: u32 = 0;
+        let mut acc_bits: usize = 0;
+
+        for value in self.payload() {
+            acc = (acc << 5) | u32::from(*value);
+            acc_bits += 5;
+
+            if acc_bits >= 8 {
+                acc_bits -= 8;
+                bytes.push((acc >> acc_bits) as u8);
+                acc &= (1 << acc_bits) - 1;
+            }
+        }
+
+        bytes
+    }
+
+    /// The share with index `s` holding `secret`, padded with zero bits
+    fn encode(secret: &[u8], threshold: u8, identifier: &str) -> Result<Codex32Share, Error> {
+        if !(MIN_SECRET_BYTES..=MAX_SECRET_BYTES).contains(&secret.len()) {
+            return Err(ErrorKind::InvalidSecretLength(secret.len()).into());
+        }
+
+        let identifier_values: Vec<u8> = identifier
+            .chars()
+            .map(char_value)
+            .collect::<Option<Vec<u8>>>()
+            .filter(|values: &Vec<u8>| values.len() == IDENTIFIER_CHARS)
+            .ok_or_else(|| ErrorKind::InvalidCodex32Identifier(identifier.to_string()))?;
+
+        let mut header: Vec<u8> = vec![char_value((b'0' + threshold) as char).unwrap()];
+        header.extend(identifier_values);
+
+        let mut payload: Vec<u8> = Vec::with_capacity((secret.len() * 8).div_ceil(5));
+        let mut acc: u32 = 0;
+        let mut acc_bits: usize = 0;
+
+        for byte in secret {
+            acc = (acc << 8) | u32::from(*byte);
+            acc_bits += 8;
+
+            while acc_bits >= 5 {
+                acc_bits -= 5;
+                payload.push(((acc >> acc_bits) & 31) as u8);
+            }
+        }
+
+        if acc_bits > 0 {
+            payload.push(((acc << (5 - acc_bits)) & 31) as u8);
+        }
+
+        Ok(Codex32Share::from_parts(
+            &header,
+            char_value(SECRET_INDEX).unwrap(),
+            &payload,
+        ))
+    }
+
+    /// Assemble a share from its header, index and payload values, adding the checksum
+    fn from_parts(header: &[u8], index: u8, payload: &[u8]) -> Codex32Share {
+        let mut values: Vec<u8> = header.to_vec();
+
+        values.push(index);
+        values.extend_from_slice(payload);
+
+        let checksum: Vec<u8> = Checksum::for_content(values.len()).create(&values);
+        values.extend(checksum);
+
+        Codex32Share { values }
+    }
+
+    /// Values of the payload, between the header and the checksum
+    fn payload(&self) -> &[u8] {
+        let checksum: &Checksum = if self.values.len() > MAX_SHORT_LENGTH {
+            &LONG_CHECKSUM
+        } else {
+            &SHORT_CHECKSUM
+        };
+
+        &self.values[HEADER_CHARS..self.values.len() - checksum.length]
+    }
+}
+
+impl fmt::Display for Codex32Share {
+    /// Write the share as a lowercase codex32 string
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(PREFIX)?;
+
+        for value in &self.values {
+            write!(f, "{}", CHARSET[*value as usize] as char)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl fmt::Debug for Codex32Share {
+    /// Only prints the share header, the payload is left out
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Codex32Share")
+            .field("threshold", &self.threshold())
+            .field("identifier", &self.identifier())
+            .field("share_index", &self.share_index())
+            .finish()
+    }
+}
+
+impl FromStr for Codex32Share {
+    type Err = Error;
+
+    fn from_str(string: &str) -> Result<Codex32Share, Error> {
+        Codex32Share::from_string(string)
+    }
+}
+
+impl KeyPhrase {
+    /// Encode the entropy of this [`KeyPhrase`][KeyPhrase] as an unshared codex32 secret
+    ///
+    /// See [`Codex32Share::new_secret()`][Codex32Share::new_secret()].
+    ///
+    /// [KeyPhrase]: ./keyphrase/struct.KeyPhrase.html
+    /// [Codex32Share::new_secret()]: ./struct.Codex32Share.html#method.new_secret
+    pub fn to_codex32_secret(&self, identifier: &str) -> Result<Codex32Share, Error> {
+        Codex32Share::new_secret(self.entropy(), identifier)
+    }
+
+    /// Split the entropy of this [`KeyPhrase`][KeyPhrase] into codex32 shares
+    ///
+    /// See [`Codex32Share::split()`][Codex32Share::split()] for the meaning of the arguments.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use keyphrase::{KeyPhrase, KeyPhraseType, Language};
+    ///
+    /// let keyphrase = KeyPhrase::new(KeyPhraseType::Words24, Language::English);
+    ///
+    /// // Any three of five shares recover the keyphrase
+    /// let shares = keyphrase.to_codex32_shares(3, "leet", 5).unwrap();
+    ///
+    /// let recovered = KeyPhrase::from_codex32(&shares[2..], Language::English).unwrap();
+    ///
+    /// assert_eq!(recovered.phrase(), keyphrase.phrase());
+    /// ```
+    ///
+    /// [KeyPhrase]: ./keyphrase/struct.KeyPhrase.html
+    /// [Codex32Share::split()]: ./struct.Codex32Share.html#method.split
+    pub fn to_codex32_shares(
+        &self,
+        threshold: u8,
+        identifier: &str,
+        count: u8,
+    ) -> Result<Vec<Codex32Share>, Error> {
+        Codex32Share::split(self.entropy(), threshold, identifier, count)
+    }
+
+    /// Recover a [`KeyPhrase`][KeyPhrase] in the given language from codex32 shares
+    ///
+    /// [KeyPhrase]: ./keyphrase/struct.KeyPhrase.html
+    pub fn from_codex32(shares: &[Codex32Share], lang: Language) -> Result<KeyPhrase, Error> {
+        KeyPhrase::from_entropy(&Codex32Share::combine(shares)?, lang)
+    }
+}
+
+/// The value of a bech32 character, either case
+fn char_value(c: char) -> Option<u8> {
+    if !c.is_ascii() {
+        return None;
+    }
+
+    let lowercase: u8 = c.to_ascii_lowercase() as u8;
+
+    CHARSET
+        .iter()
+        .position(|charset_c: &u8| *charset_c == lowercase)
+        .map(|value: usize| value as u8)
+}
+
+fn gf32_mul(a: u8, b: u8) -> u8 {
+    let mut product: u16 = 0;
+
+    for i in 0..5 {
+        if (b >> i) & 1 != 0 {
+            product ^= u16::from(a) << i;
+        }
+    }
+
+    for i in (5..9).rev() {
+        if (product >> i) & 1 != 0 {
+            product ^= GF32_MODULUS << (i - 5);
+        }
+    }
+
+    product as u8
+}
+
+/// The inverse of a non-zero element, `a^30` since the multiplicative group has 31 elements
+fn gf32_inv(a: u8) -> u8 {
+    (1..30).fold(a, |power: u8, _| gf32_mul(power, a))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn gf32_inverse() {
+        for a in 1..32 {
+            assert_eq!(gf32_mul(a, gf32_inv(a)), 1);
+        }
+    }
+
+    #[test]
+    fn share_indices_are_bech32() {
+        for index in SHARE_INDICES.iter() {
+            assert!(char_value(*index as char).is_some());
+            assert_ne!(*index as char, SECRET_INDEX);
+        }
+    }
+
+    #[test]
+    fn checksum_length_follows_content() {
+        for len in &[16, 32, 46, 47, 64] {
+            let share: Codex32Share = Codex32Share::new_secret(&vec![0xA5; *len], "test").unwrap();
+            let string: String = share.to_string();
+
+            assert_eq!(share.data(), vec![0xA5; *len]);
+            assert_eq!(Codex32Share::from_string(&string).unwrap(), share);
+        }
+    }
+}
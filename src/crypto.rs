@@ -5,17 +5,32 @@
 //! [Seed]: ../seed/struct.Seed.html
 //!
 
+use crate::seed_options::Pbkdf2Hash;
 use hmac::Hmac;
 use rand::{thread_rng, RngCore};
 use sha2::Digest;
 
-const PBKDF2_ROUNDS: usize = 2048;
-const PBKDF2_BYTES: usize = 64;
+/// PBKDF2 iterations of the default seed derivation
+pub(crate) const PBKDF2_ROUNDS: u32 = 2048;
+
+/// Seed length of the default seed derivation, in bytes
+pub(crate) const PBKDF2_BYTES: usize = 64;
 
 /// SHA256 helper function, internal to the crate
 ///
-pub(crate) fn sha256_first_byte(input: &[u8]) -> u8 {
-    sha2::Sha256::digest(input).as_ref()[0]
+/// Returns the first two bytes of the hash, enough for the checksum of the longest keyphrase.
+pub(crate) fn sha256_first_bytes(input: &[u8]) -> [u8; 2] {
+    let hash = sha2::Sha256::digest(input);
+
+    [hash[0], hash[1]]
+}
+
+/// SHA256 helper function, internal to the crate
+pub(crate) fn sha256(input: &[u8]) -> [u8; 32] {
+    let mut hash = [0u8; 32];
+    hash.copy_from_slice(&sha2::Sha256::digest(input));
+
+    hash
 }
 
 /// Random byte generator, used to create new keyphrases
@@ -33,10 +48,48 @@ pub(crate) fn gen_random_bytes(byte_length: usize) -> Vec<u8> {
 /// [KeyPhrase]: ../keyphrase/struct.KeyPhrase.html
 /// [Seed]: ../seed/struct.Seed.html
 ///
-pub(crate) fn pbkdf2(input: &[u8], salt: &str) -> Vec<u8> {
-    let mut seed = vec![0u8; PBKDF2_BYTES];
+pub(crate) fn pbkdf2(
+    input: &[u8],
+    salt: &[u8],
+    hash: Pbkdf2Hash,
+    rounds: u32,
+    length: usize,
+) -> Vec<u8> {
+    let mut seed = vec![0u8; length];
 
-    pbkdf2::pbkdf2::<Hmac<sha2::Sha512>>(input, salt.as_bytes(), PBKDF2_ROUNDS, &mut seed);
+    match hash {
+        Pbkdf2Hash::Sha512 => {
+            pbkdf2::pbkdf2::<Hmac<sha2::Sha512>>(input, salt, rounds as usize, &mut seed)
+        }
+        Pbkdf2Hash::Sha256 => {
+            pbkdf2::pbkdf2::<Hmac<sha2::Sha256>>(input, salt, rounds as usize, &mut seed)
+        }
+    }
 
     seed
 }
+
+/// Argon2id helper, the memory-hard alternative to [`pbkdf2()`][pbkdf2()]
+///
+/// The parameters must have been checked by `SeedOptions`.
+///
+/// [pbkdf2()]: ./fn.pbkdf2.html
+///
+#[cfg(feature = "argon2id")]
+pub(crate) fn argon2id(
+    input: &[u8],
+    salt: &[u8],
+    params: argon2::Params,
+) -> Result<Vec<u8>, argon2::Error> {
+    let mut seed = vec![
+        0u8;
+        params
+            .output_len()
+            .unwrap_or(argon2::Params::DEFAULT_OUTPUT_LEN)
+    ];
+
+    argon2::Argon2::new(argon2::Algorithm::Argon2id, argon2::Version::V0x13, params)
+        .hash_password_into(input, salt, &mut seed)?;
+
+    Ok(seed)
+}
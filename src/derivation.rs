@@ -0,0 +1,279 @@
+//! Child numbers and derivation paths shared by the hierarchical key derivation schemes,
+//! [BIP32](https://github.com/bitcoin/bips/blob/master/bip-0032.mediawiki) on secp256k1 and
+//! [SLIP-10](https://github.com/satoshilabs/slips/blob/master/slip-0010.md) on ed25519.
+//!
+//! Only available with the `bip32` or `slip10` cargo feature.
+//!
+
+use crate::error::{Error, ErrorKind};
+use hmac::{Hmac, Mac};
+use ripemd160::Ripemd160;
+use sha2::{Digest, Sha256, Sha512};
+use std::fmt;
+use std::str::FromStr;
+
+/// Child numbers with this bit set derive hardened keys
+const HARDENED_BIT: u32 = 1 << 31;
+
+/// Shortest seed accepted when deriving a master key, in bytes
+pub(crate) const MIN_SEED_BYTES: usize = 16;
+
+/// Longest seed accepted when deriving a master key, in bytes
+pub(crate) const MAX_SEED_BYTES: usize = 64;
+
+/// The index of a key among the children of its parent
+///
+/// Indices from `0` to `2^31 - 1` are normal children, the same indices with the highest bit set
+/// are hardened children. Hardened children can only be derived from a private key.
+///
+/// # Example
+///
+/// ```
+/// use keyphrase::ChildNumber;
+///
+/// let child: ChildNumber = "44'".parse().unwrap();
+///
+/// assert!(child.is_hardened());
+/// assert_eq!(child.index(), 44);
+/// assert_eq!(u32::from(child), 0x8000_002C);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ChildNumber(u32);
+
+impl ChildNumber {
+    /// Create a normal child number
+    ///
+    /// Returns an `Error` of kind `ErrorKind::InvalidChildIndex` if `index` is `2^31` or above.
+    pub fn normal(index: u32) -> Result<ChildNumber, Error> {
+        if index & HARDENED_BIT != 0 {
+            return Err(ErrorKind::InvalidChildIndex(index).into());
+        }
+
+        Ok(ChildNumber(index))
+    }
+
+    /// Create a hardened child number
+    ///
+    /// Returns an `Error` of kind `ErrorKind::InvalidChildIndex` if `index` is `2^31` or above.
+    pub fn hardened(index: u32) -> Result<ChildNumber, Error> {
+        ChildNumber::normal(index).map(|child: ChildNumber| ChildNumber(child.0 | HARDENED_BIT))
+    }
+
+    /// Whether this is a hardened child number
+    pub fn is_hardened(self) -> bool {
+        self.0 & HARDENED_BIT != 0
+    }
+
+    /// Get the index without the hardened bit
+    pub fn index(self) -> u32 {
+        self.0 & !HARDENED_BIT
+    }
+}
+
+impl From<u32> for ChildNumber {
+    fn from(val: u32) -> ChildNumber {
+        ChildNumber(val)
+    }
+}
+
+impl From<ChildNumber> for u32 {
+    fn from(val: ChildNumber) -> u32 {
+        val.0
+    }
+}
+
+impl fmt::Display for ChildNumber {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.is_hardened() {
+            write!(f, "{}'", self.index())
+        } else {
+            write!(f, "{}", self.index())
+        }
+    }
+}
+
+impl FromStr for ChildNumber {
+    type Err = Error;
+
+    /// Parse a child number, hardened ones are marked with a trailing `'`, `h` or `H`
+    fn from_str(child: &str) -> Result<ChildNumber, Error> {
+        let invalid = || ErrorKind::InvalidDerivationPath(child.to_string());
+
+        let (index, hardened): (&str, bool) = match child.strip_suffix(&['\'', 'h', 'H'][..]) {
+            Some(index) => (index, true),
+            None => (child, false),
+        };
+
+        // `u32::from_str` would also accept a leading `+`
+        if index.is_empty() || !index.bytes().all(|b: u8| b.is_ascii_digit()) {
+            return Err(invalid().into());
+        }
+
+        let index: u32 = index.parse().map_err(|_| invalid())?;
+
+        if hardened {
+            ChildNumber::hardened(index)
+        } else {
+            ChildNumber::normal(index)
+        }
+    }
+}
+
+/// A list of [`ChildNumber`][ChildNumber]s leading from the master key to a descendant key
+///
+/// # Example
+///
+/// ```
+/// use keyphrase::DerivationPath;
+///
+/// let path: DerivationPath = "m/44'/0'/0'/0/0".parse().unwrap();
+///
+/// assert_eq!(path.as_ref().len(), 5);
+/// assert_eq!(path.to_string(), "m/44'/0'/0'/0/0");
+/// ```
+///
+/// [ChildNumber]: ./struct.ChildNumber.html
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct DerivationPath(Vec<ChildNumber>);
+
+impl DerivationPath {
+    /// Iterate over the child numbers in the path, starting from the master key
+    pub fn iter(&self) -> std::slice::Iter<'_, ChildNumber> {
+        self.0.iter()
+    }
+}
+
+impl AsRef<[ChildNumber]> for DerivationPath {
+    fn as_ref(&self) -> &[ChildNumber] {
+        &self.0
+    }
+}
+
+impl From<Vec<ChildNumber>> for DerivationPath {
+    fn from(val: Vec<ChildNumber>) -> DerivationPath {
+        DerivationPath(val)
+    }
+}
+
+impl fmt::Display for DerivationPath {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("m")?;
+
+        for child in &self.0 {
+            write!(f, "/{}", child)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl FromStr for DerivationPath {
+    type Err = Error;
+
+    fn from_str(path: &str) -> Result<DerivationPath, Error> {
+        let mut parts = path.split('/');
+
+        if parts.next() != Some("m") {
+            return Err(ErrorKind::InvalidDerivationPath(path.to_string()).into());
+        }
+
+        parts
+            .map(ChildNumber::from_str)
+            .collect::<Result<Vec<ChildNumber>, Error>>()
+            .map(DerivationPath)
+            .map_err(|_| ErrorKind::InvalidDerivationPath(path.to_string()).into())
+    }
+}
+
+/// HMAC-SHA512 over the concatenation of `data`, split into its left and right halves
+pub(crate) fn hmac_sha512(key: &[u8], data: &[&[u8]]) -> ([u8; 32], [u8; 32]) {
+    let mut mac = Hmac::<Sha512>::new_varkey(key).expect("HMAC accepts keys of any length");
+
+    for part in data {
+        mac.input(part);
+    }
+
+    let result = mac.result().code();
+    let mut left = [0u8; 32];
+    let mut right = [0u8; 32];
+
+    left.copy_from_slice(&result[..32]);
+    right.copy_from_slice(&result[32..]);
+
+    (left, right)
+}
+
+pub(crate) fn hash160(input: &[u8]) -> [u8; 20] {
+    let mut hash = [0u8; 20];
+
+    hash.copy_from_slice(&Ripemd160::digest(&Sha256::digest(input)));
+
+    hash
+}
+
+pub(crate) fn fingerprint(identifier: &[u8; 20]) -> [u8; 4] {
+    let mut fingerprint = [0u8; 4];
+
+    fingerprint.copy_from_slice(&identifier[..4]);
+
+    fingerprint
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn parse_child_number() {
+        assert_eq!("0".parse::<ChildNumber>().unwrap(), ChildNumber(0));
+        assert_eq!(
+            "1'".parse::<ChildNumber>().unwrap(),
+            ChildNumber(HARDENED_BIT | 1)
+        );
+        assert_eq!(
+            "1h".parse::<ChildNumber>().unwrap(),
+            ChildNumber(HARDENED_BIT | 1)
+        );
+        assert_eq!(
+            "1H".parse::<ChildNumber>().unwrap(),
+            ChildNumber(HARDENED_BIT | 1)
+        );
+        assert_eq!(
+            "2147483647'".parse::<ChildNumber>().unwrap(),
+            ChildNumber(u32::MAX)
+        );
+
+        assert!("2147483648".parse::<ChildNumber>().is_err());
+        assert!("+1".parse::<ChildNumber>().is_err());
+        assert!("'".parse::<ChildNumber>().is_err());
+        assert!("1''".parse::<ChildNumber>().is_err());
+        assert!("".parse::<ChildNumber>().is_err());
+    }
+
+    #[test]
+    fn parse_derivation_path() {
+        let path: DerivationPath = "m/44'/0h/0H/0/1".parse().unwrap();
+
+        assert_eq!(
+            path.as_ref(),
+            &[
+                ChildNumber::hardened(44).unwrap(),
+                ChildNumber::hardened(0).unwrap(),
+                ChildNumber::hardened(0).unwrap(),
+                ChildNumber::normal(0).unwrap(),
+                ChildNumber::normal(1).unwrap(),
+            ]
+        );
+        assert_eq!(path.to_string(), "m/44'/0'/0'/0/1");
+        assert_eq!(
+            "m".parse::<DerivationPath>().unwrap(),
+            DerivationPath::default()
+        );
+
+        assert!("".parse::<DerivationPath>().is_err());
+        assert!("44'/0'".parse::<DerivationPath>().is_err());
+        assert!("m/".parse::<DerivationPath>().is_err());
+        assert!("m/0//1".parse::<DerivationPath>().is_err());
+        assert!("M/0".parse::<DerivationPath>().is_err());
+    }
+}
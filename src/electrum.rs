@@ -0,0 +1,639 @@
+//! [Electrum](https://electrum.readthedocs.io/en/latest/seedphrase.html) seed phrases, for users
+//! moving their wallets over from Electrum.
+//!
+//! Electrum 2.0 and later use their own seed format. The words come from the same word lists as
+//! a [`KeyPhrase`][KeyPhrase], but there is no checksum: instead the HMAC-SHA512 of the phrase,
+//! keyed with `"Seed version"`, has to start with the hex prefix of the wallet type. The seed is
+//! derived with PBKDF2 like for BIP39, only salted with `"electrum"` instead of `"mnemonic"`.
+//!
+//! The older format of Electrum 1.x is a 128 or 256 bit seed, written either as hex or as 12 or
+//! 24 words of a 1626 word list. That word list is not part of this crate, it has to be supplied
+//! as an [`ElectrumOldWordList`][ElectrumOldWordList] to read phrases in words.
+//!
+//! The formats overlap: a phrase can be a valid [`KeyPhrase`][KeyPhrase] and a valid Electrum
+//! seed at the same time, and restore a different wallet with each. Use
+//! [`PhraseFormat::detect()`][PhraseFormat::detect()] to find every format a phrase is valid in,
+//! and ask the user which one they meant when there is more than one.
+//!
+//! [KeyPhrase]: ./struct.KeyPhrase.html
+//! [ElectrumOldWordList]: ./struct.ElectrumOldWordList.html
+//! [PhraseFormat::detect()]: ./enum.PhraseFormat.html#method.detect
+
+use crate::crypto::{pbkdf2, sha256, PBKDF2_BYTES, PBKDF2_ROUNDS};
+use crate::error::{Error, ErrorKind};
+use crate::keyphrase::KeyPhrase;
+use crate::language::{Language, WordList};
+use crate::seed::Seed;
+use crate::seed_options::Pbkdf2Hash;
+use crate::util::{from_hex, to_hex, Bits11, IterExt};
+use hashbrown::HashMap;
+use hmac::{Hmac, Mac};
+use rand::{thread_rng, RngCore};
+use sha2::Sha512;
+use std::fs;
+use std::path::Path;
+use std::str::FromStr;
+use unicode_normalization::char::canonical_combining_class;
+use unicode_normalization::UnicodeNormalization;
+
+/// HMAC key of the version hash that has to start with the seed type prefix
+const VERSION_HMAC_KEY: &[u8] = b"Seed version";
+
+/// Salt prefix of the seed derivation, followed by the passphrase
+const SEED_SALT: &str = "electrum";
+
+/// Number of words a new seed is drawn with, 132 bits
+const NEW_SEED_WORDS: usize = 12;
+
+/// Number of words in the word list of old seeds
+const OLD_WORDLIST_LENGTH: usize = 1626;
+
+/// Rounds of SHA256 stretching an old seed into the master private key
+const OLD_STRETCH_ROUNDS: usize = 100_000;
+
+/// Unicode blocks Electrum treats as CJK, spaces between two of their characters are dropped
+const CJK_INTERVALS: &[(u32, u32)] = &[
+    (0x4E00, 0x9FFF),
+    (0x3400, 0x4DBF),
+    (0x20000, 0x2A6DF),
+    (0x2A700, 0x2B73F),
+    (0x2B740, 0x2B81F),
+    (0xF900, 0xFAFF),
+    (0x2F800, 0x2FA1D),
+    (0x3190, 0x319F),
+    (0x2E80, 0x2EFF),
+    (0x2F00, 0x2FDF),
+    (0x31C0, 0x31EF),
+    (0x2FF0, 0x2FFF),
+    (0xE0100, 0xE01EF),
+    (0x3100, 0x312F),
+    (0x31A0, 0x31BF),
+    (0xFF00, 0xFFEF),
+    (0x3040, 0x309F),
+    (0x30A0, 0x30FF),
+    (0x31F0, 0x31FF),
+    (0x1B000, 0x1B0FF),
+    (0xAC00, 0xD7AF),
+    (0x1100, 0x11FF),
+    (0xA960, 0xA97F),
+    (0xD7B0, 0xD7FF),
+    (0x3130, 0x318F),
+    (0xA4D0, 0xA4FF),
+    (0x16F00, 0x16F9F),
+    (0xA000, 0xA48F),
+    (0xA490, 0xA4CF),
+];
+
+/// The wallet type of an Electrum seed, given by the prefix of its version hash
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ElectrumSeedType {
+    /// Legacy P2PKH wallet, prefix `01`
+    Standard,
+    /// Native segwit P2WPKH wallet, prefix `100`
+    Segwit,
+    /// Two-factor authentication wallet, prefix `101`
+    ///
+    /// Only phrases of 12 words, or 20 words and more, are of this type.
+    TwoFactor,
+}
+
+impl ElectrumSeedType {
+    /// Get the hex prefix the version hash of a seed of this type starts with
+    pub fn prefix(self) -> &'static str {
+        match self {
+            ElectrumSeedType::Standard => "01",
+            ElectrumSeedType::Segwit => "100",
+            ElectrumSeedType::TwoFactor => "101",
+        }
+    }
+
+    /// Find the type of a phrase already normalized with `normalize_text()`
+    fn of_normalized(normalized: &str) -> Option<ElectrumSeedType> {
+        let mut mac =
+            Hmac::<Sha512>::new_varkey(VERSION_HMAC_KEY).expect("HMAC accepts keys of any length");
+        mac.input(normalized.as_bytes());
+
+        let version: String = to_hex(&mac.result().code()[..2]);
+        let word_count: usize = normalized.split(' ').count();
+
+        [
+            ElectrumSeedType::Standard,
+            ElectrumSeedType::Segwit,
+            ElectrumSeedType::TwoFactor,
+        ]
+        .iter()
+        .copied()
+        .filter(|seed_type: &ElectrumSeedType| {
+            *seed_type != ElectrumSeedType::TwoFactor || word_count == 12 || word_count >= 20
+        })
+        .find(|seed_type: &ElectrumSeedType| version.starts_with(seed_type.prefix()))
+    }
+}
+
+/// An Electrum 2.0 and later seed phrase
+///
+/// Electrum's `102` prefix of two-factor segwit wallets is not supported.
+///
+/// # Example
+///
+/// ```
+/// use keyphrase::{ElectrumSeed, ElectrumSeedType, Language};
+///
+/// let electrum = ElectrumSeed::new(ElectrumSeedType::Segwit, Language::English);
+///
+/// let restored = ElectrumSeed::from_phrase(electrum.phrase()).unwrap();
+///
+/// assert_eq!(restored.seed_type(), ElectrumSeedType::Segwit);
+/// assert_eq!(restored.to_seed("").as_bytes(), electrum.to_seed("").as_bytes());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ElectrumSeed {
+    phrase: String,
+    seed_type: ElectrumSeedType,
+}
+
+impl ElectrumSeed {
+    /// Generate a new 12 word seed of `seed_type` with the operating system's random number
+    /// generator
+    pub fn new(seed_type: ElectrumSeedType, lang: Language) -> ElectrumSeed {
+        ElectrumSeed::new_with_rng(seed_type, lang, &mut thread_rng())
+    }
+
+    /// Generate a new 12 word seed of `seed_type` with the given random number generator
+    ///
+    /// Like Electrum, 132 random bits with a nonzero first word are counted up until the phrase
+    /// has the version prefix of `seed_type`, which takes 256 tries on average for a standard
+    /// seed and 4096 for the others. Unlike Electrum, the phrase is not checked against the old
+    /// word list, which is not part of this crate. Only use a cryptographically secure generator
+    /// for seeds that protect anything, a seeded one is meant for tests.
+    pub fn new_with_rng<R>(seed_type: ElectrumSeedType, lang: Language, rng: &mut R) -> ElectrumSeed
+    where
+        R: RngCore + ?Sized,
+    {
+        let wordlist: &WordList = lang.wordlist();
+
+        // Word indices, least significant first like Electrum's mnemonic_encode
+        let mut digits: Vec<u16> = loop {
+            let digits: Vec<u16> = (0..NEW_SEED_WORDS)
+                .map(|_| (rng.next_u32() & 0x7FF) as u16)
+                .collect();
+
+            if digits[NEW_SEED_WORDS - 1] != 0 {
+                break digits;
+            }
+        };
+
+        loop {
+            increment(&mut digits);
+
+            let phrase: String = digits
+                .iter()
+                .map(|digit: &u16| wordlist.get_word(Bits11::from(*digit)))
+                .join(" ");
+
+            if ElectrumSeedType::of_normalized(&normalize_text(&phrase)) == Some(seed_type) {
+                return ElectrumSeed { phrase, seed_type };
+            }
+        }
+    }
+
+    /// Read an existing Electrum seed phrase
+    ///
+    /// The phrase is normalized like Electrum does: to NFKD and lower case, without accents or
+    /// repeated whitespace. The words are not looked up in any word list, as Electrum doesn't
+    /// either. Returns an `Error` of kind `ErrorKind::InvalidElectrumSeed` if the version hash
+    /// has none of the supported prefixes.
+    ///
+    /// This does not tell whether the phrase is also valid in another format, see
+    /// [`PhraseFormat::detect()`][PhraseFormat::detect()] for that.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use keyphrase::{ElectrumSeed, ElectrumSeedType};
+    ///
+    /// let phrase = "wild father tree among universe such mobile favorite target dynamic credit identify";
+    /// let electrum = ElectrumSeed::from_phrase(phrase).unwrap();
+    ///
+    /// assert_eq!(electrum.seed_type(), ElectrumSeedType::Segwit);
+    /// ```
+    ///
+    /// [PhraseFormat::detect()]: ./enum.PhraseFormat.html#method.detect
+    pub fn from_phrase(phrase: &str) -> Result<ElectrumSeed, Error> {
+        match ElectrumSeedType::of_normalized(&normalize_text(phrase)) {
+            Some(seed_type) => Ok(ElectrumSeed {
+                phrase: phrase.to_string(),
+                seed_type,
+            }),
+            None => Err(ErrorKind::InvalidElectrumSeed.into()),
+        }
+    }
+
+    /// Get the phrase as it was generated or given
+    pub fn phrase(&self) -> &str {
+        &self.phrase
+    }
+
+    /// Get the wallet type of the seed
+    pub fn seed_type(&self) -> ElectrumSeedType {
+        self.seed_type
+    }
+
+    /// Derive the 64 byte wallet seed, protected by `passphrase`
+    ///
+    /// The passphrase is normalized like the phrase, so it is not case sensitive. The result
+    /// is the seed Electrum creates its master key from with BIP32.
+    pub fn to_seed(&self, passphrase: &str) -> Seed {
+        let salt: String = format!("{}{}", SEED_SALT, normalize_text(passphrase));
+        let bytes: Vec<u8> = pbkdf2(
+            normalize_text(&self.phrase).as_bytes(),
+            salt.as_bytes(),
+            Pbkdf2Hash::Sha512,
+            PBKDF2_ROUNDS,
+            PBKDF2_BYTES,
+        );
+
+        Seed::from_bytes(&bytes).expect("64 bytes is a valid seed length")
+    }
+}
+
+/// The 1626 word list of Electrum 1.x seeds
+///
+/// Validated like a [`CustomLanguage`][CustomLanguage], with 1626 unique words that are already
+/// normalized like Electrum normalizes phrases: NFKD, lower case and without accents.
+///
+/// [CustomLanguage]: ./struct.CustomLanguage.html
+#[derive(Clone, PartialEq, Eq)]
+pub struct ElectrumOldWordList {
+    words: Vec<String>,
+    indices: HashMap<String, u32>,
+}
+
+impl ElectrumOldWordList {
+    /// Create an [`ElectrumOldWordList`][ElectrumOldWordList] from a list of words, in index order
+    ///
+    /// [ElectrumOldWordList]: ./struct.ElectrumOldWordList.html
+    pub fn from_words<I, S>(words: I) -> Result<ElectrumOldWordList, Error>
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        let words: Vec<String> = words.into_iter().map(Into::into).collect();
+
+        if words.len() != OLD_WORDLIST_LENGTH {
+            return Err(ErrorKind::InvalidWordListLength(words.len()).into());
+        }
+
+        let mut indices: HashMap<String, u32> = HashMap::with_capacity(OLD_WORDLIST_LENGTH);
+
+        for (index, word) in words.iter().enumerate() {
+            if word.is_empty() {
+                return Err(ErrorKind::EmptyWord(index).into());
+            }
+            if word.chars().any(char::is_whitespace) {
+                return Err(ErrorKind::WhitespaceInWord(word.to_string()).into());
+            }
+            if normalize_text(word) != *word {
+                return Err(ErrorKind::UnnormalizedWord(word.to_string()).into());
+            }
+            if indices.insert(word.to_string(), index as u32).is_some() {
+                return Err(ErrorKind::DuplicateWord(word.to_string()).into());
+            }
+        }
+
+        Ok(ElectrumOldWordList { words, indices })
+    }
+
+    /// Read an [`ElectrumOldWordList`][ElectrumOldWordList] from a file with one word per line
+    ///
+    /// [ElectrumOldWordList]: ./struct.ElectrumOldWordList.html
+    pub fn from_file<P: AsRef<Path>>(path: P) -> Result<ElectrumOldWordList, Error> {
+        fs::read_to_string(path)?.parse()
+    }
+
+    fn index_of(&self, position: usize, word: &str) -> Result<u32, Error> {
+        self.indices
+            .get(word)
+            .copied()
+            .ok_or_else(|| ErrorKind::InvalidWord { position }.into())
+    }
+}
+
+impl FromStr for ElectrumOldWordList {
+    type Err = Error;
+
+    /// Parse a word list with one word per line
+    ///
+    /// A trailing newline at the end of the list is allowed, as are `\r\n` line endings.
+    fn from_str(words: &str) -> Result<ElectrumOldWordList, Error> {
+        let words: &str = words.strip_suffix('\n').unwrap_or(words);
+
+        ElectrumOldWordList::from_words(
+            words
+                .split('\n')
+                .map(|line: &str| line.strip_suffix('\r').unwrap_or(line)),
+        )
+    }
+}
+
+impl std::fmt::Debug for ElectrumOldWordList {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "ElectrumOldWordList({} words)", self.words.len())
+    }
+}
+
+/// An Electrum 1.x seed of 128 or 256 bits
+///
+/// # Example
+///
+/// ```
+/// use keyphrase::ElectrumOldSeed;
+///
+/// let electrum = ElectrumOldSeed::from_hex("acb740e454c3134901d7c8f16497cc1c").unwrap();
+///
+/// assert_eq!(electrum.to_hex(), "acb740e454c3134901d7c8f16497cc1c");
+/// assert_eq!(electrum.master_private_key()[..4], [0x21, 0xb8, 0x80, 0xfd]);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ElectrumOldSeed {
+    bytes: Vec<u8>,
+}
+
+impl ElectrumOldSeed {
+    /// Read a seed written as 32 or 64 hex digits
+    ///
+    /// Returns an `Error` of kind `ErrorKind::InvalidHexCharacter` or `ErrorKind::InvalidHexLength`
+    /// if the hex is not valid, and `ErrorKind::InvalidElectrumSeedLength` if it is not 16 or 32
+    /// bytes long.
+    pub fn from_hex(hex: &str) -> Result<ElectrumOldSeed, Error> {
+        ElectrumOldSeed::from_bytes(&from_hex(hex.trim())?)
+    }
+
+    /// Read a seed phrase of 12 or 24 words from `wordlist`
+    ///
+    /// The phrase is normalized like for [`ElectrumSeed::from_phrase()`][ElectrumSeed::from_phrase()].
+    /// Returns an `Error` of kind `ErrorKind::InvalidWordLength` for any other number of words,
+    /// `ErrorKind::InvalidWord` for a word that is not in the word list, and
+    /// `ErrorKind::InvalidElectrumSeed` for three words that don't fit in 32 bits, which
+    /// Electrum never writes.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use keyphrase::{ElectrumOldSeed, ElectrumOldWordList};
+    ///
+    /// // Stand-in for Electrum's old_mnemonic.py word list
+    /// let wordlist = ElectrumOldWordList::from_words((0..1626).map(|i| format!("w{}", i))).unwrap();
+    ///
+    /// let electrum = ElectrumOldSeed::from_hex("00000000000000000000000000000001").unwrap();
+    /// let phrase: String = electrum.to_phrase(&wordlist);
+    ///
+    /// assert_eq!(phrase, "w0 w0 w0 w0 w0 w0 w0 w0 w0 w1 w1 w1");
+    /// assert_eq!(ElectrumOldSeed::from_phrase(&phrase, &wordlist).unwrap(), electrum);
+    /// ```
+    ///
+    /// [ElectrumSeed::from_phrase()]: ./struct.ElectrumSeed.html#method.from_phrase
+    pub fn from_phrase(
+        phrase: &str,
+        wordlist: &ElectrumOldWordList,
+    ) -> Result<ElectrumOldSeed, Error> {
+        let normalized: String = normalize_text(phrase);
+        let words: Vec<&str> = normalized.split_whitespace().collect();
+
+        if words.len() != 12 && words.len() != 24 {
+            return Err(ErrorKind::InvalidWordLength(words.len()).into());
+        }
+
+        let indices: Vec<u64> = words
+            .iter()
+            .enumerate()
+            .map(|(position, word): (usize, &&str)| {
+                wordlist.index_of(position, word).map(u64::from)
+            })
+            .collect::<Result<_, _>>()?;
+
+        let n: u64 = OLD_WORDLIST_LENGTH as u64;
+        let mut bytes: Vec<u8> = Vec::with_capacity(words.len() / 3 * 4);
+
+        for triple in indices.chunks(3) {
+            let (w1, w2, w3): (u64, u64, u64) = (triple[0], triple[1], triple[2]);
+
+            let x: u64 = w1 + n * ((w2 + n - w1) % n) + n * n * ((w3 + n - w2) % n);
+
+            if x > u32::MAX as u64 {
+                return Err(ErrorKind::InvalidElectrumSeed.into());
+            }
+
+            bytes.extend_from_slice(&(x as u32).to_be_bytes());
+        }
+
+        ElectrumOldSeed::from_bytes(&bytes)
+    }
+
+    fn from_bytes(bytes: &[u8]) -> Result<ElectrumOldSeed, Error> {
+        if bytes.len() != 16 && bytes.len() != 32 {
+            return Err(ErrorKind::InvalidElectrumSeedLength(bytes.len()).into());
+        }
+
+        Ok(ElectrumOldSeed {
+            bytes: bytes.to_vec(),
+        })
+    }
+
+    /// Write the seed as lowercase hex, the form Electrum stretches into the master key
+    pub fn to_hex(&self) -> String {
+        to_hex(&self.bytes)
+    }
+
+    /// Write the seed as words from `wordlist`, three words for every 32 bits
+    pub fn to_phrase(&self, wordlist: &ElectrumOldWordList) -> String {
+        let n: u32 = OLD_WORDLIST_LENGTH as u32;
+
+        self.bytes
+            .chunks(4)
+            .flat_map(|chunk: &[u8]| {
+                let x: u32 = u32::from_be_bytes([chunk[0], chunk[1], chunk[2], chunk[3]]);
+                let w1: u32 = x % n;
+                let w2: u32 = (x / n + w1) % n;
+                let w3: u32 = (x / n / n + w2) % n;
+
+                vec![w1, w2, w3]
+            })
+            .map(|index: u32| wordlist.words[index as usize].as_str())
+            .join(" ")
+    }
+
+    /// Derive the master private key, 100000 rounds of SHA256 over the hex seed
+    ///
+    /// This is the secp256k1 private key of the whole wallet, old seeds don't use BIP32.
+    pub fn master_private_key(&self) -> [u8; 32] {
+        let seed: String = self.to_hex();
+        let mut key: Vec<u8> = seed.as_bytes().to_vec();
+
+        for _ in 0..OLD_STRETCH_ROUNDS {
+            key.extend_from_slice(seed.as_bytes());
+            key = sha256(&key).to_vec();
+        }
+
+        let mut output = [0u8; 32];
+        output.copy_from_slice(&key);
+
+        output
+    }
+}
+
+/// A format a seed phrase can be valid in, see [`PhraseFormat::detect()`][PhraseFormat::detect()]
+///
+/// [PhraseFormat::detect()]: ./enum.PhraseFormat.html#method.detect
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PhraseFormat {
+    /// A BIP39 [`KeyPhrase`][KeyPhrase]
+    ///
+    /// [KeyPhrase]: ./struct.KeyPhrase.html
+    Bip39,
+    /// An Electrum 2.0 and later [`ElectrumSeed`][ElectrumSeed] of the given type
+    ///
+    /// [ElectrumSeed]: ./struct.ElectrumSeed.html
+    Electrum(ElectrumSeedType),
+    /// An Electrum 1.x [`ElectrumOldSeed`][ElectrumOldSeed]
+    ///
+    /// [ElectrumOldSeed]: ./struct.ElectrumOldSeed.html
+    ElectrumOld,
+}
+
+impl PhraseFormat {
+    /// Find every format `phrase` is valid in
+    ///
+    /// The phrase is checked as a BIP39 [`KeyPhrase`][KeyPhrase] in `lang`, as an Electrum seed,
+    /// and as a hex Electrum 1.x seed. Old seeds in words are only found by
+    /// [`PhraseFormat::detect_with_old_words()`][PhraseFormat::detect_with_old_words()].
+    ///
+    /// Nothing is picked for the caller: when more than one format comes back, each restores a
+    /// different wallet, so the user has to be asked which one they meant. No format at all
+    /// means the phrase is invalid, or mistyped.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use keyphrase::{ElectrumSeedType, Language, PhraseFormat};
+    ///
+    /// let phrase = "wild father tree among universe such mobile favorite target dynamic credit identify";
+    /// assert_eq!(
+    ///     PhraseFormat::detect(phrase, Language::English),
+    ///     [PhraseFormat::Electrum(ElectrumSeedType::Segwit)]
+    /// );
+    ///
+    /// let phrase = "park remain person kitchen mule spell knee armed position rail grid ankle";
+    /// assert_eq!(PhraseFormat::detect(phrase, Language::English), [PhraseFormat::Bip39]);
+    /// ```
+    ///
+    /// [KeyPhrase]: ./struct.KeyPhrase.html
+    /// [PhraseFormat::detect_with_old_words()]: ./enum.PhraseFormat.html#method.detect_with_old_words
+    pub fn detect(phrase: &str, lang: Language) -> Vec<PhraseFormat> {
+        let mut formats: Vec<PhraseFormat> = Vec::new();
+
+        if KeyPhrase::validate(phrase, lang).is_ok() {
+            formats.push(PhraseFormat::Bip39);
+        }
+
+        if let Ok(electrum) = ElectrumSeed::from_phrase(phrase) {
+            formats.push(PhraseFormat::Electrum(electrum.seed_type()));
+        }
+
+        if ElectrumOldSeed::from_hex(phrase).is_ok() {
+            formats.push(PhraseFormat::ElectrumOld);
+        }
+
+        formats
+    }
+
+    /// Find every format `phrase` is valid in, including Electrum 1.x seeds in words from
+    /// `old_words`
+    ///
+    /// See [`PhraseFormat::detect()`][PhraseFormat::detect()].
+    ///
+    /// [PhraseFormat::detect()]: ./enum.PhraseFormat.html#method.detect
+    pub fn detect_with_old_words(
+        phrase: &str,
+        lang: Language,
+        old_words: &ElectrumOldWordList,
+    ) -> Vec<PhraseFormat> {
+        let mut formats: Vec<PhraseFormat> = PhraseFormat::detect(phrase, lang);
+
+        if !formats.contains(&PhraseFormat::ElectrumOld)
+            && ElectrumOldSeed::from_phrase(phrase, old_words).is_ok()
+        {
+            formats.push(PhraseFormat::ElectrumOld);
+        }
+
+        formats
+    }
+}
+
+/// Add one to the little endian base 2048 number in `digits`, growing it on overflow
+fn increment(digits: &mut Vec<u16>) {
+    for digit in digits.iter_mut() {
+        if *digit < 2047 {
+            *digit += 1;
+            return;
+        }
+
+        *digit = 0;
+    }
+
+    digits.push(1);
+}
+
+/// Normalize text like Electrum's `normalize_text()`
+///
+/// NFKD, lower case, combining marks removed, whitespace collapsed to single spaces and spaces
+/// between two CJK characters removed.
+fn normalize_text(text: &str) -> String {
+    let text: String = text
+        .nfkd()
+        .collect::<String>()
+        .to_lowercase()
+        .chars()
+        .filter(|c: &char| canonical_combining_class(*c) == 0)
+        .collect();
+
+    let text: String = text.split_whitespace().join(" ");
+    let chars: Vec<char> = text.chars().collect();
+
+    chars
+        .iter()
+        .enumerate()
+        .filter(|(i, c)| **c != ' ' || !(is_cjk(chars[*i - 1]) && is_cjk(chars[*i + 1])))
+        .map(|(_, c)| *c)
+        .collect()
+}
+
+fn is_cjk(c: char) -> bool {
+    CJK_INTERVALS
+        .iter()
+        .any(|(start, end)| (*start..=*end).contains(&(c as u32)))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn normalize_like_electrum() {
+        assert_eq!(normalize_text("  Almíbar\tTIBIO \n"), "almibar tibio");
+        assert_eq!(normalize_text("眼 悲  叛"), "眼悲叛");
+        assert_eq!(normalize_text("眼 abc 悲"), "眼 abc 悲");
+        assert_eq!(normalize_text(""), "");
+    }
+
+    #[test]
+    fn increment_carries() {
+        let mut digits: Vec<u16> = vec![2047, 2047, 5];
+        increment(&mut digits);
+        assert_eq!(digits, [0, 0, 6]);
+
+        let mut digits: Vec<u16> = vec![2047, 2047];
+        increment(&mut digits);
+        assert_eq!(digits, [0, 0, 1]);
+    }
+}
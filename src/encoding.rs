@@ -0,0 +1,151 @@
+use crate::error::{Error, ErrorKind};
+use crate::keyphrase::KeyPhrase;
+use crate::language::Language;
+use crate::seed::Seed;
+use crate::util::{from_hex, to_hex};
+use base64::Engine;
+
+/// Text encoding of the entropy of a [`KeyPhrase`][KeyPhrase] or the bytes of a [`Seed`][Seed]
+///
+/// Every encoding round trips any bytes exactly, including leading zero bytes.
+///
+/// # Example
+///
+/// ```
+/// use keyphrase::Encoding;
+///
+/// let bytes: &[u8] = &[0x00, 0x0f, 0xf0, 0xff];
+///
+/// assert_eq!(Encoding::Hex.encode(bytes), "000ff0ff");
+/// assert_eq!(Encoding::Base64.encode(bytes), "AA/w/w==");
+/// assert_eq!(Encoding::Base58.encode(bytes), "16MZg");
+///
+/// for encoding in &[Encoding::Hex, Encoding::Base64, Encoding::Base58] {
+///     assert_eq!(encoding.decode(&encoding.encode(bytes)).unwrap(), bytes);
+/// }
+/// ```
+///
+/// [KeyPhrase]: ./keyphrase/struct.KeyPhrase.html
+/// [Seed]: ./seed/struct.Seed.html
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Encoding {
+    /// Lowercase hex, two digits per byte
+    ///
+    /// Decoding accepts either case and an optional `0x` prefix.
+    Hex,
+    /// Base64 with the standard alphabet and `=` padding
+    Base64,
+    /// Base58 with the Bitcoin alphabet, without a checksum
+    Base58,
+}
+
+impl Encoding {
+    /// Encode bytes as text
+    pub fn encode(self, bytes: &[u8]) -> String {
+        match self {
+            Encoding::Hex => to_hex(bytes),
+            Encoding::Base64 => base64::engine::general_purpose::STANDARD.encode(bytes),
+            Encoding::Base58 => bs58::encode(bytes).into_string(),
+        }
+    }
+
+    /// Decode text back into bytes
+    ///
+    /// Returns an `Error` of kind `ErrorKind::InvalidHexCharacter`,
+    /// `ErrorKind::InvalidHexLength`, `ErrorKind::InvalidBase64` or `ErrorKind::InvalidBase58`
+    /// if the text is not valid in this encoding.
+    pub fn decode(self, encoded: &str) -> Result<Vec<u8>, Error> {
+        match self {
+            Encoding::Hex => from_hex(encoded),
+            Encoding::Base64 => base64::engine::general_purpose::STANDARD
+                .decode(encoded)
+                .map_err(|_| ErrorKind::InvalidBase64.into()),
+            Encoding::Base58 => bs58::decode(encoded)
+                .into_vec()
+                .map_err(|_| ErrorKind::InvalidBase58.into()),
+        }
+    }
+}
+
+impl KeyPhrase {
+    /// Encode the entropy of the keyphrase as text
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use keyphrase::{Encoding, KeyPhrase, Language};
+    ///
+    /// let phrase = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+    /// let keyphrase = KeyPhrase::from_phrase(phrase, Language::English).unwrap();
+    ///
+    /// let encoded: String = keyphrase.encode_entropy(Encoding::Base64);
+    ///
+    /// assert_eq!(encoded, "AAAAAAAAAAAAAAAAAAAAAA==");
+    /// assert_eq!(KeyPhrase::decode_entropy(&encoded, Encoding::Base64, Language::English).unwrap().phrase(), phrase);
+    /// ```
+    pub fn encode_entropy(&self, encoding: Encoding) -> String {
+        encoding.encode(self.entropy())
+    }
+
+    /// Create a [`KeyPhrase`][KeyPhrase] from entropy encoded as text
+    ///
+    /// The entropy is checked like for [`KeyPhrase::from_entropy()`][KeyPhrase::from_entropy()].
+    ///
+    /// [KeyPhrase]: ./keyphrase/struct.KeyPhrase.html
+    /// [KeyPhrase::from_entropy()]: ./keyphrase/struct.KeyPhrase.html#method.from_entropy
+    pub fn decode_entropy(
+        encoded: &str,
+        encoding: Encoding,
+        lang: Language,
+    ) -> Result<KeyPhrase, Error> {
+        KeyPhrase::from_entropy(&encoding.decode(encoded)?, lang)
+    }
+}
+
+impl Seed {
+    /// Encode the seed as text
+    pub fn encode(&self, encoding: Encoding) -> String {
+        encoding.encode(self.as_bytes())
+    }
+
+    /// Rebuild a seed from text
+    ///
+    /// The length is checked like for [`Seed::from_bytes()`][Seed::from_bytes()].
+    ///
+    /// [Seed::from_bytes()]: ./seed/struct.Seed.html#method.from_bytes
+    pub fn decode(encoded: &str, encoding: Encoding) -> Result<Seed, Error> {
+        Seed::from_bytes(&encoding.decode(encoded)?)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn leading_zeros_are_kept() {
+        for encoding in &[Encoding::Hex, Encoding::Base64, Encoding::Base58] {
+            for bytes in &[&[][..], &[0], &[0, 0, 1], &[0, 0, 0, 0]] {
+                assert_eq!(&encoding.decode(&encoding.encode(bytes)).unwrap(), bytes);
+            }
+        }
+
+        assert_eq!(Encoding::Base58.encode(&[0, 0, 1]), "112");
+    }
+
+    #[test]
+    fn reject_invalid_text() {
+        let error: Error = Encoding::Base64.decode("AA/w/w=").unwrap_err();
+        assert!(matches!(error.kind(), ErrorKind::InvalidBase64));
+
+        let error: Error = Encoding::Base64.decode("AA-w_w==").unwrap_err();
+        assert!(matches!(error.kind(), ErrorKind::InvalidBase64));
+
+        // 0, O, I and l are not in the Base58 alphabet
+        let error: Error = Encoding::Base58.decode("16MZ0").unwrap_err();
+        assert!(matches!(error.kind(), ErrorKind::InvalidBase58));
+
+        let error: Error = Encoding::Hex.decode("0f0").unwrap_err();
+        assert!(matches!(error.kind(), ErrorKind::InvalidHexLength(3)));
+    }
+}
@@ -1,18 +1,340 @@
 use crate::keyphrase_type::KeyPhraseType;
+use std::error::Error as StdError;
+use std::fmt;
+use std::io;
 
-#[derive(Debug, Fail)]
+/// The error type of every fallible function in this crate
+///
+/// Match on [`Error::kind()`][Error::kind()] to find out what went wrong. Most errors are caused
+/// by bad input, like a mistyped word or a wrong password, and can be shown to the user as they
+/// are. [`Error::is_input_error()`][Error::is_input_error()] tells them apart from the few that
+/// are not.
+///
+/// # Example
+///
+/// ```
+/// use keyphrase::{ErrorKind, KeyPhrase, Language};
+///
+/// let error = KeyPhrase::from_phrase("park remain person", Language::English).unwrap_err();
+///
+/// assert!(matches!(error.kind(), ErrorKind::InvalidWordLength(3)));
+/// assert!(error.is_input_error());
+/// ```
+///
+/// [Error::kind()]: ./struct.Error.html#method.kind
+/// [Error::is_input_error()]: ./struct.Error.html#method.is_input_error
+#[derive(Debug)]
+pub struct Error {
+    kind: ErrorKind,
+}
+
+impl Error {
+    /// Get what went wrong
+    pub fn kind(&self) -> &ErrorKind {
+        &self.kind
+    }
+
+    /// Get what went wrong, by value
+    pub fn into_kind(self) -> ErrorKind {
+        self.kind
+    }
+
+    /// Whether the error was caused by the input given to the function
+    ///
+    /// This is `false` for I/O errors, and for BIP32 and SLIP-10 derivations that hit an invalid
+    /// key, which only happens with a probability below 2<sup>-127</sup>. Those are worth logging
+    /// and retrying, the others are worth showing to whoever gave the input.
+    pub fn is_input_error(&self) -> bool {
+        !matches!(self.kind, ErrorKind::Io(_) | ErrorKind::InvalidChildKey(_))
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.kind.fmt(f)
+    }
+}
+
+impl StdError for Error {
+    fn source(&self) -> Option<&(dyn StdError + 'static)> {
+        match &self.kind {
+            ErrorKind::Io(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<ErrorKind> for Error {
+    fn from(kind: ErrorKind) -> Error {
+        Error { kind }
+    }
+}
+
+impl From<io::Error> for Error {
+    fn from(err: io::Error) -> Error {
+        ErrorKind::Io(err).into()
+    }
+}
+
+/// What went wrong, see [`Error`][Error]
+///
+/// New kinds can be added without a major version, so matches need a wildcard arm.
+///
+/// [Error]: ./struct.Error.html
+#[derive(Debug)]
+#[non_exhaustive]
 pub enum ErrorKind {
-    #[fail(display = "invalid checksum")]
     InvalidChecksum,
-    #[fail(display = "invalid word in phrase")]
-    InvalidWord,
-    #[fail(display = "invalid keysize: {}", _0)]
+    InvalidWord { position: usize },
     InvalidKeysize(usize),
-    #[fail(display = "invalid number of words in phrase: {}", _0)]
     InvalidWordLength(usize),
-    #[fail(
-        display = "invalid entropy length {}bits for keyphrase type {:?}",
-        _0, _1
-    )]
     InvalidEntropyLength(usize, KeyPhraseType),
+    TooManyUnknownWords(usize),
+    InvalidWordListLength(usize),
+    EmptyWord(usize),
+    WhitespaceInWord(String),
+    UnnormalizedWord(String),
+    DuplicateWord(String),
+    AmbiguousPrefix(String, String, usize),
+    UnknownLanguage(String),
+    InvalidSeedLength(usize),
+    InvalidDerivationPath(String),
+    InvalidChildIndex(u32),
+    HardenedPublicDerivation,
+    InvalidChildKey(u32),
+    MaxDepthExceeded,
+    InvalidBase58Check,
+    InvalidKeyVersion(u32),
+    InvalidExtendedKey,
+    NormalEd25519Derivation(u32),
+    UnsupportedLanguage(String),
+    InvalidBip85Length(usize),
+    InvalidSecretLength(usize),
+    InvalidIterationExponent(u8),
+    InvalidThreshold(u8, u8),
+    InvalidGroupIndex(u8, u8),
+    NotEnoughShares(u8, usize),
+    MismatchedShares,
+    InvalidShareWordCount(usize),
+    InvalidShareChecksum,
+    InvalidSharePadding,
+    InvalidShareDigest,
+    InvalidPassphrase,
+    InvalidPartCount(usize),
+    MismatchedParts,
+    InvalidCodex32Prefix,
+    MixedCaseCodex32,
+    InvalidCodex32Character(char),
+    InvalidCodex32Length(usize),
+    InvalidCodex32Threshold(char),
+    InvalidCodex32Identifier(String),
+    InvalidShareIndex(char),
+    InvalidWordIndex(u16),
+    InvalidIndexRow(String),
+    InvalidSeedQrLength(usize),
+    InvalidSeedQrCharacter(char),
+    InvalidKeystore(String),
+    UnsupportedKeystoreVersion(u32),
+    InvalidKeystoreParams(u8, u32, u32),
+    KeystoreDecryption,
+    InvalidSeedOptions(String),
+    InvalidHexCharacter(char),
+    InvalidHexLength(usize),
+    InvalidBase64,
+    InvalidBase58,
+    InvalidDataLength(usize),
+    InvalidChecksumLength(u8),
+    InvalidElectrumSeed,
+    InvalidElectrumSeedLength(usize),
+    Io(io::Error),
+}
+
+impl fmt::Display for ErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ErrorKind::InvalidChecksum => f.write_str("invalid checksum"),
+            ErrorKind::InvalidWord { position } => {
+                write!(f, "invalid word at index {}", position)
+            }
+            ErrorKind::InvalidKeysize(value) => write!(f, "invalid keysize: {}", value),
+            ErrorKind::InvalidWordLength(value) => {
+                write!(f, "invalid number of words in phrase: {}", value)
+            }
+            ErrorKind::InvalidEntropyLength(bits, keyphrase_type) => write!(
+                f,
+                "invalid entropy length {}bits for keyphrase type {:?}",
+                bits, keyphrase_type
+            ),
+            ErrorKind::TooManyUnknownWords(value) => {
+                write!(f, "too many unknown words in phrase: {}", value)
+            }
+            ErrorKind::InvalidWordListLength(value) => {
+                write!(f, "invalid number of words in word list: {}", value)
+            }
+            ErrorKind::EmptyWord(value) => write!(f, "empty word in word list at index {}", value),
+            ErrorKind::WhitespaceInWord(value) => {
+                write!(f, "word contains whitespace: {:?}", value)
+            }
+            ErrorKind::UnnormalizedWord(value) => {
+                write!(f, "word is not in NFKD form: {:?}", value)
+            }
+            ErrorKind::DuplicateWord(value) => {
+                write!(f, "duplicate word in word list: {:?}", value)
+            }
+            ErrorKind::AmbiguousPrefix(first, second, len) => write!(
+                f,
+                "words {:?} and {:?} share the same first {} characters",
+                first, second, len
+            ),
+            ErrorKind::UnknownLanguage(value) => write!(f, "unknown language: {}", value),
+            ErrorKind::InvalidSeedLength(value) => {
+                write!(f, "invalid seed length: {} bytes", value)
+            }
+            ErrorKind::InvalidDerivationPath(value) => {
+                write!(f, "invalid derivation path: {}", value)
+            }
+            ErrorKind::InvalidChildIndex(value) => write!(f, "child index out of range: {}", value),
+            ErrorKind::HardenedPublicDerivation => {
+                f.write_str("hardened child keys can't be derived from a public key")
+            }
+            ErrorKind::InvalidChildKey(value) => write!(
+                f,
+                "derived key for child {:#x} is invalid, use the next index",
+                value
+            ),
+            ErrorKind::MaxDepthExceeded => f.write_str("maximum derivation depth exceeded"),
+            ErrorKind::InvalidBase58Check => f.write_str("invalid base58check encoding"),
+            ErrorKind::InvalidKeyVersion(value) => {
+                write!(f, "unexpected extended key version: {:#010x}", value)
+            }
+            ErrorKind::InvalidExtendedKey => f.write_str("invalid extended key"),
+            ErrorKind::NormalEd25519Derivation(value) => {
+                write!(f, "ed25519 keys only have hardened children, got {}", value)
+            }
+            ErrorKind::UnsupportedLanguage(value) => {
+                write!(f, "{} has no standard language code", value)
+            }
+            ErrorKind::InvalidBip85Length(value) => {
+                write!(f, "invalid BIP85 output length: {}", value)
+            }
+            ErrorKind::InvalidSecretLength(value) => {
+                write!(f, "invalid secret length: {} bytes", value)
+            }
+            ErrorKind::InvalidIterationExponent(value) => {
+                write!(f, "invalid iteration exponent: {}", value)
+            }
+            ErrorKind::InvalidThreshold(threshold, count) => {
+                write!(f, "invalid threshold: {} of {}", threshold, count)
+            }
+            ErrorKind::InvalidGroupIndex(index, count) => {
+                write!(f, "invalid group index: {} of {} groups", index, count)
+            }
+            ErrorKind::NotEnoughShares(needed, given) => {
+                write!(f, "not enough shares: {} needed, {} given", needed, given)
+            }
+            ErrorKind::MismatchedShares => f.write_str("shares don't belong to the same secret"),
+            ErrorKind::InvalidShareWordCount(value) => {
+                write!(f, "invalid number of words in share: {}", value)
+            }
+            ErrorKind::InvalidShareChecksum => f.write_str("invalid share checksum"),
+            ErrorKind::InvalidSharePadding => f.write_str("invalid share padding"),
+            ErrorKind::InvalidShareDigest => {
+                f.write_str("invalid share digest, the shares don't belong together")
+            }
+            ErrorKind::InvalidPassphrase => {
+                f.write_str("passphrase must only contain printable ASCII characters")
+            }
+            ErrorKind::InvalidPartCount(value) => write!(f, "invalid number of parts: {}", value),
+            ErrorKind::MismatchedParts => f.write_str("parts have different lengths"),
+            ErrorKind::InvalidCodex32Prefix => {
+                f.write_str("codex32 string must start with \"ms1\"")
+            }
+            ErrorKind::MixedCaseCodex32 => f.write_str("codex32 string mixes upper and lower case"),
+            ErrorKind::InvalidCodex32Character(value) => {
+                write!(f, "invalid character in codex32 string: {:?}", value)
+            }
+            ErrorKind::InvalidCodex32Length(value) => {
+                write!(f, "invalid codex32 string length: {}", value)
+            }
+            ErrorKind::InvalidCodex32Threshold(value) => {
+                write!(f, "invalid codex32 threshold: {:?}", value)
+            }
+            ErrorKind::InvalidCodex32Identifier(value) => write!(
+                f,
+                "codex32 identifier must be 4 bech32 characters: {:?}",
+                value
+            ),
+            ErrorKind::InvalidShareIndex(value) => write!(f, "invalid share index: {:?}", value),
+            ErrorKind::InvalidWordIndex(value) => write!(f, "word index out of range: {}", value),
+            ErrorKind::InvalidIndexRow(value) => write!(f, "invalid word index row: {:?}", value),
+            ErrorKind::InvalidSeedQrLength(value) => {
+                write!(f, "invalid SeedQR length: {} digits", value)
+            }
+            ErrorKind::InvalidSeedQrCharacter(value) => {
+                write!(f, "SeedQR must only contain digits, got {:?}", value)
+            }
+            ErrorKind::InvalidKeystore(value) => write!(f, "invalid keystore: {}", value),
+            ErrorKind::UnsupportedKeystoreVersion(value) => {
+                write!(f, "unsupported keystore version: {}", value)
+            }
+            ErrorKind::InvalidKeystoreParams(log_n, r, p) => write!(
+                f,
+                "invalid scrypt parameters: log_n={} r={} p={}",
+                log_n, r, p
+            ),
+            ErrorKind::KeystoreDecryption => {
+                f.write_str("keystore decryption failed, wrong password or modified keystore")
+            }
+            ErrorKind::InvalidSeedOptions(value) => write!(f, "invalid seed options: {}", value),
+            ErrorKind::InvalidHexCharacter(value) => {
+                write!(f, "invalid hex character: {:?}", value)
+            }
+            ErrorKind::InvalidHexLength(value) => {
+                write!(f, "hex must have an even number of digits, got {}", value)
+            }
+            ErrorKind::InvalidBase64 => f.write_str("invalid base64"),
+            ErrorKind::InvalidBase58 => f.write_str("invalid base58"),
+            ErrorKind::InvalidDataLength(value) => {
+                write!(f, "data too long to encode as words: {} bytes", value)
+            }
+            ErrorKind::InvalidChecksumLength(value) => {
+                write!(f, "invalid checksum length: {} bits", value)
+            }
+            ErrorKind::InvalidElectrumSeed => f.write_str("not a valid Electrum seed"),
+            ErrorKind::InvalidElectrumSeedLength(value) => {
+                write!(f, "invalid Electrum 1.x seed length: {} bytes", value)
+            }
+            ErrorKind::Io(err) => write!(f, "I/O error: {}", err),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::keyphrase::KeyPhrase;
+    use crate::language::Language;
+
+    #[test]
+    fn invalid_word_is_not_named() {
+        let phrase = "park remain person kitchen mule spell knee armed position rail grid ankel";
+        let error: Error = KeyPhrase::from_phrase(phrase, Language::English).unwrap_err();
+
+        assert!(error.is_input_error());
+        assert!(error.source().is_none());
+        assert_eq!(error.to_string(), "invalid word at index 11");
+        assert!(matches!(
+            error.into_kind(),
+            ErrorKind::InvalidWord { position: 11 }
+        ));
+    }
+
+    #[test]
+    fn io_error_is_not_input_error() {
+        let error: Error = io::Error::new(io::ErrorKind::UnexpectedEof, "end of input").into();
+
+        assert!(!error.is_input_error());
+        assert_eq!(error.source().unwrap().to_string(), "end of input");
+        assert!(matches!(error.kind(), ErrorKind::Io(_)));
+    }
 }
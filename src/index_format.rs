@@ -0,0 +1,223 @@
+use crate::error::{Error, ErrorKind};
+use crate::keyphrase::{KeyPhrase, WORDLIST_LENGTH};
+use crate::keyphrase_type::LengthPolicy;
+use crate::language::Language;
+
+/// Number of bits in a word index
+const INDEX_BITS: usize = 11;
+
+/// Number of decimal digits a word index is padded to
+const INDEX_DIGITS: usize = 4;
+
+/// How word indices are written down, one row per word
+///
+/// Metal punch plates and dot-grid cards store a [`KeyPhrase`][KeyPhrase] as numbers instead of
+/// words, usually the 1-based position of the word in the word list or its 11 bits.
+///
+/// # Example
+///
+/// ```
+/// use keyphrase::IndexFormat;
+///
+/// // "park" is the 1283rd word of the English word list
+/// assert_eq!(IndexFormat::OneBased.format(1282), "1283");
+/// assert_eq!(IndexFormat::ZeroBased.format(1282), "1282");
+/// assert_eq!(IndexFormat::Binary.format(1282), "10100000010");
+///
+/// assert_eq!(IndexFormat::Binary.parse("10100000010").unwrap(), 1282);
+/// ```
+///
+/// [KeyPhrase]: ./keyphrase/struct.KeyPhrase.html
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum IndexFormat {
+    /// Decimal from `0001` to `2048`, the line number of the word in the word list file
+    OneBased,
+    /// Decimal from `0000` to `2047`, as used by SeedQR
+    ZeroBased,
+    /// 11 binary digits, most significant bit first
+    Binary,
+}
+
+impl IndexFormat {
+    /// Write a 0-based word index in this format
+    ///
+    /// Indices past the word list don't fit the padding and are written out wider.
+    pub fn format(self, index: u16) -> String {
+        match self {
+            IndexFormat::OneBased => {
+                format!("{:0width$}", u32::from(index) + 1, width = INDEX_DIGITS)
+            }
+            IndexFormat::ZeroBased => format!("{:0width$}", index, width = INDEX_DIGITS),
+            IndexFormat::Binary => format!("{:0width$b}", index, width = INDEX_BITS),
+        }
+    }
+
+    /// Read a row written in this format back into a 0-based word index
+    ///
+    /// Surrounding whitespace is ignored, and decimal rows don't need to be padded. Rows that
+    /// are not numbers in this format return an `Error` of kind `ErrorKind::InvalidIndexRow`,
+    /// numbers outside of the word list an `Error` of kind `ErrorKind::InvalidWordIndex`.
+    pub fn parse(self, row: &str) -> Result<u16, Error> {
+        let row: &str = row.trim();
+        let invalid_row = || ErrorKind::InvalidIndexRow(row.to_string());
+
+        let radix: u32 = match self {
+            IndexFormat::Binary if row.len() != INDEX_BITS => return Err(invalid_row().into()),
+            IndexFormat::Binary => 2,
+            _ => 10,
+        };
+
+        if row.is_empty() || !row.chars().all(|c: char| c.is_digit(radix)) {
+            return Err(invalid_row().into());
+        }
+
+        let value: u16 = u16::from_str_radix(row, radix).map_err(|_| invalid_row())?;
+
+        let index: u16 = match self {
+            IndexFormat::OneBased => value
+                .checked_sub(1)
+                .ok_or(ErrorKind::InvalidWordIndex(value))?,
+            _ => value,
+        };
+
+        if index >= WORDLIST_LENGTH {
+            return Err(ErrorKind::InvalidWordIndex(value).into());
+        }
+
+        Ok(index)
+    }
+}
+
+impl KeyPhrase {
+    /// Write the word list index of every word in the given format, one row per word
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use keyphrase::{IndexFormat, KeyPhrase, Language};
+    ///
+    /// let phrase = "park remain person kitchen mule spell knee armed position rail grid ankle";
+    /// let keyphrase = KeyPhrase::from_phrase(phrase, Language::English).unwrap();
+    ///
+    /// let rows: Vec<String> = keyphrase.to_index_rows(IndexFormat::OneBased);
+    ///
+    /// assert_eq!(rows[..3], ["1283", "1453", "1307"]);
+    ///
+    /// let restored = KeyPhrase::from_index_rows(&rows, IndexFormat::OneBased, Language::English).unwrap();
+    ///
+    /// assert_eq!(restored.phrase(), phrase);
+    /// ```
+    pub fn to_index_rows(&self, format: IndexFormat) -> Vec<String> {
+        self.indices()
+            .into_iter()
+            .map(|index: u16| format.format(index))
+            .collect()
+    }
+
+    /// Read a [`KeyPhrase`][KeyPhrase] back from rows written in the given format
+    ///
+    /// The checksum is verified like for [`KeyPhrase::from_indices()`][KeyPhrase::from_indices()].
+    ///
+    /// [KeyPhrase]: ./keyphrase/struct.KeyPhrase.html
+    /// [KeyPhrase::from_indices()]: ./keyphrase/struct.KeyPhrase.html#method.from_indices
+    pub fn from_index_rows<S>(
+        rows: &[S],
+        format: IndexFormat,
+        lang: Language,
+    ) -> Result<KeyPhrase, Error>
+    where
+        S: AsRef<str>,
+    {
+        KeyPhrase::from_index_rows_with_policy(rows, format, lang, LengthPolicy::BIP39)
+    }
+
+    /// Read a [`KeyPhrase`][KeyPhrase] of any length allowed by `policy` back from rows written
+    /// in the given format
+    ///
+    /// [KeyPhrase]: ./keyphrase/struct.KeyPhrase.html
+    pub fn from_index_rows_with_policy<S>(
+        rows: &[S],
+        format: IndexFormat,
+        lang: Language,
+        policy: LengthPolicy,
+    ) -> Result<KeyPhrase, Error>
+    where
+        S: AsRef<str>,
+    {
+        let indices: Vec<u16> = rows
+            .iter()
+            .map(|row: &S| format.parse(row.as_ref()))
+            .collect::<Result<_, _>>()?;
+
+        KeyPhrase::from_indices_with_policy(&indices, lang, policy)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn formats_round_trip() {
+        let formats = [
+            IndexFormat::OneBased,
+            IndexFormat::ZeroBased,
+            IndexFormat::Binary,
+        ];
+
+        for format in &formats {
+            for index in &[0, 1, 1282, 2047] {
+                assert_eq!(format.parse(&format.format(*index)).unwrap(), *index);
+            }
+        }
+
+        assert_eq!(IndexFormat::OneBased.format(0), "0001");
+        assert_eq!(IndexFormat::OneBased.format(2047), "2048");
+        assert_eq!(IndexFormat::ZeroBased.format(73), "0073");
+        assert_eq!(IndexFormat::Binary.format(0), "00000000000");
+        assert_eq!(IndexFormat::OneBased.parse(" 74\n").unwrap(), 73);
+        assert_eq!(IndexFormat::OneBased.format(u16::MAX), "65536");
+    }
+
+    #[test]
+    fn reject_invalid_rows() {
+        let error: Error = IndexFormat::OneBased.parse("0").unwrap_err();
+        assert!(matches!(error.kind(), ErrorKind::InvalidWordIndex(0)));
+
+        let error: Error = IndexFormat::ZeroBased.parse("2048").unwrap_err();
+        assert!(matches!(error.kind(), ErrorKind::InvalidWordIndex(2048)));
+
+        for (format, row) in &[
+            (IndexFormat::OneBased, ""),
+            (IndexFormat::OneBased, "-1"),
+            (IndexFormat::ZeroBased, "12a"),
+            (IndexFormat::ZeroBased, "99999"),
+            (IndexFormat::Binary, "1010"),
+            (IndexFormat::Binary, "10100000012"),
+        ] {
+            let error: Error = format.parse(row).unwrap_err();
+            assert!(matches!(error.kind(), ErrorKind::InvalidIndexRow(_)));
+        }
+    }
+
+    #[test]
+    fn keyphrase_index_rows() {
+        let phrase = "park remain person kitchen mule spell knee armed position rail grid ankle";
+        let keyphrase = KeyPhrase::from_phrase(phrase, Language::English).unwrap();
+
+        let rows: Vec<String> = keyphrase.to_index_rows(IndexFormat::Binary);
+
+        assert_eq!(rows[11], "00001001001");
+
+        let restored = KeyPhrase::from_index_rows(&rows, IndexFormat::Binary, Language::English);
+        assert_eq!(restored.unwrap().phrase(), phrase);
+
+        let mut swapped: Vec<String> = rows.clone();
+        swapped.swap(0, 1);
+
+        let error: Error =
+            KeyPhrase::from_index_rows(&swapped, IndexFormat::Binary, Language::English)
+                .unwrap_err();
+        assert!(matches!(error.kind(), ErrorKind::InvalidChecksum));
+    }
+}
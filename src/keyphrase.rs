@@ -1,11 +1,21 @@
-use crate::crypto::{gen_random_bytes, sha256_first_byte};
-use crate::error::ErrorKind;
-use crate::keyphrase_type::KeyPhraseType;
+use crate::crypto::{gen_random_bytes, sha256_first_bytes};
+use crate::error::{Error, ErrorKind};
+use crate::keyphrase_type::{KeyPhraseType, LengthPolicy};
 use crate::language::{Language, WordList, WordMap};
-use crate::util::{checksum, BitWriter, Bits11, IterExt};
-use failure::Error;
+use crate::util::{checksum, from_hex, to_hex, BitWriter, Bits11, IterExt};
 use std::fmt;
 
+/// Number of words in every word list
+pub(crate) const WORDLIST_LENGTH: u16 = 2048;
+
+/// Maximum number of unknown words accepted by [`KeyPhrase::find_candidates()`][KeyPhrase::find_candidates()]
+///
+/// [KeyPhrase::find_candidates()]: ./struct.KeyPhrase.html#method.find_candidates
+const MAX_UNKNOWN_WORDS: usize = 2;
+
+/// Number of entropy and checksum bits of the longest keyphrase, 48 words
+const MAX_TOTAL_BITS: usize = 48 * 11;
+
 /// Human readable backup phrases which contain most of the information needed to recreate your [EARTH](https://www.earth.engineering) addresses.
 ///
 /// To create a *new* [`KeyPhrase`][KeyPhrase] from a randomly generated key, call [`KeyPhrase::new()`][KeyPhrase::new()].
@@ -78,12 +88,60 @@ impl KeyPhrase {
     ///
     /// [KeyPhrase]: ../keyphrase/struct.KeyPhrase.html
     pub fn from_entropy(entropy: &[u8], lang: Language) -> Result<KeyPhrase, Error> {
+        KeyPhrase::from_entropy_with_policy(entropy, lang, LengthPolicy::BIP39)
+    }
+
+    /// Create a [`KeyPhrase`][KeyPhrase] from pre-generated entropy of any length allowed by
+    /// `policy`
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use keyphrase::{KeyPhrase, Language, LengthPolicy};
+    ///
+    /// let entropy = &[0x33, 0xE4, 0x6B, 0xB1];
+    ///
+    /// assert!(KeyPhrase::from_entropy(entropy, Language::English).is_err());
+    ///
+    /// let keyphrase = KeyPhrase::from_entropy_with_policy(entropy, Language::English, LengthPolicy::EXTENDED).unwrap();
+    ///
+    /// assert_eq!(keyphrase.phrase().split(' ').count(), 3);
+    /// ```
+    ///
+    /// [KeyPhrase]: ../keyphrase/struct.KeyPhrase.html
+    pub fn from_entropy_with_policy(
+        entropy: &[u8],
+        lang: Language,
+        policy: LengthPolicy,
+    ) -> Result<KeyPhrase, Error> {
         // Validate entropy size
-        KeyPhraseType::for_key_size(entropy.len() * 8)?;
+        KeyPhraseType::for_key_size_with_policy(entropy.len() * 8, policy)?;
 
         Ok(Self::from_entropy_unchecked(entropy, lang))
     }
 
+    /// Create a [`KeyPhrase`][KeyPhrase] from pre-generated entropy written as hex
+    ///
+    /// The hex can be in either case and have a `0x` prefix. Returns an `Error` of kind
+    /// `ErrorKind::InvalidHexCharacter` or `ErrorKind::InvalidHexLength` if it is not valid hex,
+    /// and checks the entropy like [`KeyPhrase::from_entropy()`][KeyPhrase::from_entropy()].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use keyphrase::{KeyPhrase, Language};
+    ///
+    /// let keyphrase = KeyPhrase::from_hex_entropy("33E46BB13A746EA41CDDE45C90846A79", Language::English).unwrap();
+    ///
+    /// assert_eq!("crop cash unable insane eight faith inflict route frame loud box vibrant", keyphrase.phrase());
+    /// ```
+    ///
+    /// [KeyPhrase]: ../keyphrase/struct.KeyPhrase.html
+    /// [KeyPhrase::from_entropy()]: ../keyphrase/struct.KeyPhrase.html#method.from_entropy
+    pub fn from_hex_entropy(hex: &str, lang: Language) -> Result<KeyPhrase, Error> {
+        KeyPhrase::from_entropy(&from_hex(hex)?, lang)
+    }
+
     fn from_entropy_unchecked<E>(entropy: E, lang: Language) -> KeyPhrase
     where
         E: Into<Vec<u8>>,
@@ -91,10 +149,10 @@ impl KeyPhrase {
         let entropy: Vec<u8> = entropy.into();
         let wordlist: &WordList = lang.wordlist();
 
-        let checksum_byte: u8 = sha256_first_byte(&entropy);
+        let checksum: Vec<u8> = KeyPhrase::checksum_bytes(&entropy);
 
-        // First, create a byte iterator for the given entropy and the first byte of the
-        // hash of the entropy that will serve as the checksum (up to 8 bits for biggest
+        // First, create a byte iterator for the given entropy and the first bytes of the
+        // hash of the entropy that will serve as the checksum (up to 16 bits for biggest
         // entropy source).
         //
         // Then we transform that into a bits iterator that returns 11 bits at a
@@ -104,7 +162,7 @@ impl KeyPhrase {
         // count.
         let phrase: String = entropy
             .iter()
-            .chain(Some(&checksum_byte))
+            .chain(&checksum)
             .bits()
             .map(|bits: Bits11| wordlist.get_word(bits))
             .join(" ");
@@ -134,6 +192,36 @@ impl KeyPhrase {
     ///
     /// [KeyPhrase]: ../keyphrase/struct.KeyPhrase.html
     pub fn from_phrase<S>(phrase: S, lang: Language) -> Result<KeyPhrase, Error>
+    where
+        S: Into<String>,
+    {
+        KeyPhrase::from_phrase_with_policy(phrase, lang, LengthPolicy::BIP39)
+    }
+
+    /// Create a [`KeyPhrase`][KeyPhrase] from an existing keyphrase of any length allowed by
+    /// `policy`
+    ///
+    /// The checksum is validated like for [`KeyPhrase::from_phrase()`][KeyPhrase::from_phrase()],
+    /// with one checksum bit for every 32 bits of entropy.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use keyphrase::{KeyPhrase, Language, LengthPolicy};
+    ///
+    /// let phrase = "crop cash unable";
+    /// let keyphrase = KeyPhrase::from_phrase_with_policy(phrase, Language::English, LengthPolicy::EXTENDED).unwrap();
+    ///
+    /// assert_eq!(keyphrase.entropy(), &[0x33, 0xE4, 0x6B, 0xB1]);
+    /// ```
+    ///
+    /// [KeyPhrase]: ../keyphrase/struct.KeyPhrase.html
+    /// [KeyPhrase::from_phrase()]: ../keyphrase/struct.KeyPhrase.html#method.from_phrase
+    pub fn from_phrase_with_policy<S>(
+        phrase: S,
+        lang: Language,
+        policy: LengthPolicy,
+    ) -> Result<KeyPhrase, Error>
     where
         S: Into<String>,
     {
@@ -142,7 +230,7 @@ impl KeyPhrase {
         // this also validates the checksum and phrase length before returning the entropy so we
         // can store it. We don't use the validate function here to avoid having a public API that
         // takes a phrase string and returns the entropy directly.
-        let entropy: Vec<u8> = KeyPhrase::phrase_to_entropy(&phrase, lang)?;
+        let entropy: Vec<u8> = KeyPhrase::phrase_to_entropy(&phrase, &lang, policy)?;
 
         let keyphrase: KeyPhrase = KeyPhrase {
             phrase,
@@ -168,7 +256,7 @@ impl KeyPhrase {
     /// assert!(KeyPhrase::validate(test_keyphrase, Language::English).is_ok());
     /// ```
     pub fn validate(phrase: &str, lang: Language) -> Result<(), Error> {
-        KeyPhrase::phrase_to_entropy(phrase, lang)?;
+        KeyPhrase::phrase_to_entropy(phrase, &lang, LengthPolicy::BIP39)?;
 
         Ok(())
     }
@@ -178,17 +266,33 @@ impl KeyPhrase {
     /// Only intended for internal use, as returning a `Vec<u8>` that looks a bit like it could be
     /// used as the seed is likely to cause problems for someone eventually. All the other functions
     /// that return something like that are explicit about what it is and what to use it for.
-    fn phrase_to_entropy(phrase: &str, lang: Language) -> Result<Vec<u8>, Error> {
+    fn phrase_to_entropy(
+        phrase: &str,
+        lang: &Language,
+        policy: LengthPolicy,
+    ) -> Result<Vec<u8>, Error> {
         let wordmap: &WordMap = lang.wordmap();
 
+        let words: Vec<Bits11> = phrase
+            .split(' ')
+            .enumerate()
+            .map(|(position, word): (usize, &str)| wordmap.get_bits_at(position, word))
+            .collect::<Result<_, _>>()?;
+
+        KeyPhrase::bits_to_entropy(&words, policy)
+    }
+
+    /// Calculate the checksum of the 11-bit word indices, verify it and return the entropy
+    fn bits_to_entropy(words: &[Bits11], policy: LengthPolicy) -> Result<Vec<u8>, Error> {
         // Preallocate enough space for the longest possible word list
-        let mut bits = BitWriter::with_capacity(264);
+        let mut bits = BitWriter::with_capacity(MAX_TOTAL_BITS);
 
-        for word in phrase.split(' ') {
-            bits.push(wordmap.get_bits(&word)?);
+        for word in words {
+            bits.push(*word);
         }
 
-        let mtype: KeyPhraseType = KeyPhraseType::for_word_count(bits.len() / 11)?;
+        let mtype: KeyPhraseType =
+            KeyPhraseType::for_word_count_with_policy(bits.len() / 11, policy)?;
 
         debug_assert!(
             bits.len() == mtype.total_bits(),
@@ -198,13 +302,18 @@ impl KeyPhrase {
         let mut entropy = bits.into_bytes();
         let entropy_bytes: usize = mtype.entropy_bits() / 8;
 
-        let actual_checksum: u8 = checksum(entropy[entropy_bytes], mtype.checksum_bits());
+        // Shorter checksums only take up the first of these two bytes
+        entropy.resize(entropy_bytes + 2, 0);
+
+        let actual_checksum: u16 = checksum(
+            [entropy[entropy_bytes], entropy[entropy_bytes + 1]],
+            mtype.checksum_bits(),
+        );
 
-        // Truncate to get rid of the byte containing the checksum
+        // Truncate to get rid of the bytes containing the checksum
         entropy.truncate(entropy_bytes);
 
-        let checksum_byte: u8 = sha256_first_byte(&entropy);
-        let expected_checksum: u8 = checksum(checksum_byte, mtype.checksum_bits());
+        let expected_checksum: u16 = checksum(sha256_first_bytes(&entropy), mtype.checksum_bits());
 
         if actual_checksum != expected_checksum {
             Err(ErrorKind::InvalidChecksum.into())
@@ -213,6 +322,274 @@ impl KeyPhrase {
         }
     }
 
+    /// Get the bytes of the hash of `entropy` that hold its checksum bits
+    fn checksum_bytes(entropy: &[u8]) -> Vec<u8> {
+        // One checksum bit for every 32 bits of entropy
+        let checksum_bits: usize = entropy.len() / 4;
+
+        sha256_first_bytes(entropy)[..checksum_bits.div_ceil(8)].to_vec()
+    }
+
+    /// Find every valid [`KeyPhrase`][KeyPhrase] matching a partially known phrase
+    ///
+    /// Known words are given as `Some(word)`, unknown words as `None`. Every combination of words
+    /// from the word list is tried in place of the unknown words, and the combinations that pass
+    /// the checksum are returned in word list order. At most two words can be unknown; more than
+    /// that will return an `Error` of kind `ErrorKind::TooManyUnknownWords`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use keyphrase::{KeyPhrase, Language};
+    ///
+    /// let words = [
+    ///     Some("park"), Some("remain"), Some("person"), Some("kitchen"), Some("mule"), Some("spell"),
+    ///     Some("knee"), Some("armed"), Some("position"), Some("rail"), Some("grid"), None,
+    /// ];
+    ///
+    /// let candidates = KeyPhrase::find_candidates(&words, Language::English).unwrap();
+    ///
+    /// assert!(candidates.iter().any(|keyphrase| keyphrase.phrase().ends_with(" ankle")));
+    /// ```
+    ///
+    /// [KeyPhrase]: ../keyphrase/struct.KeyPhrase.html
+    pub fn find_candidates(
+        words: &[Option<&str>],
+        lang: Language,
+    ) -> Result<Vec<KeyPhrase>, Error> {
+        KeyPhrase::find_candidates_with_policy(words, lang, LengthPolicy::BIP39)
+    }
+
+    /// Find every valid [`KeyPhrase`][KeyPhrase] of any length allowed by `policy` matching a
+    /// partially known phrase
+    ///
+    /// Works like [`KeyPhrase::find_candidates()`][KeyPhrase::find_candidates()].
+    ///
+    /// [KeyPhrase]: ../keyphrase/struct.KeyPhrase.html
+    /// [KeyPhrase::find_candidates()]: ../keyphrase/struct.KeyPhrase.html#method.find_candidates
+    pub fn find_candidates_with_policy(
+        words: &[Option<&str>],
+        lang: Language,
+        policy: LengthPolicy,
+    ) -> Result<Vec<KeyPhrase>, Error> {
+        KeyPhraseType::for_word_count_with_policy(words.len(), policy)?;
+
+        let wordmap: &WordMap = lang.wordmap();
+        let mut bits: Vec<Bits11> = Vec::with_capacity(words.len());
+        let mut unknown: Vec<usize> = Vec::new();
+
+        for (position, word) in words.iter().enumerate() {
+            match word {
+                Some(word) => bits.push(wordmap.get_bits_at(position, word)?),
+                None => {
+                    unknown.push(position);
+                    bits.push(Bits11::from(0));
+                }
+            }
+        }
+
+        if unknown.len() > MAX_UNKNOWN_WORDS {
+            return Err(ErrorKind::TooManyUnknownWords(unknown.len()).into());
+        }
+
+        let mut candidates: Vec<KeyPhrase> = Vec::new();
+
+        // Count through every combination of indices for the unknown positions, with the first
+        // unknown word being the most significant digit.
+        loop {
+            if let Ok(entropy) = KeyPhrase::bits_to_entropy(&bits, policy) {
+                candidates.push(KeyPhrase::from_entropy_unchecked(entropy, lang.clone()));
+            }
+
+            let mut carry: bool = true;
+
+            for position in unknown.iter().rev() {
+                let next: u16 = u16::from(bits[*position]) + 1;
+
+                if next < WORDLIST_LENGTH {
+                    bits[*position] = Bits11::from(next);
+                    carry = false;
+                    break;
+                }
+
+                bits[*position] = Bits11::from(0);
+            }
+
+            if carry {
+                break;
+            }
+        }
+
+        Ok(candidates)
+    }
+
+    /// Render the same entropy as a [`KeyPhrase`][KeyPhrase] in another [`Language`][Language]
+    ///
+    /// Every word list maps the same 11-bit index to a word, so the converted keyphrase carries
+    /// the same entropy and checksum, just spelled with different words.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use keyphrase::{KeyPhrase, Language};
+    ///
+    /// let phrase = "park remain person kitchen mule spell knee armed position rail grid ankle";
+    /// let keyphrase = KeyPhrase::from_phrase(phrase, Language::English).unwrap();
+    ///
+    /// #[cfg(feature = "spanish")]
+    /// {
+    ///     let spanish = keyphrase.to_language(Language::Spanish);
+    ///
+    ///     assert_eq!(keyphrase.entropy(), spanish.entropy());
+    ///     assert_eq!(phrase, spanish.to_language(Language::English).phrase());
+    /// }
+    /// ```
+    ///
+    /// [KeyPhrase]: ../keyphrase/struct.KeyPhrase.html
+    /// [Language]: ../language/enum.Language.html
+    pub fn to_language(&self, lang: Language) -> KeyPhrase {
+        KeyPhrase::from_entropy_unchecked(self.entropy.clone(), lang)
+    }
+
+    /// Split into `n` keyphrases of the same length whose entropies XOR back to this one
+    ///
+    /// This is Seed XOR: the first `n - 1` parts are random, the last one is this entropy XORed
+    /// with all of them, so every part is needed to get the original back. Each part is a valid
+    /// keyphrase with its own checksum, and works on its own as a decoy wallet. At least two parts
+    /// are needed, otherwise this returns an `Error` of kind `ErrorKind::InvalidPartCount`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use keyphrase::{KeyPhrase, Language};
+    ///
+    /// let phrase = "park remain person kitchen mule spell knee armed position rail grid ankle";
+    /// let keyphrase = KeyPhrase::from_phrase(phrase, Language::English).unwrap();
+    ///
+    /// let parts: Vec<KeyPhrase> = keyphrase.xor_split(3).unwrap();
+    ///
+    /// assert_eq!(parts.len(), 3);
+    /// assert_eq!(KeyPhrase::xor_combine(&parts).unwrap().phrase(), phrase);
+    /// ```
+    pub fn xor_split(&self, n: usize) -> Result<Vec<KeyPhrase>, Error> {
+        if n < 2 {
+            return Err(ErrorKind::InvalidPartCount(n).into());
+        }
+
+        let mut last: Vec<u8> = self.entropy.clone();
+        let mut parts: Vec<KeyPhrase> = Vec::with_capacity(n);
+
+        for _ in 1..n {
+            let entropy: Vec<u8> = gen_random_bytes(self.entropy.len());
+
+            xor_into(&mut last, &entropy);
+            parts.push(KeyPhrase::from_entropy_unchecked(
+                entropy,
+                self.lang.clone(),
+            ));
+        }
+
+        parts.push(KeyPhrase::from_entropy_unchecked(last, self.lang.clone()));
+
+        Ok(parts)
+    }
+
+    /// Combine the parts made by [`KeyPhrase::xor_split()`][KeyPhrase::xor_split()] back into the
+    /// original keyphrase
+    ///
+    /// The parts can be given in any order, and the result is in the language of the first part.
+    /// All parts must have the same length, otherwise this returns an `Error` of kind
+    /// `ErrorKind::MismatchedParts`.
+    ///
+    /// [KeyPhrase::xor_split()]: ./struct.KeyPhrase.html#method.xor_split
+    pub fn xor_combine(parts: &[KeyPhrase]) -> Result<KeyPhrase, Error> {
+        let first: &KeyPhrase = parts.first().ok_or(ErrorKind::InvalidPartCount(0))?;
+        let mut entropy: Vec<u8> = first.entropy.clone();
+
+        for part in &parts[1..] {
+            if part.entropy.len() != entropy.len() {
+                return Err(ErrorKind::MismatchedParts.into());
+            }
+
+            xor_into(&mut entropy, &part.entropy);
+        }
+
+        Ok(KeyPhrase::from_entropy_unchecked(
+            entropy,
+            first.lang.clone(),
+        ))
+    }
+
+    /// Get the 0-based word list index of every word
+    ///
+    /// These are the 11-bit values the phrase encodes, the last one includes the checksum bits.
+    /// See [`KeyPhrase::to_index_rows()`][KeyPhrase::to_index_rows()] to write them down.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use keyphrase::{KeyPhrase, Language};
+    ///
+    /// let phrase = "park remain person kitchen mule spell knee armed position rail grid ankle";
+    /// let keyphrase = KeyPhrase::from_phrase(phrase, Language::English).unwrap();
+    ///
+    /// let indices: Vec<u16> = keyphrase.indices();
+    ///
+    /// assert_eq!(indices[..4], [1282, 1452, 1306, 984]);
+    /// assert_eq!(KeyPhrase::from_indices(&indices, Language::English).unwrap().phrase(), phrase);
+    /// ```
+    ///
+    /// [KeyPhrase::to_index_rows()]: ./struct.KeyPhrase.html#method.to_index_rows
+    pub fn indices(&self) -> Vec<u16> {
+        let checksum: Vec<u8> = KeyPhrase::checksum_bytes(&self.entropy);
+
+        self.entropy
+            .iter()
+            .chain(&checksum)
+            .bits()
+            .map(|bits: Bits11| u16::from(bits))
+            .collect()
+    }
+
+    /// Create a [`KeyPhrase`][KeyPhrase] from 0-based word list indices
+    ///
+    /// The word count and checksum are validated like for [`KeyPhrase::from_phrase()`][KeyPhrase::from_phrase()].
+    /// Indices past the end of the word list return an `Error` of kind `ErrorKind::InvalidWordIndex`.
+    ///
+    /// [KeyPhrase]: ../keyphrase/struct.KeyPhrase.html
+    /// [KeyPhrase::from_phrase()]: ./struct.KeyPhrase.html#method.from_phrase
+    pub fn from_indices(indices: &[u16], lang: Language) -> Result<KeyPhrase, Error> {
+        KeyPhrase::from_indices_with_policy(indices, lang, LengthPolicy::BIP39)
+    }
+
+    /// Create a [`KeyPhrase`][KeyPhrase] of any length allowed by `policy` from 0-based word list
+    /// indices
+    ///
+    /// Validated like for [`KeyPhrase::from_indices()`][KeyPhrase::from_indices()].
+    ///
+    /// [KeyPhrase]: ../keyphrase/struct.KeyPhrase.html
+    /// [KeyPhrase::from_indices()]: ./struct.KeyPhrase.html#method.from_indices
+    pub fn from_indices_with_policy(
+        indices: &[u16],
+        lang: Language,
+        policy: LengthPolicy,
+    ) -> Result<KeyPhrase, Error> {
+        let words: Vec<Bits11> = indices
+            .iter()
+            .map(|index: &u16| {
+                if *index < WORDLIST_LENGTH {
+                    Ok(Bits11::from(*index))
+                } else {
+                    Err(ErrorKind::InvalidWordIndex(*index))
+                }
+            })
+            .collect::<Result<_, _>>()?;
+
+        let entropy: Vec<u8> = KeyPhrase::bits_to_entropy(&words, policy)?;
+
+        Ok(KeyPhrase::from_entropy_unchecked(entropy, lang))
+    }
+
     /// Get the keyphrase as a string reference.
     ///
     /// # Example
@@ -281,7 +658,32 @@ impl KeyPhrase {
     /// let lang = keyphrase.language();
     /// ```
     pub fn language(&self) -> Language {
-        self.lang
+        self.lang.clone()
+    }
+
+    /// Get the [`KeyPhraseType`][KeyPhraseType], the length of the keyphrase
+    ///
+    /// [KeyPhraseType]: ../keyphrase_type/struct.KeyPhraseType.html
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use keyphrase::{KeyPhrase, KeyPhraseType, Language};
+    ///
+    /// let keyphrase = KeyPhrase::new(KeyPhraseType::Words18, Language::English);
+    ///
+    /// assert_eq!(keyphrase.keyphrase_type(), KeyPhraseType::Words18);
+    /// ```
+    pub fn keyphrase_type(&self) -> KeyPhraseType {
+        KeyPhraseType::for_key_size_with_policy(self.entropy.len() * 8, LengthPolicy::EXTENDED)
+            .expect("A keyphrase has a valid length")
+    }
+}
+
+/// XOR `other` into `target`, byte by byte
+fn xor_into(target: &mut [u8], other: &[u8]) {
+    for (byte, other) in target.iter_mut().zip(other) {
+        *byte ^= other;
     }
 }
 
@@ -309,11 +711,7 @@ impl fmt::LowerHex for KeyPhrase {
             f.write_str("0x")?;
         }
 
-        for byte in self.entropy() {
-            write!(f, "{:x}", byte)?;
-        }
-
-        Ok(())
+        f.write_str(&to_hex(self.entropy()))
     }
 }
 
@@ -323,11 +721,7 @@ impl fmt::UpperHex for KeyPhrase {
             f.write_str("0x")?;
         }
 
-        for byte in self.entropy() {
-            write!(f, "{:X}", byte)?;
-        }
-
-        Ok(())
+        f.write_str(&to_hex(self.entropy()).to_ascii_uppercase())
     }
 }
 
@@ -381,6 +775,98 @@ mod test {
         assert_eq!(entropy, keyphrase.entropy());
     }
 
+    #[test]
+    fn find_candidates_with_unknown_last_word() {
+        let phrase: &str =
+            "crop cash unable insane eight faith inflict route frame loud box vibrant";
+        let mut words: Vec<Option<&str>> = phrase.split(' ').map(Some).collect();
+        words[11] = None;
+
+        let candidates: Vec<KeyPhrase> =
+            KeyPhrase::find_candidates(&words, Language::English).unwrap();
+
+        // 7 bits of the last word are entropy, the remaining 4 bits are checksum
+        assert_eq!(candidates.len(), 128);
+        assert!(candidates.iter().any(|c: &KeyPhrase| c.phrase() == phrase));
+    }
+
+    #[test]
+    fn find_candidates_with_unknown_middle_word() {
+        let phrase: &str =
+            "crop cash unable insane eight faith inflict route frame loud box vibrant";
+        let mut words: Vec<Option<&str>> = phrase.split(' ').map(Some).collect();
+        words[4] = None;
+
+        let candidates: Vec<KeyPhrase> =
+            KeyPhrase::find_candidates(&words, Language::English).unwrap();
+
+        assert!(candidates.iter().any(|c: &KeyPhrase| c.phrase() == phrase));
+        assert!(candidates.iter().all(|c: &KeyPhrase| KeyPhrase::validate(
+            c.phrase(),
+            Language::English
+        )
+        .is_ok()));
+    }
+
+    #[test]
+    fn find_candidates_rejects_too_many_unknown_words() {
+        let words: Vec<Option<&str>> = vec![None; 12];
+
+        assert!(KeyPhrase::find_candidates(&words, Language::English).is_err());
+    }
+
+    #[test]
+    fn xor_split_and_combine() {
+        for mtype in &[KeyPhraseType::Words12, KeyPhraseType::Words24] {
+            let keyphrase: KeyPhrase = KeyPhrase::new(*mtype, Language::English);
+            let mut parts: Vec<KeyPhrase> = keyphrase.xor_split(4).unwrap();
+
+            assert_eq!(parts.len(), 4);
+
+            for part in &parts {
+                assert_eq!(part.entropy().len(), keyphrase.entropy().len());
+                assert!(KeyPhrase::validate(part.phrase(), Language::English).is_ok());
+            }
+
+            parts.reverse();
+
+            let combined: KeyPhrase = KeyPhrase::xor_combine(&parts).unwrap();
+
+            assert_eq!(combined.phrase(), keyphrase.phrase());
+            assert_ne!(
+                KeyPhrase::xor_combine(&parts[1..]).unwrap().phrase(),
+                keyphrase.phrase()
+            );
+        }
+    }
+
+    #[test]
+    fn xor_combine_coldcard_example() {
+        let parts: Vec<KeyPhrase> = [
+            "romance wink lottery autumn shop bring dawn tongue range crater truth ability miss spice fitness easy legal release recall obey exchange recycle dragon room",
+            "lion misery divide hurry latin fluid camp advance illegal lab pyramid unaware eager fringe sick camera series noodle toy crowd jeans select depth lounge",
+            "vault nominee cradle silk own frown throw leg cactus recall talent worry gadget surface shy planet purpose coffee drip few seven term squeeze educate",
+        ]
+        .iter()
+        .map(|phrase: &&str| KeyPhrase::from_phrase(*phrase, Language::English).unwrap())
+        .collect();
+
+        assert_eq!(
+            KeyPhrase::xor_combine(&parts).unwrap().phrase(),
+            "silent toe meat possible chair blossom wait occur this worth option bag nurse find fish scene bench asthma bike wage world quit primary indoor"
+        );
+    }
+
+    #[test]
+    fn xor_rejects_invalid_parts() {
+        let keyphrase: KeyPhrase = KeyPhrase::new(KeyPhraseType::Words12, Language::English);
+        let other: KeyPhrase = KeyPhrase::new(KeyPhraseType::Words24, Language::English);
+
+        assert!(keyphrase.xor_split(1).is_err());
+        assert!(KeyPhrase::xor_combine(&[]).is_err());
+        assert!(KeyPhrase::xor_combine(&[keyphrase, other]).is_err());
+    }
+
     #[test]
     fn keyphrase_format() {
         let keyphrase: KeyPhrase = KeyPhrase::new(KeyPhraseType::Words15, Language::English);
@@ -414,4 +900,23 @@ mod test {
             "0x33E46BB13A746EA41CDDE45C90846A79"
         );
     }
+
+    #[test]
+    fn keyphrase_hex_format_pads_bytes() {
+        let entropy: &[u8; 16] = &[
+            0x00, 0x01, 0x0A, 0x0F, 0x10, 0xF0, 0xFF, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+            0x00, 0x09,
+        ];
+
+        let keyphrase: KeyPhrase = KeyPhrase::from_entropy(entropy, Language::English).unwrap();
+
+        assert_eq!(
+            format!("{:x}", keyphrase),
+            "00010a0f10f0ff000000000000000009"
+        );
+        assert_eq!(
+            format!("{:X}", keyphrase),
+            "00010A0F10F0FF000000000000000009"
+        );
+    }
 }
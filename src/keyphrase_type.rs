@@ -1,8 +1,18 @@
-use crate::error::ErrorKind;
-use failure::Error;
+use crate::error::{Error, ErrorKind};
 use std::fmt;
 
-const ENTROPY_OFFSET: usize = 8;
+/// Number of entropy bits per checksum bit, and per 3 words
+const BITS_PER_CHECKSUM_BIT: usize = 32;
+
+/// Shortest entropy supported by [`LengthPolicy::EXTENDED`][LengthPolicy::EXTENDED], 3 words
+///
+/// [LengthPolicy::EXTENDED]: ./struct.LengthPolicy.html#associatedconstant.EXTENDED
+const MIN_ENTROPY_BITS: usize = 32;
+
+/// Longest entropy supported by [`LengthPolicy::EXTENDED`][LengthPolicy::EXTENDED], 48 words
+///
+/// [LengthPolicy::EXTENDED]: ./struct.LengthPolicy.html#associatedconstant.EXTENDED
+const MAX_ENTROPY_BITS: usize = 512;
 
 /// Determines the number of words that will be present in a [`KeyPhrase`][KeyPhrase] phrase
 ///
@@ -13,8 +23,8 @@ const ENTROPY_OFFSET: usize = 8;
 /// For example, a 12 word keyphrase is essentially a friendly representation of a 128-bit key,
 /// while a 24 word keyphrase is essentially a 256-bit key.
 ///
-/// If you know you want a specific phrase length, you can use the enum variant directly, for example
-/// `KeyPhraseType::Words12`.
+/// If you know you want a specific phrase length, you can use one of the BIP39 constants directly,
+/// for example `KeyPhraseType::Words12`.
 ///
 /// You can also get a `KeyPhraseType` that corresponds to one of the standard BIP39 key sizes by
 /// passing arbitrary `usize` values:
@@ -25,18 +35,42 @@ const ENTROPY_OFFSET: usize = 8;
 /// let keyphrase_type = KeyPhraseType::for_key_size(128).unwrap();
 /// ```
 ///
+/// Other multiples of 32 bits, from 3 words for 32 bits up to 48 words for 512 bits, are only
+/// accepted under a [`LengthPolicy`][LengthPolicy] that allows them:
+///
+/// ```
+/// use keyphrase::{KeyPhraseType, LengthPolicy};
+///
+/// assert!(KeyPhraseType::for_word_count(6).is_err());
+///
+/// let keyphrase_type = KeyPhraseType::for_word_count_with_policy(6, LengthPolicy::EXTENDED).unwrap();
+///
+/// assert_eq!(keyphrase_type.entropy_bits(), 64);
+/// assert_eq!(keyphrase_type.checksum_bits(), 2);
+/// ```
+///
 /// [KeyPhraseType]: ../keyphrase_type/struct.KeyPhraseType.html
 /// [KeyPhrase]: ../keyphrase/struct.KeyPhrase.html
 /// [Seed]: ../seed/struct.Seed.html
+/// [LengthPolicy]: ./struct.LengthPolicy.html
 ///
-#[derive(Debug, Copy, Clone)]
-pub enum KeyPhraseType {
-    //  ... = (entropy_bits << ...)   | checksum_bits
-    Words12 = (128 << ENTROPY_OFFSET) | 4,
-    Words15 = (160 << ENTROPY_OFFSET) | 5,
-    Words18 = (192 << ENTROPY_OFFSET) | 6,
-    Words21 = (224 << ENTROPY_OFFSET) | 7,
-    Words24 = (256 << ENTROPY_OFFSET) | 8,
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct KeyPhraseType {
+    entropy_bits: usize,
+}
+
+#[allow(non_upper_case_globals)]
+impl KeyPhraseType {
+    /// 12 words, 128 bits of entropy
+    pub const Words12: KeyPhraseType = KeyPhraseType { entropy_bits: 128 };
+    /// 15 words, 160 bits of entropy
+    pub const Words15: KeyPhraseType = KeyPhraseType { entropy_bits: 160 };
+    /// 18 words, 192 bits of entropy
+    pub const Words18: KeyPhraseType = KeyPhraseType { entropy_bits: 192 };
+    /// 21 words, 224 bits of entropy
+    pub const Words21: KeyPhraseType = KeyPhraseType { entropy_bits: 224 };
+    /// 24 words, 256 bits of entropy
+    pub const Words24: KeyPhraseType = KeyPhraseType { entropy_bits: 256 };
 }
 
 impl KeyPhraseType {
@@ -52,16 +86,30 @@ impl KeyPhraseType {
     /// let keyphrase_type = KeyPhraseType::for_word_count(12).unwrap();
     /// ```
     pub fn for_word_count(size: usize) -> Result<KeyPhraseType, Error> {
-        let keyphrase_type: KeyPhraseType = match size {
-            12 => KeyPhraseType::Words12,
-            15 => KeyPhraseType::Words15,
-            18 => KeyPhraseType::Words18,
-            21 => KeyPhraseType::Words21,
-            24 => KeyPhraseType::Words24,
-            _ => return Err(ErrorKind::InvalidWordLength(size).into()),
+        Self::for_word_count_with_policy(size, LengthPolicy::BIP39)
+    }
+
+    /// Get a `KeyPhraseType` for a keyphrase with a specific number of words, allowed by `policy`
+    ///
+    /// Specifying a word count that is not a multiple of 3 within the policy will return an
+    /// `Error` of kind `ErrorKind::InvalidWordLength`.
+    pub fn for_word_count_with_policy(
+        size: usize,
+        policy: LengthPolicy,
+    ) -> Result<KeyPhraseType, Error> {
+        if !size.is_multiple_of(3) {
+            return Err(ErrorKind::InvalidWordLength(size).into());
+        }
+
+        let keyphrase_type = KeyPhraseType {
+            entropy_bits: size / 3 * BITS_PER_CHECKSUM_BIT,
         };
 
-        Ok(keyphrase_type)
+        if policy.allows(keyphrase_type) {
+            Ok(keyphrase_type)
+        } else {
+            Err(ErrorKind::InvalidWordLength(size).into())
+        }
     }
 
     /// Get a `KeyPhraseType` for a keyphrase representing the given key size as bits
@@ -76,16 +124,25 @@ impl KeyPhraseType {
     /// let keyphrase_type = KeyPhraseType::for_key_size(128).unwrap();
     /// ```
     pub fn for_key_size(size: usize) -> Result<KeyPhraseType, Error> {
-        let keyphrase_type: KeyPhraseType = match size {
-            128 => KeyPhraseType::Words12,
-            160 => KeyPhraseType::Words15,
-            192 => KeyPhraseType::Words18,
-            224 => KeyPhraseType::Words21,
-            256 => KeyPhraseType::Words24,
-            _ => return Err(ErrorKind::InvalidKeysize(size).into()),
-        };
+        Self::for_key_size_with_policy(size, LengthPolicy::BIP39)
+    }
+
+    /// Get a `KeyPhraseType` for a keyphrase representing the given key size as bits, allowed
+    /// by `policy`
+    ///
+    /// Specifying a key size that is not a multiple of 32 within the policy will return an
+    /// `Error` of kind `ErrorKind::InvalidKeysize`.
+    pub fn for_key_size_with_policy(
+        size: usize,
+        policy: LengthPolicy,
+    ) -> Result<KeyPhraseType, Error> {
+        let keyphrase_type = KeyPhraseType { entropy_bits: size };
 
-        Ok(keyphrase_type)
+        if size.is_multiple_of(BITS_PER_CHECKSUM_BIT) && policy.allows(keyphrase_type) {
+            Ok(keyphrase_type)
+        } else {
+            Err(ErrorKind::InvalidKeysize(size).into())
+        }
     }
 
     /// Get a `KeyPhraseType` for an existing keyphrase
@@ -108,11 +165,24 @@ impl KeyPhraseType {
     /// let entropy_bits = keyphrase_type.entropy_bits();
     /// ```
     ///
-    /// [KeyPhraseType::entropy_bits()]: ./enum.KeyPhraseType.html#method.entropy_bits
+    /// [KeyPhraseType::entropy_bits()]: ./struct.KeyPhraseType.html#method.entropy_bits
     pub fn for_phrase(phrase: &str) -> Result<KeyPhraseType, Error> {
+        Self::for_phrase_with_policy(phrase, LengthPolicy::BIP39)
+    }
+
+    /// Get a `KeyPhraseType` for an existing keyphrase with a length allowed by `policy`
+    ///
+    /// Like [`KeyPhraseType::for_phrase()`][KeyPhraseType::for_phrase()], the phrase will not be
+    /// validated in any other way.
+    ///
+    /// [KeyPhraseType::for_phrase()]: ./struct.KeyPhraseType.html#method.for_phrase
+    pub fn for_phrase_with_policy(
+        phrase: &str,
+        policy: LengthPolicy,
+    ) -> Result<KeyPhraseType, Error> {
         let word_count: usize = phrase.split(' ').count();
 
-        Self::for_word_count(word_count)
+        Self::for_word_count_with_policy(word_count, policy)
     }
 
     /// Return the number of entropy+checksum bits
@@ -146,7 +216,7 @@ impl KeyPhraseType {
     /// let entropy_bits = keyphrase_type.entropy_bits();
     /// ```
     pub fn entropy_bits(self) -> usize {
-        (self as usize) >> ENTROPY_OFFSET
+        self.entropy_bits
     }
 
     /// Return the number of checksum bits
@@ -163,7 +233,7 @@ impl KeyPhraseType {
     /// let checksum_bits = keyphrase_type.checksum_bits();
     /// ```
     pub fn checksum_bits(self) -> u8 {
-        (self as usize) as u8
+        (self.entropy_bits / BITS_PER_CHECKSUM_BIT) as u8
     }
 
     /// Return the number of words
@@ -180,6 +250,11 @@ impl KeyPhraseType {
     pub fn word_count(self) -> usize {
         self.total_bits() / 11
     }
+
+    /// Whether this is one of the lengths of the BIP39 standard, 12 to 24 words
+    pub fn is_bip39(self) -> bool {
+        LengthPolicy::BIP39.allows(self)
+    }
 }
 
 impl Default for KeyPhraseType {
@@ -199,6 +274,91 @@ impl fmt::Display for KeyPhraseType {
     }
 }
 
+/// Range of [`KeyPhraseType`][KeyPhraseType] lengths accepted when reading or creating a keyphrase
+///
+/// Functions without a policy argument use [`LengthPolicy::BIP39`][LengthPolicy::BIP39], so
+/// a short pairing code or a 48 word phrase is never mistaken for a wallet backup. Any other range
+/// is opt-in, through the `_with_policy` variants of those functions.
+///
+/// # Example
+///
+/// ```
+/// use keyphrase::{KeyPhraseType, LengthPolicy};
+///
+/// // 3 to 9 words, for short pairing codes
+/// let policy = LengthPolicy::new(32, 96).unwrap();
+///
+/// assert!(policy.allows(KeyPhraseType::for_word_count_with_policy(9, policy).unwrap()));
+/// assert!(!policy.allows(KeyPhraseType::Words12));
+/// ```
+///
+/// [KeyPhraseType]: ./struct.KeyPhraseType.html
+/// [LengthPolicy::BIP39]: ./struct.LengthPolicy.html#associatedconstant.BIP39
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct LengthPolicy {
+    min_entropy_bits: usize,
+    max_entropy_bits: usize,
+}
+
+impl LengthPolicy {
+    /// 12 to 24 words, the lengths of the BIP39 standard
+    pub const BIP39: LengthPolicy = LengthPolicy {
+        min_entropy_bits: 128,
+        max_entropy_bits: 256,
+    };
+
+    /// 3 to 48 words, every length supported by this crate
+    pub const EXTENDED: LengthPolicy = LengthPolicy {
+        min_entropy_bits: MIN_ENTROPY_BITS,
+        max_entropy_bits: MAX_ENTROPY_BITS,
+    };
+
+    /// Allow every multiple of 32 entropy bits from `min_entropy_bits` to `max_entropy_bits`
+    ///
+    /// Both bounds must be multiples of 32 from 32 to 512, otherwise an `Error` of kind
+    /// `ErrorKind::InvalidKeysize` is returned for the first bound that is not. An empty range
+    /// also returns it, for `max_entropy_bits`.
+    pub fn new(min_entropy_bits: usize, max_entropy_bits: usize) -> Result<LengthPolicy, Error> {
+        for bits in &[min_entropy_bits, max_entropy_bits] {
+            if !bits.is_multiple_of(BITS_PER_CHECKSUM_BIT)
+                || !(MIN_ENTROPY_BITS..=MAX_ENTROPY_BITS).contains(bits)
+            {
+                return Err(ErrorKind::InvalidKeysize(*bits).into());
+            }
+        }
+
+        if min_entropy_bits > max_entropy_bits {
+            return Err(ErrorKind::InvalidKeysize(max_entropy_bits).into());
+        }
+
+        Ok(LengthPolicy {
+            min_entropy_bits,
+            max_entropy_bits,
+        })
+    }
+
+    /// Get the smallest number of entropy bits allowed
+    pub fn min_entropy_bits(self) -> usize {
+        self.min_entropy_bits
+    }
+
+    /// Get the largest number of entropy bits allowed
+    pub fn max_entropy_bits(self) -> usize {
+        self.max_entropy_bits
+    }
+
+    /// Whether a keyphrase of this type is accepted under the policy
+    pub fn allows(self, keyphrase_type: KeyPhraseType) -> bool {
+        (self.min_entropy_bits..=self.max_entropy_bits).contains(&keyphrase_type.entropy_bits())
+    }
+}
+
+impl Default for LengthPolicy {
+    fn default() -> LengthPolicy {
+        LengthPolicy::BIP39
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -229,4 +389,65 @@ mod test {
         assert_eq!(KeyPhraseType::Words21.checksum_bits(), 7);
         assert_eq!(KeyPhraseType::Words24.checksum_bits(), 8);
     }
+
+    #[test]
+    fn extended_lengths() {
+        for words in (3..=48).step_by(3) {
+            let keyphrase_type: KeyPhraseType =
+                KeyPhraseType::for_word_count_with_policy(words, LengthPolicy::EXTENDED).unwrap();
+
+            assert_eq!(keyphrase_type.word_count(), words);
+            assert_eq!(keyphrase_type.total_bits(), words * 11);
+            assert_eq!(
+                KeyPhraseType::for_key_size_with_policy(
+                    keyphrase_type.entropy_bits(),
+                    LengthPolicy::EXTENDED
+                )
+                .unwrap(),
+                keyphrase_type
+            );
+            assert_eq!(
+                keyphrase_type.is_bip39(),
+                KeyPhraseType::for_word_count(words).is_ok()
+            );
+        }
+
+        let keyphrase_type: KeyPhraseType =
+            KeyPhraseType::for_word_count_with_policy(48, LengthPolicy::EXTENDED).unwrap();
+        assert_eq!(keyphrase_type.entropy_bits(), 512);
+        assert_eq!(keyphrase_type.checksum_bits(), 16);
+    }
+
+    #[test]
+    fn lengths_outside_policy() {
+        for words in &[0, 1, 10, 13, 51] {
+            assert!(
+                KeyPhraseType::for_word_count_with_policy(*words, LengthPolicy::EXTENDED).is_err()
+            );
+        }
+
+        for bits in &[0, 100, 129, 544] {
+            assert!(
+                KeyPhraseType::for_key_size_with_policy(*bits, LengthPolicy::EXTENDED).is_err()
+            );
+        }
+
+        assert!(KeyPhraseType::for_word_count(9).is_err());
+        assert!(KeyPhraseType::for_word_count(27).is_err());
+        assert!(KeyPhraseType::for_key_size(512).is_err());
+    }
+
+    #[test]
+    fn custom_policy() {
+        let policy: LengthPolicy = LengthPolicy::new(64, 64).unwrap();
+
+        assert!(KeyPhraseType::for_word_count_with_policy(6, policy).is_ok());
+        assert!(KeyPhraseType::for_word_count_with_policy(3, policy).is_err());
+        assert!(KeyPhraseType::for_word_count_with_policy(9, policy).is_err());
+
+        assert!(LengthPolicy::new(0, 64).is_err());
+        assert!(LengthPolicy::new(32, 544).is_err());
+        assert!(LengthPolicy::new(32, 100).is_err());
+        assert!(LengthPolicy::new(96, 64).is_err());
+    }
 }
@@ -0,0 +1,529 @@
+//! Encrypted keystore files, for keeping keyphrases at rest.
+//!
+//! The format is JSON in the style of
+//! [Web3 Secret Storage](https://ethereum.org/en/developers/docs/data-structures-and-encoding/web3-secret-storage/).
+//! The entropy of the keyphrase is encrypted with AES-256-GCM under a key stretched from the
+//! password with scrypt. The scrypt parameters are stored next to the ciphertext, and the
+//! version, label, language and word count are authenticated along with it.
+//!
+//! ```json
+//! {
+//!   "version": 1,
+//!   "label": "cold storage",
+//!   "language": "en",
+//!   "words": 12,
+//!   "crypto": {
+//!     "cipher": "aes-256-gcm",
+//!     "cipherparams": { "nonce": "..." },
+//!     "ciphertext": "...",
+//!     "kdf": "scrypt",
+//!     "kdfparams": { "dklen": 32, "n": 262144, "r": 8, "p": 1, "salt": "..." }
+//!   }
+//! }
+//! ```
+//!
+//! Only available with the `keystore` cargo feature.
+//!
+
+use crate::crypto::gen_random_bytes;
+use crate::error::{Error, ErrorKind};
+use crate::keyphrase::KeyPhrase;
+use crate::keyphrase_type::{KeyPhraseType, LengthPolicy};
+use crate::language::Language;
+use crate::util::{from_hex, to_hex};
+use aes_gcm::aead::{Aead, KeyInit, Payload};
+use aes_gcm::{Aes256Gcm, Nonce};
+use serde::{Deserialize, Serialize};
+use std::convert::TryInto;
+use std::fmt;
+use zeroize::Zeroizing;
+
+/// Version of the keystore format written by this crate
+const KEYSTORE_VERSION: u32 = 1;
+
+/// Name of the cipher in the keystore
+const CIPHER: &str = "aes-256-gcm";
+
+/// Name of the key derivation function in the keystore
+const KDF: &str = "scrypt";
+
+/// Length of the derived key, in bytes
+const KEY_LENGTH: usize = 32;
+
+/// Length of the scrypt salt, in bytes
+const SALT_LENGTH: usize = 32;
+
+/// Length of the AES-GCM nonce, in bytes
+const NONCE_LENGTH: usize = 12;
+
+/// Length of the AES-GCM authentication tag appended to the ciphertext, in bytes
+const TAG_LENGTH: usize = 16;
+
+/// Largest accepted scrypt cost exponent
+const MAX_LOG_N: u8 = 20;
+
+/// Most memory scrypt may use, `128 * r * 2^log_n` bytes, 1 GiB like `log_n = 20` with `r = 8`
+const MAX_MEMORY: u64 = 1 << 30;
+
+/// Largest accepted scrypt parallelism
+const MAX_P: u32 = 16;
+
+/// scrypt parameters used to stretch the password of a [`Keystore`][Keystore]
+///
+/// The memory needed grows with `128 * r * 2^log_n` bytes, and the time with `p` as well. The
+/// defaults, `log_n = 18`, `r = 8` and `p = 1`, are the ones of Web3 Secret Storage.
+///
+/// [Keystore]: ./struct.Keystore.html
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct KeystoreParams {
+    log_n: u8,
+    r: u32,
+    p: u32,
+}
+
+impl KeystoreParams {
+    /// Create scrypt parameters with a cost of `2^log_n`
+    ///
+    /// Returns an `Error` of kind `ErrorKind::InvalidKeystoreParams` if scrypt rejects them, if
+    /// `log_n` is above 20, if they need more than 1 GiB of memory or if `p` is above 16.
+    pub fn new(log_n: u8, r: u32, p: u32) -> Result<KeystoreParams, Error> {
+        let params = KeystoreParams { log_n, r, p };
+
+        params.scrypt_params()?;
+
+        Ok(params)
+    }
+
+    /// Get the base 2 logarithm of the scrypt cost
+    pub fn log_n(&self) -> u8 {
+        self.log_n
+    }
+
+    /// Get the scrypt block size
+    pub fn r(&self) -> u32 {
+        self.r
+    }
+
+    /// Get the scrypt parallelism
+    pub fn p(&self) -> u32 {
+        self.p
+    }
+
+    fn scrypt_params(&self) -> Result<scrypt::Params, Error> {
+        let invalid = || ErrorKind::InvalidKeystoreParams(self.log_n, self.r, self.p);
+
+        if self.log_n > MAX_LOG_N
+            || 128 * self.r as u64 * (1 << self.log_n) > MAX_MEMORY
+            || self.p > MAX_P
+        {
+            return Err(invalid().into());
+        }
+
+        scrypt::Params::new(self.log_n, self.r, self.p, KEY_LENGTH).map_err(|_| invalid().into())
+    }
+}
+
+impl Default for KeystoreParams {
+    fn default() -> KeystoreParams {
+        KeystoreParams {
+            log_n: 18,
+            r: 8,
+            p: 1,
+        }
+    }
+}
+
+/// A [`KeyPhrase`][KeyPhrase] encrypted with a password, to be stored as JSON
+///
+/// Only the entropy is encrypted, the language and the number of words are kept in the clear
+/// with an optional label. All of them are authenticated, so a keystore that was tampered with
+/// doesn't decrypt.
+///
+/// # Example
+///
+/// ```
+/// use keyphrase::{KeyPhrase, KeyPhraseType, Keystore, KeystoreParams, Language};
+///
+/// let keyphrase = KeyPhrase::new(KeyPhraseType::Words24, Language::English);
+///
+/// // Cheap parameters to keep the example fast, use the defaults in production
+/// let params = KeystoreParams::new(10, 8, 1).unwrap();
+/// let keystore = Keystore::encrypt(&keyphrase, "correct horse", Some("cold storage"), params).unwrap();
+///
+/// let json: String = keystore.to_json();
+///
+/// let restored = Keystore::from_json(&json).unwrap().decrypt("correct horse").unwrap();
+///
+/// assert_eq!(restored.phrase(), keyphrase.phrase());
+/// ```
+///
+/// [KeyPhrase]: ./keyphrase/struct.KeyPhrase.html
+#[derive(Clone)]
+pub struct Keystore {
+    label: Option<String>,
+    lang: Language,
+    keyphrase_type: KeyPhraseType,
+    params: KeystoreParams,
+    salt: Vec<u8>,
+    nonce: [u8; NONCE_LENGTH],
+    ciphertext: Vec<u8>,
+}
+
+impl Keystore {
+    /// Encrypt the entropy of `keyphrase` with a key stretched from `password`
+    ///
+    /// A new random salt and nonce are used every time. Keyphrases in a custom language can't
+    /// be stored, they return an `Error` of kind `ErrorKind::UnsupportedLanguage`.
+    pub fn encrypt(
+        keyphrase: &KeyPhrase,
+        password: &str,
+        label: Option<&str>,
+        params: KeystoreParams,
+    ) -> Result<Keystore, Error> {
+        Keystore::encrypt_with(
+            keyphrase,
+            password,
+            label,
+            params,
+            gen_random_bytes(SALT_LENGTH),
+            gen_random_bytes(NONCE_LENGTH)
+                .try_into()
+                .expect("The nonce has the right length"),
+        )
+    }
+
+    /// Decrypt the [`KeyPhrase`][KeyPhrase]
+    ///
+    /// Returns an `Error` of kind `ErrorKind::KeystoreDecryption` if the password is wrong or the
+    /// keystore was modified.
+    ///
+    /// [KeyPhrase]: ./keyphrase/struct.KeyPhrase.html
+    pub fn decrypt(&self, password: &str) -> Result<KeyPhrase, Error> {
+        let cipher: Aes256Gcm = self.cipher(password)?;
+        let associated_data: Vec<u8> = self.associated_data();
+
+        let entropy: Zeroizing<Vec<u8>> = cipher
+            .decrypt(
+                &Nonce::from(self.nonce),
+                Payload {
+                    msg: &self.ciphertext,
+                    aad: &associated_data,
+                },
+            )
+            .map(Zeroizing::new)
+            .map_err(|_| ErrorKind::KeystoreDecryption)?;
+
+        KeyPhrase::from_entropy_with_policy(&entropy[..], self.lang.clone(), LengthPolicy::EXTENDED)
+    }
+
+    /// Encrypt the keyphrase again, with a new password or stronger parameters
+    ///
+    /// The label is kept, the salt and nonce are new.
+    pub fn reencrypt(
+        &self,
+        password: &str,
+        new_password: &str,
+        params: KeystoreParams,
+    ) -> Result<Keystore, Error> {
+        let keyphrase: KeyPhrase = self.decrypt(password)?;
+
+        Keystore::encrypt(&keyphrase, new_password, self.label(), params)
+    }
+
+    /// Get the label, if any
+    pub fn label(&self) -> Option<&str> {
+        self.label.as_deref()
+    }
+
+    /// Get the language of the encrypted keyphrase
+    pub fn language(&self) -> Language {
+        self.lang.clone()
+    }
+
+    /// Get the number of words of the encrypted keyphrase
+    pub fn keyphrase_type(&self) -> KeyPhraseType {
+        self.keyphrase_type
+    }
+
+    /// Get the scrypt parameters the password is stretched with
+    pub fn params(&self) -> KeystoreParams {
+        self.params
+    }
+
+    /// Write the keystore as pretty printed JSON
+    pub fn to_json(&self) -> String {
+        let json = KeystoreJson {
+            header: Header {
+                version: KEYSTORE_VERSION,
+                label: self.label.clone(),
+                language: self.lang.code().to_string(),
+                words: self.keyphrase_type.word_count(),
+            },
+            crypto: CryptoJson {
+                cipher: CIPHER.to_string(),
+                cipherparams: CipherParamsJson {
+                    nonce: to_hex(&self.nonce),
+                },
+                ciphertext: to_hex(&self.ciphertext),
+                kdf: KDF.to_string(),
+                kdfparams: KdfParamsJson {
+                    dklen: KEY_LENGTH,
+                    n: 1 << self.params.log_n,
+                    r: self.params.r,
+                    p: self.params.p,
+                    salt: to_hex(&self.salt),
+                },
+            },
+        };
+
+        serde_json::to_string_pretty(&json).expect("A keystore always serializes")
+    }
+
+    /// Read a keystore from JSON
+    ///
+    /// Nothing is decrypted yet. Returns an `Error` of kind
+    /// `ErrorKind::UnsupportedKeystoreVersion` for versions other than 1, and of kind
+    /// `ErrorKind::InvalidKeystore` for JSON that is not a valid keystore.
+    pub fn from_json(json: &str) -> Result<Keystore, Error> {
+        let invalid = |reason: &str| ErrorKind::InvalidKeystore(reason.to_string());
+
+        let json: KeystoreJson =
+            serde_json::from_str(json).map_err(|err| invalid(&err.to_string()))?;
+        let header: Header = json.header;
+        let crypto: CryptoJson = json.crypto;
+        let kdfparams: KdfParamsJson = crypto.kdfparams;
+
+        if header.version != KEYSTORE_VERSION {
+            return Err(ErrorKind::UnsupportedKeystoreVersion(header.version).into());
+        }
+
+        if crypto.cipher != CIPHER {
+            return Err(invalid(&format!("unsupported cipher {:?}", crypto.cipher)).into());
+        }
+
+        if crypto.kdf != KDF {
+            return Err(invalid(&format!("unsupported kdf {:?}", crypto.kdf)).into());
+        }
+
+        if kdfparams.dklen != KEY_LENGTH {
+            return Err(invalid("dklen must be 32").into());
+        }
+
+        if !kdfparams.n.is_power_of_two() || kdfparams.n < 2 {
+            return Err(invalid("n must be a power of two").into());
+        }
+
+        let params =
+            KeystoreParams::new(kdfparams.n.trailing_zeros() as u8, kdfparams.r, kdfparams.p)?;
+
+        let lang: Language = header.language.parse()?;
+        let keyphrase_type: KeyPhraseType =
+            KeyPhraseType::for_word_count_with_policy(header.words, LengthPolicy::EXTENDED)?;
+
+        let salt: Vec<u8> = from_hex(&kdfparams.salt).map_err(|_| invalid("invalid salt"))?;
+        let nonce: [u8; NONCE_LENGTH] = from_hex(&crypto.cipherparams.nonce)
+            .ok()
+            .and_then(|nonce: Vec<u8>| nonce.try_into().ok())
+            .ok_or_else(|| invalid("invalid nonce"))?;
+        let ciphertext: Vec<u8> = from_hex(&crypto.ciphertext)
+            .ok()
+            .filter(|ciphertext: &Vec<u8>| {
+                ciphertext.len() == keyphrase_type.entropy_bits() / 8 + TAG_LENGTH
+            })
+            .ok_or_else(|| invalid("invalid ciphertext"))?;
+
+        Ok(Keystore {
+            label: header.label,
+            lang,
+            keyphrase_type,
+            params,
+            salt,
+            nonce,
+            ciphertext,
+        })
+    }
+
+    /// Encrypt with the given salt and nonce, which must never be reused with the same password
+    fn encrypt_with(
+        keyphrase: &KeyPhrase,
+        password: &str,
+        label: Option<&str>,
+        params: KeystoreParams,
+        salt: Vec<u8>,
+        nonce: [u8; NONCE_LENGTH],
+    ) -> Result<Keystore, Error> {
+        let lang: Language = keyphrase.language();
+
+        if let Language::Custom(_) = lang {
+            return Err(ErrorKind::UnsupportedLanguage(lang.to_string()).into());
+        }
+
+        let mut keystore = Keystore {
+            label: label.map(str::to_string),
+            lang,
+            keyphrase_type: keyphrase.keyphrase_type(),
+            params,
+            salt,
+            nonce,
+            ciphertext: Vec::new(),
+        };
+
+        let cipher: Aes256Gcm = keystore.cipher(password)?;
+        let associated_data: Vec<u8> = keystore.associated_data();
+
+        keystore.ciphertext = cipher
+            .encrypt(
+                &Nonce::from(keystore.nonce),
+                Payload {
+                    msg: keyphrase.entropy(),
+                    aad: &associated_data,
+                },
+            )
+            .expect("AES-GCM encrypts any short message");
+
+        Ok(keystore)
+    }
+
+    /// Stretch the password into an AES-256-GCM cipher
+    fn cipher(&self, password: &str) -> Result<Aes256Gcm, Error> {
+        let mut key: Zeroizing<[u8; KEY_LENGTH]> = Zeroizing::new([0u8; KEY_LENGTH]);
+
+        scrypt::scrypt(
+            password.as_bytes(),
+            &self.salt,
+            &self.params.scrypt_params()?,
+            &mut *key,
+        )
+        .expect("The key length is valid for scrypt");
+
+        Ok(Aes256Gcm::new_from_slice(&*key).expect("The key has the right length"))
+    }
+
+    /// The cleartext fields as compact JSON, in the order they are written
+    fn associated_data(&self) -> Vec<u8> {
+        let header = Header {
+            version: KEYSTORE_VERSION,
+            label: self.label.clone(),
+            language: self.lang.code().to_string(),
+            words: self.keyphrase_type.word_count(),
+        };
+
+        serde_json::to_vec(&header).expect("A header always serializes")
+    }
+}
+
+impl fmt::Debug for Keystore {
+    /// Only prints the cleartext fields
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Keystore")
+            .field("label", &self.label)
+            .field("lang", &self.lang)
+            .field("keyphrase_type", &self.keyphrase_type)
+            .field("params", &self.params)
+            .finish()
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct KeystoreJson {
+    #[serde(flatten)]
+    header: Header,
+    crypto: CryptoJson,
+}
+
+#[derive(Serialize, Deserialize)]
+struct Header {
+    version: u32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    label: Option<String>,
+    language: String,
+    words: usize,
+}
+
+#[derive(Serialize, Deserialize)]
+struct CryptoJson {
+    cipher: String,
+    cipherparams: CipherParamsJson,
+    ciphertext: String,
+    kdf: String,
+    kdfparams: KdfParamsJson,
+}
+
+#[derive(Serialize, Deserialize)]
+struct CipherParamsJson {
+    nonce: String,
+}
+
+#[derive(Serialize, Deserialize)]
+struct KdfParamsJson {
+    dklen: usize,
+    n: u64,
+    r: u32,
+    p: u32,
+    salt: String,
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn cheap_params() -> KeystoreParams {
+        KeystoreParams::new(10, 8, 1).unwrap()
+    }
+
+    #[test]
+    fn encrypt_fixed_vector() {
+        let phrase = "park remain person kitchen mule spell knee armed position rail grid ankle";
+        let keyphrase = KeyPhrase::from_phrase(phrase, Language::English).unwrap();
+
+        let keystore = Keystore::encrypt_with(
+            &keyphrase,
+            "correct horse",
+            Some("cold storage"),
+            cheap_params(),
+            (0..32).collect(),
+            [0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11],
+        )
+        .unwrap();
+
+        assert_eq!(
+            to_hex(&keystore.ciphertext),
+            "22f16a88448fafe4ce0308dbab305128fd15e077af6a7544c58e16bfe8485911"
+        );
+        assert_eq!(
+            keystore.associated_data(),
+            br#"{"version":1,"label":"cold storage","language":"en","words":12}"#.to_vec()
+        );
+    }
+
+    #[test]
+    fn params_are_checked() {
+        let error: Error = KeystoreParams::new(21, 8, 1).unwrap_err();
+        assert!(matches!(
+            error.kind(),
+            ErrorKind::InvalidKeystoreParams(21, 8, 1)
+        ));
+
+        let error: Error = KeystoreParams::new(10, 0, 1).unwrap_err();
+        assert!(matches!(
+            error.kind(),
+            ErrorKind::InvalidKeystoreParams(10, 0, 1)
+        ));
+
+        // 128 * 16 * 2^20 bytes, 2 GiB
+        let error: Error = KeystoreParams::new(20, 16, 1).unwrap_err();
+        assert!(matches!(
+            error.kind(),
+            ErrorKind::InvalidKeystoreParams(20, 16, 1)
+        ));
+
+        let error: Error = KeystoreParams::new(10, 8, 17).unwrap_err();
+        assert!(matches!(
+            error.kind(),
+            ErrorKind::InvalidKeystoreParams(10, 8, 17)
+        ));
+
+        assert!(KeystoreParams::new(20, 8, 16).is_ok());
+        assert_eq!(KeystoreParams::default().log_n(), 18);
+    }
+}
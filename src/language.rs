@@ -1,38 +1,303 @@
+use crate::error::Error;
+use crate::util::edit_distance;
 use crate::ErrorKind;
 use crate::{Bits, Bits11};
-use failure::Error;
-use hashbrown::HashMap;
+use hashbrown::{HashMap, HashSet};
+use std::borrow::Cow;
+use std::fmt;
+use std::fs;
+use std::hash::{Hash, Hasher};
+use std::path::Path;
+use std::str::FromStr;
+use std::sync::Arc;
+use unicode_normalization::is_nfkd;
 
+/// Number of words every word list must contain
+const WORDLIST_LENGTH: usize = 2048;
+
+/// Maximum number of suggestions returned by [`WordList::suggestions()`][WordList::suggestions()]
+///
+/// [WordList::suggestions()]: ./struct.WordList.html#method.suggestions
+const MAX_SUGGESTIONS: usize = 5;
+
+/// Maximum edit distance for a word to be considered a suggestion
+const MAX_SUGGESTION_DISTANCE: usize = 2;
+
+/// Word -> index lookup table for a [`Language`][Language]
+///
+/// [Language]: ./enum.Language.html
 pub struct WordMap {
-    inner: HashMap<&'static str, Bits11>,
+    inner: HashMap<Cow<'static, str>, Bits11>,
 }
 
+/// Index -> word lookup table for a [`Language`][Language]
+///
+/// [Language]: ./enum.Language.html
+#[derive(PartialEq, Eq, Hash)]
 pub struct WordList {
-    inner: Vec<&'static str>,
+    inner: Vec<Cow<'static, str>>,
 }
 
 impl WordMap {
-    pub fn get_bits(&self, word: &str) -> Result<Bits11, Error> {
-        match self.inner.get(word) {
-            Some(n) => Ok(*n),
-            None => Err(ErrorKind::InvalidWord.into()),
-        }
+    /// Get the 11-bit index of `word`, or `None` if it is not in the word list
+    pub fn get_bits(&self, word: &str) -> Option<Bits11> {
+        self.inner.get(word).copied()
+    }
+
+    /// Get the 11-bit index of the word at `position` of a phrase
+    ///
+    /// Fails with `ErrorKind::InvalidWord`, which only names the position so the word doesn't
+    /// end up in logs.
+    pub(crate) fn get_bits_at(&self, position: usize, word: &str) -> Result<Bits11, Error> {
+        self.get_bits(word)
+            .ok_or_else(|| ErrorKind::InvalidWord { position }.into())
     }
 }
 
 impl WordList {
-    pub fn get_word(&self, bits: Bits11) -> &'static str {
-        self.inner[bits.bits() as usize]
+    pub fn get_word(&self, bits: Bits11) -> &str {
+        &self.inner[bits.bits() as usize]
+    }
+
+    /// Get all the words in the list that start with the given prefix, in word list order
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use keyphrase::Language;
+    ///
+    /// let words = Language::English.wordlist().get_words_by_prefix("act");
+    ///
+    /// assert_eq!(words, vec!["act", "action", "actor", "actress", "actual"]);
+    /// ```
+    pub fn get_words_by_prefix(&self, prefix: &str) -> Vec<&str> {
+        self.inner
+            .iter()
+            .filter(|word: &&Cow<'static, str>| word.starts_with(prefix))
+            .map(|word: &Cow<'static, str>| word.as_ref())
+            .collect()
+    }
+
+    /// Get the words closest to a possibly misspelled word, ordered by edit distance
+    ///
+    /// At most five words within an edit distance of two are returned.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use keyphrase::Language;
+    ///
+    /// let words = Language::English.wordlist().suggestions("kitchin");
+    ///
+    /// assert_eq!(words[0], "kitchen");
+    /// ```
+    pub fn suggestions(&self, word: &str) -> Vec<&str> {
+        let mut scored: Vec<(usize, &str)> = self
+            .inner
+            .iter()
+            .map(|candidate: &Cow<'static, str>| {
+                (edit_distance(word, candidate), candidate.as_ref())
+            })
+            .filter(|(distance, _): &(usize, &str)| *distance <= MAX_SUGGESTION_DISTANCE)
+            .collect();
+
+        // stable sort keeps word list order between candidates of equal distance
+        scored.sort_by_key(|(distance, _): &(usize, &str)| *distance);
+
+        scored
+            .into_iter()
+            .take(MAX_SUGGESTIONS)
+            .map(|(_, candidate): (usize, &str)| candidate)
+            .collect()
+    }
+
+    /// Number of words in the list
+    pub fn len(&self) -> usize {
+        self.inner.len()
+    }
+
+    /// Whether the list has no words
+    pub fn is_empty(&self) -> bool {
+        self.inner.is_empty()
+    }
+}
+
+/// A word list supplied at runtime instead of being compiled into the crate
+///
+/// The words are validated when the [`CustomLanguage`][CustomLanguage] is created:
+///
+/// - there must be exactly 2048 words, one per line
+/// - every word must be unique
+/// - every word must be in [NFKD](https://unicode.org/reports/tr15/) form
+/// - words can't be empty or contain whitespace
+///
+/// Each rule has its own `ErrorKind`. Word lists that are meant to be typed in by prefix, like the
+/// English list where the first four letters identify a word, can additionally be checked with
+/// [`CustomLanguage::check_unique_prefix()`][CustomLanguage::check_unique_prefix()].
+///
+/// A [`Language`][Language] shares the custom word list through an `Arc`, so cloning the language
+/// is cheap and the list is freed once the last [`KeyPhrase`][KeyPhrase] using it is dropped:
+///
+/// ```
+/// use keyphrase::{CustomLanguage, KeyPhrase, KeyPhraseType, Language};
+///
+/// let words: String = (0..2048).map(|i| format!("word{}\n", i)).collect();
+/// let custom: CustomLanguage = words.parse().unwrap();
+/// let lang = Language::from(custom);
+///
+/// let keyphrase = KeyPhrase::new(KeyPhraseType::Words12, lang.clone());
+///
+/// assert!(KeyPhrase::validate(keyphrase.phrase(), lang).is_ok());
+/// ```
+///
+/// [CustomLanguage]: ./struct.CustomLanguage.html
+/// [CustomLanguage::check_unique_prefix()]: ./struct.CustomLanguage.html#method.check_unique_prefix
+/// [Language]: ./enum.Language.html
+/// [KeyPhrase]: ./keyphrase/struct.KeyPhrase.html
+pub struct CustomLanguage {
+    wordlist: WordList,
+    wordmap: WordMap,
+}
+
+impl CustomLanguage {
+    /// Create a [`CustomLanguage`][CustomLanguage] from a list of words, in index order
+    ///
+    /// [CustomLanguage]: ./struct.CustomLanguage.html
+    pub fn from_words<I, S>(words: I) -> Result<CustomLanguage, Error>
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        let inner: Vec<Cow<'static, str>> = words
+            .into_iter()
+            .map(|word: S| Cow::Owned(word.into()))
+            .collect();
+
+        if inner.len() != WORDLIST_LENGTH {
+            return Err(ErrorKind::InvalidWordListLength(inner.len()).into());
+        }
+
+        {
+            let mut seen: HashSet<&str> = HashSet::with_capacity(WORDLIST_LENGTH);
+
+            for (index, word) in inner.iter().enumerate() {
+                if word.is_empty() {
+                    return Err(ErrorKind::EmptyWord(index).into());
+                }
+                if word.chars().any(char::is_whitespace) {
+                    return Err(ErrorKind::WhitespaceInWord(word.to_string()).into());
+                }
+                if !is_nfkd(word) {
+                    return Err(ErrorKind::UnnormalizedWord(word.to_string()).into());
+                }
+                if !seen.insert(word) {
+                    return Err(ErrorKind::DuplicateWord(word.to_string()).into());
+                }
+            }
+        }
+
+        let wordlist = WordList { inner };
+        let wordmap: WordMap = lazy::gen_wordmap(&wordlist);
+
+        Ok(CustomLanguage { wordlist, wordmap })
+    }
+
+    /// Read a [`CustomLanguage`][CustomLanguage] from a file with one word per line
+    ///
+    /// [CustomLanguage]: ./struct.CustomLanguage.html
+    pub fn from_file<P: AsRef<Path>>(path: P) -> Result<CustomLanguage, Error> {
+        fs::read_to_string(path)?.parse()
+    }
+
+    /// Check that the first `len` characters of every word are enough to tell it apart
+    ///
+    /// Words shorter than `len` characters count as their own prefix. Returns an `Error` of kind
+    /// `ErrorKind::AmbiguousPrefix` naming the first two words that share a prefix.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use keyphrase::CustomLanguage;
+    ///
+    /// let words: String = (0..2048).map(|i| format!("word{}\n", i)).collect();
+    /// let custom: CustomLanguage = words.parse().unwrap();
+    ///
+    /// assert!(custom.check_unique_prefix(8).is_ok());
+    /// assert!(custom.check_unique_prefix(4).is_err());
+    /// ```
+    pub fn check_unique_prefix(&self, len: usize) -> Result<(), Error> {
+        let mut seen: HashMap<String, &str> = HashMap::with_capacity(WORDLIST_LENGTH);
+
+        for word in &self.wordlist.inner {
+            let prefix: String = word.chars().take(len).collect();
+
+            if let Some(other) = seen.insert(prefix, word) {
+                return Err(
+                    ErrorKind::AmbiguousPrefix(other.to_string(), word.to_string(), len).into(),
+                );
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Get the word list
+    pub fn wordlist(&self) -> &WordList {
+        &self.wordlist
+    }
+
+    /// Get the word map
+    pub fn wordmap(&self) -> &WordMap {
+        &self.wordmap
+    }
+}
+
+impl FromStr for CustomLanguage {
+    type Err = Error;
+
+    /// Parse a word list with one word per line
+    ///
+    /// A trailing newline at the end of the list is allowed, as are `\r\n` line endings.
+    fn from_str(words: &str) -> Result<CustomLanguage, Error> {
+        let words: &str = words.strip_suffix('\n').unwrap_or(words);
+
+        CustomLanguage::from_words(
+            words
+                .split('\n')
+                .map(|line: &str| line.strip_suffix('\r').unwrap_or(line)),
+        )
+    }
+}
+
+// Two custom languages are the same language if they have the same words in the same order
+impl PartialEq for CustomLanguage {
+    fn eq(&self, other: &CustomLanguage) -> bool {
+        self.wordlist == other.wordlist
+    }
+}
+
+impl Eq for CustomLanguage {}
+
+impl Hash for CustomLanguage {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.wordlist.hash(state);
+    }
+}
+
+impl fmt::Debug for CustomLanguage {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "CustomLanguage({} words)", self.wordlist.len())
     }
 }
 
 mod lazy {
     use super::{Bits11, WordList, WordMap};
     use once_cell::sync::Lazy;
+    use std::borrow::Cow;
 
     /// lazy generation of the word list
     fn gen_wordlist(lang_words: &'static str) -> WordList {
-        let inner: Vec<_> = lang_words.split_whitespace().collect();
+        let inner: Vec<_> = lang_words.split_whitespace().map(Cow::Borrowed).collect();
 
         debug_assert!(inner.len() == 2048, "Invalid wordlist length");
 
@@ -40,12 +305,12 @@ mod lazy {
     }
 
     /// lazy generation of the word map
-    fn gen_wordmap(wordlist: &WordList) -> WordMap {
+    pub(super) fn gen_wordmap(wordlist: &WordList) -> WordMap {
         let inner = wordlist
             .inner
             .iter()
             .enumerate()
-            .map(|(i, item): (usize, &&'static str)| (*item, Bits11::from(i as u16)))
+            .map(|(i, item): (usize, &Cow<'static, str>)| (item.clone(), Bits11::from(i as u16)))
             .collect();
 
         WordMap { inner }
@@ -59,6 +324,9 @@ mod lazy {
     #[cfg(feature = "chinese-traditional")]
     pub static WORDLIST_CHINESE_TRADITIONAL: Lazy<WordList> =
         sync_lazy! { gen_wordlist(include_str!("langs/chinese_traditional.txt")) };
+    #[cfg(feature = "czech")]
+    pub static WORDLIST_CZECH: Lazy<WordList> =
+        sync_lazy! { gen_wordlist(include_str!("langs/czech.txt")) };
     #[cfg(feature = "french")]
     pub static WORDLIST_FRENCH: Lazy<WordList> =
         sync_lazy! { gen_wordlist(include_str!("langs/french.txt")) };
@@ -71,6 +339,9 @@ mod lazy {
     #[cfg(feature = "korean")]
     pub static WORDLIST_KOREAN: Lazy<WordList> =
         sync_lazy! { gen_wordlist(include_str!("langs/korean.txt")) };
+    #[cfg(feature = "portuguese")]
+    pub static WORDLIST_PORTUGUESE: Lazy<WordList> =
+        sync_lazy! { gen_wordlist(include_str!("langs/portuguese.txt")) };
     #[cfg(feature = "spanish")]
     pub static WORDLIST_SPANISH: Lazy<WordList> =
         sync_lazy! { gen_wordlist(include_str!("langs/spanish.txt")) };
@@ -82,6 +353,8 @@ mod lazy {
     #[cfg(feature = "chinese-traditional")]
     pub static WORDMAP_CHINESE_TRADITIONAL: Lazy<WordMap> =
         sync_lazy! { gen_wordmap(&WORDLIST_CHINESE_TRADITIONAL) };
+    #[cfg(feature = "czech")]
+    pub static WORDMAP_CZECH: Lazy<WordMap> = sync_lazy! { gen_wordmap(&WORDLIST_CZECH) };
     #[cfg(feature = "french")]
     pub static WORDMAP_FRENCH: Lazy<WordMap> = sync_lazy! { gen_wordmap(&WORDLIST_FRENCH) };
     #[cfg(feature = "italian")]
@@ -90,6 +363,8 @@ mod lazy {
     pub static WORDMAP_JAPANESE: Lazy<WordMap> = sync_lazy! { gen_wordmap(&WORDLIST_JAPANESE) };
     #[cfg(feature = "korean")]
     pub static WORDMAP_KOREAN: Lazy<WordMap> = sync_lazy! { gen_wordmap(&WORDLIST_KOREAN) };
+    #[cfg(feature = "portuguese")]
+    pub static WORDMAP_PORTUGUESE: Lazy<WordMap> = sync_lazy! { gen_wordmap(&WORDLIST_PORTUGUESE) };
     #[cfg(feature = "spanish")]
     pub static WORDMAP_SPANISH: Lazy<WordMap> = sync_lazy! { gen_wordmap(&WORDLIST_SPANISH) };
 }
@@ -99,15 +374,23 @@ mod lazy {
 ///
 /// There is no official language specified by the standard except English.
 ///
+/// Besides the word lists compiled into the crate, a word list loaded at runtime can be used
+/// through [`Language::Custom`][Language::Custom] with a [`CustomLanguage`][CustomLanguage].
+///
 /// [KeyPhrase]: ./keyphrase/struct.KeyPhrase.html
 /// [Seed]: ./seed/struct.Seed.html
-#[derive(Debug, Clone, Copy)]
+/// [Language::Custom]: ./enum.Language.html#variant.Custom
+/// [CustomLanguage]: ./struct.CustomLanguage.html
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
 pub enum Language {
+    #[default]
     English,
     #[cfg(feature = "chinese-simplified")]
     ChineseSimplified,
     #[cfg(feature = "chinese-traditional")]
     ChineseTraditional,
+    #[cfg(feature = "czech")]
+    Czech,
     #[cfg(feature = "french")]
     French,
     #[cfg(feature = "italian")]
@@ -116,19 +399,178 @@ pub enum Language {
     Japanese,
     #[cfg(feature = "korean")]
     Korean,
+    #[cfg(feature = "portuguese")]
+    Portuguese,
     #[cfg(feature = "spanish")]
     Spanish,
+    Custom(Arc<CustomLanguage>),
 }
 
+/// Every language compiled into the crate, in declaration order
+const ALL: &[Language] = &[
+    Language::English,
+    #[cfg(feature = "chinese-simplified")]
+    Language::ChineseSimplified,
+    #[cfg(feature = "chinese-traditional")]
+    Language::ChineseTraditional,
+    #[cfg(feature = "czech")]
+    Language::Czech,
+    #[cfg(feature = "french")]
+    Language::French,
+    #[cfg(feature = "italian")]
+    Language::Italian,
+    #[cfg(feature = "japanese")]
+    Language::Japanese,
+    #[cfg(feature = "korean")]
+    Language::Korean,
+    #[cfg(feature = "portuguese")]
+    Language::Portuguese,
+    #[cfg(feature = "spanish")]
+    Language::Spanish,
+];
+
 impl Language {
+    /// Get every language compiled into the crate
+    ///
+    /// Only the languages enabled through cargo features are listed, and
+    /// [`Language::Custom`][Language::Custom] never is.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use keyphrase::Language;
+    ///
+    /// for lang in Language::all() {
+    ///     println!("{} ({})", lang, lang.code());
+    /// }
+    /// ```
+    ///
+    /// [Language::Custom]: ./enum.Language.html#variant.Custom
+    pub fn all() -> &'static [Language] {
+        ALL
+    }
+
+    /// Get the [BCP 47](https://tools.ietf.org/html/bcp47) language code, based on ISO 639-1
+    ///
+    /// Chinese uses script subtags to tell the simplified and traditional word lists apart. Custom
+    /// languages have the code for an undetermined language, `und`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use keyphrase::Language;
+    ///
+    /// assert_eq!(Language::English.code(), "en");
+    /// #[cfg(feature = "chinese-simplified")]
+    /// assert_eq!(Language::ChineseSimplified.code(), "zh-Hans");
+    /// ```
+    pub fn code(&self) -> &'static str {
+        match self {
+            Language::English => "en",
+            #[cfg(feature = "chinese-simplified")]
+            Language::ChineseSimplified => "zh-Hans",
+            #[cfg(feature = "chinese-traditional")]
+            Language::ChineseTraditional => "zh-Hant",
+            #[cfg(feature = "czech")]
+            Language::Czech => "cs",
+            #[cfg(feature = "french")]
+            Language::French => "fr",
+            #[cfg(feature = "italian")]
+            Language::Italian => "it",
+            #[cfg(feature = "japanese")]
+            Language::Japanese => "ja",
+            #[cfg(feature = "korean")]
+            Language::Korean => "ko",
+            #[cfg(feature = "portuguese")]
+            Language::Portuguese => "pt",
+            #[cfg(feature = "spanish")]
+            Language::Spanish => "es",
+            Language::Custom(_) => "und",
+        }
+    }
+
+    /// Get the English name of the language, as used by `Display`
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use keyphrase::Language;
+    ///
+    /// assert_eq!(Language::English.name(), "English");
+    /// #[cfg(feature = "chinese-traditional")]
+    /// assert_eq!(Language::ChineseTraditional.name(), "Chinese Traditional");
+    /// ```
+    pub fn name(&self) -> &'static str {
+        match self {
+            Language::English => "English",
+            #[cfg(feature = "chinese-simplified")]
+            Language::ChineseSimplified => "Chinese Simplified",
+            #[cfg(feature = "chinese-traditional")]
+            Language::ChineseTraditional => "Chinese Traditional",
+            #[cfg(feature = "czech")]
+            Language::Czech => "Czech",
+            #[cfg(feature = "french")]
+            Language::French => "French",
+            #[cfg(feature = "italian")]
+            Language::Italian => "Italian",
+            #[cfg(feature = "japanese")]
+            Language::Japanese => "Japanese",
+            #[cfg(feature = "korean")]
+            Language::Korean => "Korean",
+            #[cfg(feature = "portuguese")]
+            Language::Portuguese => "Portuguese",
+            #[cfg(feature = "spanish")]
+            Language::Spanish => "Spanish",
+            Language::Custom(_) => "Custom",
+        }
+    }
+
+    /// Get the name of the language in that language
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use keyphrase::Language;
+    ///
+    /// assert_eq!(Language::English.native_name(), "English");
+    /// #[cfg(feature = "spanish")]
+    /// assert_eq!(Language::Spanish.native_name(), "Español");
+    /// ```
+    pub fn native_name(&self) -> &'static str {
+        match self {
+            Language::English => "English",
+            #[cfg(feature = "chinese-simplified")]
+            Language::ChineseSimplified => "简体中文",
+            #[cfg(feature = "chinese-traditional")]
+            Language::ChineseTraditional => "繁體中文",
+            #[cfg(feature = "czech")]
+            Language::Czech => "Čeština",
+            #[cfg(feature = "french")]
+            Language::French => "Français",
+            #[cfg(feature = "italian")]
+            Language::Italian => "Italiano",
+            #[cfg(feature = "japanese")]
+            Language::Japanese => "日本語",
+            #[cfg(feature = "korean")]
+            Language::Korean => "한국어",
+            #[cfg(feature = "portuguese")]
+            Language::Portuguese => "Português",
+            #[cfg(feature = "spanish")]
+            Language::Spanish => "Español",
+            Language::Custom(_) => "Custom",
+        }
+    }
+
     /// Get the word list for this language
-    pub fn wordlist(self) -> &'static WordList {
+    pub fn wordlist(&self) -> &WordList {
         match self {
             Language::English => &lazy::WORDLIST_ENGLISH,
             #[cfg(feature = "chinese-simplified")]
             Language::ChineseSimplified => &lazy::WORDLIST_CHINESE_SIMPLIFIED,
             #[cfg(feature = "chinese-traditional")]
             Language::ChineseTraditional => &lazy::WORDLIST_CHINESE_TRADITIONAL,
+            #[cfg(feature = "czech")]
+            Language::Czech => &lazy::WORDLIST_CZECH,
             #[cfg(feature = "french")]
             Language::French => &lazy::WORDLIST_FRENCH,
             #[cfg(feature = "italian")]
@@ -137,8 +579,11 @@ impl Language {
             Language::Japanese => &lazy::WORDLIST_JAPANESE,
             #[cfg(feature = "korean")]
             Language::Korean => &lazy::WORDLIST_KOREAN,
+            #[cfg(feature = "portuguese")]
+            Language::Portuguese => &lazy::WORDLIST_PORTUGUESE,
             #[cfg(feature = "spanish")]
             Language::Spanish => &lazy::WORDLIST_SPANISH,
+            Language::Custom(custom) => custom.wordlist(),
         }
     }
 
@@ -146,13 +591,15 @@ impl Language {
     ///
     /// The index of an individual word in the word list is used as the binary value of that word
     /// when the phrase is turned into a [`Seed`][Seed].
-    pub fn wordmap(self) -> &'static WordMap {
+    pub fn wordmap(&self) -> &WordMap {
         match self {
             Language::English => &lazy::WORDMAP_ENGLISH,
             #[cfg(feature = "chinese-simplified")]
             Language::ChineseSimplified => &lazy::WORDMAP_CHINESE_SIMPLIFIED,
             #[cfg(feature = "chinese-traditional")]
             Language::ChineseTraditional => &lazy::WORDMAP_CHINESE_TRADITIONAL,
+            #[cfg(feature = "czech")]
+            Language::Czech => &lazy::WORDMAP_CZECH,
             #[cfg(feature = "french")]
             Language::French => &lazy::WORDMAP_FRENCH,
             #[cfg(feature = "italian")]
@@ -161,14 +608,133 @@ impl Language {
             Language::Japanese => &lazy::WORDMAP_JAPANESE,
             #[cfg(feature = "korean")]
             Language::Korean => &lazy::WORDMAP_KOREAN,
+            #[cfg(feature = "portuguese")]
+            Language::Portuguese => &lazy::WORDMAP_PORTUGUESE,
             #[cfg(feature = "spanish")]
             Language::Spanish => &lazy::WORDMAP_SPANISH,
+            Language::Custom(custom) => custom.wordmap(),
         }
     }
 }
 
-impl Default for Language {
-    fn default() -> Language {
-        Language::English
+impl From<CustomLanguage> for Language {
+    fn from(custom: CustomLanguage) -> Language {
+        Language::Custom(Arc::new(custom))
+    }
+}
+
+impl fmt::Display for Language {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl FromStr for Language {
+    type Err = Error;
+
+    /// Parse a language from its code, English name or native name
+    ///
+    /// Matching ignores case, and hyphens or underscores can stand in for the spaces in English
+    /// names, so `"zh-Hans"`, `"Chinese Simplified"`, `"chinese-simplified"` and `"简体中文"` all
+    /// parse to `Language::ChineseSimplified`. Only languages compiled into the crate are found.
+    fn from_str(name: &str) -> Result<Language, Error> {
+        let spaced: String = name.replace(['-', '_'], " ");
+
+        ALL.iter()
+            .find(|lang: &&Language| {
+                lang.code().eq_ignore_ascii_case(name)
+                    || lang.name().eq_ignore_ascii_case(&spaced)
+                    || lang.native_name().to_lowercase() == name.to_lowercase()
+            })
+            .cloned()
+            .ok_or_else(|| ErrorKind::UnknownLanguage(name.to_string()).into())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn all_lists_every_enabled_variant() {
+        let expected: usize = 1
+            + cfg!(feature = "chinese-simplified") as usize
+            + cfg!(feature = "chinese-traditional") as usize
+            + cfg!(feature = "czech") as usize
+            + cfg!(feature = "french") as usize
+            + cfg!(feature = "italian") as usize
+            + cfg!(feature = "japanese") as usize
+            + cfg!(feature = "korean") as usize
+            + cfg!(feature = "portuguese") as usize
+            + cfg!(feature = "spanish") as usize;
+
+        let listed: HashSet<Language> = Language::all().iter().cloned().collect();
+
+        assert_eq!(Language::all().len(), expected);
+        assert_eq!(listed.len(), expected, "Languages must not be listed twice");
+
+        for lang in Language::all() {
+            // Exhaustive, so adding a variant fails to compile until it's accounted for here
+            match lang {
+                Language::English => {}
+                #[cfg(feature = "chinese-simplified")]
+                Language::ChineseSimplified => {}
+                #[cfg(feature = "chinese-traditional")]
+                Language::ChineseTraditional => {}
+                #[cfg(feature = "czech")]
+                Language::Czech => {}
+                #[cfg(feature = "french")]
+                Language::French => {}
+                #[cfg(feature = "italian")]
+                Language::Italian => {}
+                #[cfg(feature = "japanese")]
+                Language::Japanese => {}
+                #[cfg(feature = "korean")]
+                Language::Korean => {}
+                #[cfg(feature = "portuguese")]
+                Language::Portuguese => {}
+                #[cfg(feature = "spanish")]
+                Language::Spanish => {}
+                Language::Custom(_) => panic!("Custom languages must not be listed"),
+            }
+        }
+    }
+
+    #[test]
+    fn codes_and_names_are_unique() {
+        let codes: HashSet<&str> = Language::all()
+            .iter()
+            .map(|l: &Language| l.code())
+            .collect();
+        let names: HashSet<&str> = Language::all()
+            .iter()
+            .map(|l: &Language| l.name())
+            .collect();
+
+        assert_eq!(codes.len(), Language::all().len());
+        assert_eq!(names.len(), Language::all().len());
+    }
+
+    #[test]
+    fn parse_codes_and_names() {
+        for lang in Language::all() {
+            assert_eq!(lang.code().parse::<Language>().unwrap(), *lang);
+            assert_eq!(lang.to_string().parse::<Language>().unwrap(), *lang);
+            assert_eq!(lang.native_name().parse::<Language>().unwrap(), *lang);
+            assert_eq!(
+                lang.code().to_uppercase().parse::<Language>().unwrap(),
+                *lang
+            );
+        }
+
+        #[cfg(feature = "chinese-simplified")]
+        assert_eq!(
+            "chinese-simplified".parse::<Language>().unwrap(),
+            Language::ChineseSimplified
+        );
+        #[cfg(feature = "spanish")]
+        assert_eq!("ESPAÑOL".parse::<Language>().unwrap(), Language::Spanish);
+        assert!("klingon".parse::<Language>().is_err());
+        assert!("und".parse::<Language>().is_err());
     }
 }
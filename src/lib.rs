@@ -23,22 +23,62 @@
 //! ```
 //!
 #[macro_use]
-extern crate failure;
-#[macro_use]
 extern crate once_cell;
 
+pub mod codec;
+mod codex32;
+mod electrum;
+mod encoding;
 mod error;
+mod index_format;
 mod keyphrase;
 mod keyphrase_type;
 mod language;
+mod passphrase;
 mod seed;
+mod seed_options;
+mod seedqr;
+mod sheet;
+mod slip39;
 mod util;
 
 mod crypto;
 
+#[cfg(any(feature = "bip32", feature = "slip10"))]
+mod derivation;
+
+#[cfg(feature = "bip32")]
+mod bip32;
+#[cfg(feature = "bip85")]
+mod bip85;
+#[cfg(feature = "keystore")]
+mod keystore;
+#[cfg(feature = "slip10")]
+mod slip10;
+
 pub use self::keyphrase::KeyPhrase;
-pub use error::ErrorKind;
-pub use keyphrase_type::KeyPhraseType;
-pub use language::Language;
+#[cfg(feature = "bip32")]
+pub use bip32::{ExtendedKeyVersion, ExtendedPrivateKey, ExtendedPublicKey};
+pub use codex32::Codex32Share;
+#[cfg(any(feature = "bip32", feature = "slip10"))]
+pub use derivation::{ChildNumber, DerivationPath};
+pub use electrum::{
+    ElectrumOldSeed, ElectrumOldWordList, ElectrumSeed, ElectrumSeedType, PhraseFormat,
+};
+pub use encoding::Encoding;
+pub use error::{Error, ErrorKind};
+pub use index_format::IndexFormat;
+pub use keyphrase_type::{KeyPhraseType, LengthPolicy};
+#[cfg(feature = "keystore")]
+pub use keystore::{Keystore, KeystoreParams};
+pub use language::{CustomLanguage, Language, WordList, WordMap};
+pub use passphrase::{Capitalization, PassphraseGenerator};
 pub use seed::Seed;
+pub use seed_options::{Pbkdf2Hash, SeedKdf, SeedOptions};
+#[cfg(feature = "qr")]
+pub use seedqr::QrMatrix;
+pub use sheet::BackupSheet;
+#[cfg(feature = "slip10")]
+pub use slip10::Ed25519ExtendedKey;
+pub use slip39::{Share, ShareGroup};
 pub use util::*;
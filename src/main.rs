@@ -1,8 +1,88 @@
-use keyphrase::{KeyPhrase, KeyPhraseType, Language, Seed};
+use keyphrase::{
+    BackupSheet, IndexFormat, KeyPhrase, KeyPhraseType, Language, Seed, WordList, WordMap,
+};
+use std::env;
+use std::io::{self, BufRead, Write};
+use std::process;
+
+type Error = Box<dyn std::error::Error>;
+
+const USAGE: &str = "\
+usage: keyphrase [generate]
+       keyphrase recover [--words <count>] [--lang <language>] [--echo] [--reveal]
+       keyphrase convert --to <language> [--from <language>]
+       keyphrase sheet [--format <format>] [--index <format>] [--lang <language>]
+                       [--blank] [--words <count>]
+
+recover options:
+    --words <count>     number of words in the phrase (12, 15, 18, 21 or 24)
+    --lang <language>   language of the phrase, by name or code (default: english)
+    --echo              show words on screen while typing them
+    --reveal            print the recovered phrases
+
+convert options:
+    --to <language>     language to convert the phrase read from stdin to, by name or code
+    --from <language>   language of the phrase read from stdin, by name or code (default: english)
+
+sheet options:
+    --format <format>   text or svg (default: text)
+    --index <format>    one-based, zero-based or binary word indices (default: one-based)
+    --lang <language>   language of the phrase read from stdin, by name or code (default: english)
+    --blank             print a template to fill in by hand instead of reading a phrase
+    --words <count>     number of words on a blank template (default: 12)
+";
+
+/// Maximum number of autocomplete matches listed for an ambiguous prefix
+const MAX_COMPLETIONS: usize = 8;
 
 fn main() {
     better_panic::install();
 
+    let args: Vec<String> = env::args().skip(1).collect();
+
+    let result: Result<(), Error> = match args.first().map(String::as_str) {
+        None | Some("generate") => {
+            generate();
+            Ok(())
+        }
+        Some("recover") => RecoverOptions::parse(&args[1..]).and_then(|options: RecoverOptions| {
+            let stdin = io::stdin();
+            let _hidden = terminal::HiddenInput::new(options.echo);
+
+            recover(
+                &options,
+                &mut stdin.lock(),
+                &mut io::stderr(),
+                &mut io::stdout(),
+            )
+        }),
+        Some("convert") => ConvertOptions::parse(&args[1..]).and_then(|options: ConvertOptions| {
+            let stdin = io::stdin();
+
+            convert(&options, &mut stdin.lock(), &mut io::stdout())
+        }),
+        Some("sheet") => SheetOptions::parse(&args[1..]).and_then(|options: SheetOptions| {
+            let stdin = io::stdin();
+
+            sheet(&options, &mut stdin.lock(), &mut io::stdout())
+        }),
+        Some("-h") | Some("--help") => {
+            print!("{}", USAGE);
+            Ok(())
+        }
+        Some(command) => Err(Error::from(format!(
+            "unknown command: {}\n\n{}",
+            command, USAGE
+        ))),
+    };
+
+    if let Err(err) = result {
+        eprintln!("error: {}", err);
+        process::exit(1);
+    }
+}
+
+fn generate() {
     let word_count: KeyPhraseType = KeyPhraseType::Words12;
     let lang: Language = Language::English;
     let keyphrase: KeyPhrase = KeyPhrase::new(word_count, lang);
@@ -11,11 +91,388 @@ fn main() {
 
     let seed: Seed = Seed::new(&keyphrase, "");
     println!("{:X}", seed);
+}
+
+struct RecoverOptions {
+    keyphrase_type: Option<KeyPhraseType>,
+    lang: Language,
+    echo: bool,
+    reveal: bool,
+}
+
+impl RecoverOptions {
+    fn parse(args: &[String]) -> Result<RecoverOptions, Error> {
+        let mut options = RecoverOptions {
+            keyphrase_type: None,
+            lang: Language::English,
+            echo: false,
+            reveal: false,
+        };
+        let mut args = args.iter();
+
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--words" => {
+                    let value: &String = args
+                        .next()
+                        .ok_or_else(|| Error::from("--words needs a value"))?;
+                    let count: usize = value
+                        .parse()
+                        .map_err(|_| Error::from(format!("invalid word count: {}", value)))?;
+
+                    options.keyphrase_type = Some(KeyPhraseType::for_word_count(count)?);
+                }
+                "--lang" => {
+                    let value: &String = args
+                        .next()
+                        .ok_or_else(|| Error::from("--lang needs a value"))?;
+
+                    options.lang = value.parse()?;
+                }
+                "--echo" => options.echo = true,
+                "--reveal" => options.reveal = true,
+                _ => return Err(Error::from(format!("unknown option: {}\n\n{}", arg, USAGE))),
+            }
+        }
+
+        Ok(options)
+    }
+}
+
+struct ConvertOptions {
+    from: Language,
+    to: Language,
+}
+
+impl ConvertOptions {
+    fn parse(args: &[String]) -> Result<ConvertOptions, Error> {
+        let mut from: Language = Language::English;
+        let mut to: Option<Language> = None;
+        let mut args = args.iter();
+
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--from" => {
+                    let value: &String = args
+                        .next()
+                        .ok_or_else(|| Error::from("--from needs a value"))?;
+
+                    from = value.parse()?;
+                }
+                "--to" => {
+                    let value: &String = args
+                        .next()
+                        .ok_or_else(|| Error::from("--to needs a value"))?;
+
+                    to = Some(value.parse()?);
+                }
+                _ => return Err(Error::from(format!("unknown option: {}\n\n{}", arg, USAGE))),
+            }
+        }
+
+        let to: Language = to.ok_or_else(|| Error::from("--to is required"))?;
+
+        Ok(ConvertOptions { from, to })
+    }
+}
+
+struct SheetOptions {
+    svg: bool,
+    index_format: IndexFormat,
+    lang: Language,
+    blank: bool,
+    keyphrase_type: KeyPhraseType,
+}
+
+impl SheetOptions {
+    fn parse(args: &[String]) -> Result<SheetOptions, Error> {
+        let mut options = SheetOptions {
+            svg: false,
+            index_format: IndexFormat::OneBased,
+            lang: Language::English,
+            blank: false,
+            keyphrase_type: KeyPhraseType::Words12,
+        };
+        let mut args = args.iter();
+
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--format" => {
+                    let value: &String = args
+                        .next()
+                        .ok_or_else(|| Error::from("--format needs a value"))?;
+
+                    options.svg = match value.as_str() {
+                        "text" => false,
+                        "svg" => true,
+                        _ => return Err(Error::from(format!("invalid sheet format: {}", value))),
+                    };
+                }
+                "--index" => {
+                    let value: &String = args
+                        .next()
+                        .ok_or_else(|| Error::from("--index needs a value"))?;
+
+                    options.index_format = match value.as_str() {
+                        "one-based" => IndexFormat::OneBased,
+                        "zero-based" => IndexFormat::ZeroBased,
+                        "binary" => IndexFormat::Binary,
+                        _ => return Err(Error::from(format!("invalid index format: {}", value))),
+                    };
+                }
+                "--lang" => {
+                    let value: &String = args
+                        .next()
+                        .ok_or_else(|| Error::from("--lang needs a value"))?;
+
+                    options.lang = value.parse()?;
+                }
+                "--blank" => options.blank = true,
+                "--words" => {
+                    let value: &String = args
+                        .next()
+                        .ok_or_else(|| Error::from("--words needs a value"))?;
+                    let count: usize = value
+                        .parse()
+                        .map_err(|_| Error::from(format!("invalid word count: {}", value)))?;
+
+                    options.keyphrase_type = KeyPhraseType::for_word_count(count)?;
+                }
+                _ => return Err(Error::from(format!("unknown option: {}\n\n{}", arg, USAGE))),
+            }
+        }
+
+        Ok(options)
+    }
+}
+
+/// Interactive recovery of a partially known keyphrase
+///
+/// Words are read one per line from `input`. Each word can be typed in full or as an unambiguous
+/// prefix, and `?` marks a word as unknown. Prompts and feedback go to `prompt`, the result of the
+/// checksum-based candidate search goes to `output`. Neither the typed nor the recovered words are
+/// written anywhere unless asked for with `--echo` and `--reveal` respectively.
+fn recover<R, P, O>(
+    options: &RecoverOptions,
+    input: &mut R,
+    prompt: &mut P,
+    output: &mut O,
+) -> Result<(), Error>
+where
+    R: BufRead,
+    P: Write,
+    O: Write,
+{
+    let keyphrase_type: KeyPhraseType = match options.keyphrase_type {
+        Some(keyphrase_type) => keyphrase_type,
+        None => loop {
+            write!(prompt, "Number of words (12, 15, 18, 21, 24): ")?;
+            prompt.flush()?;
+
+            let line: String = read_line(input)?;
+
+            match line.parse().map(KeyPhraseType::for_word_count) {
+                Ok(Ok(keyphrase_type)) => break keyphrase_type,
+                _ => writeln!(prompt, "  invalid word count: {}", line)?,
+            }
+        },
+    };
+
+    let wordlist: &WordList = options.lang.wordlist();
+    let wordmap: &WordMap = options.lang.wordmap();
+    let word_count: usize = keyphrase_type.word_count();
+    let mut words: Vec<Option<&str>> = Vec::with_capacity(word_count);
+
+    writeln!(
+        prompt,
+        "Enter each word of the phrase, or ? if the word is unknown."
+    )?;
+
+    while words.len() < word_count {
+        write!(prompt, "Word {}/{}: ", words.len() + 1, word_count)?;
+        prompt.flush()?;
+
+        let line: String = read_line(input)?.to_lowercase();
+
+        if line == "?" {
+            writeln!(prompt, "  marked as unknown")?;
+            words.push(None);
+            continue;
+        }
+
+        let completions: Vec<&str> = match wordmap.get_bits(&line) {
+            Some(bits) => vec![wordlist.get_word(bits)],
+            None => wordlist.get_words_by_prefix(&line),
+        };
+
+        match completions.len() {
+            0 => {
+                writeln!(prompt, "  invalid word")?;
+
+                let suggestions: Vec<&str> = wordlist.suggestions(&line);
+
+                if !suggestions.is_empty() {
+                    writeln!(prompt, "  did you mean: {}", suggestions.join(", "))?;
+                }
+            }
+            1 => {
+                if options.echo {
+                    writeln!(prompt, "  {}", completions[0])?;
+                } else {
+                    writeln!(prompt, "  ok")?;
+                }
+
+                words.push(Some(completions[0]));
+            }
+            n => {
+                let listed: Vec<&str> = completions.into_iter().take(MAX_COMPLETIONS).collect();
+                let more: String = if n > MAX_COMPLETIONS {
+                    format!(" and {} more", n - MAX_COMPLETIONS)
+                } else {
+                    String::new()
+                };
+
+                writeln!(prompt, "  ambiguous: {}{}", listed.join(", "), more)?;
+            }
+        }
+    }
+
+    let candidates: Vec<KeyPhrase> = KeyPhrase::find_candidates(&words, options.lang.clone())?;
+
+    match candidates.len() {
+        0 => return Err(Error::from("no valid keyphrase matches the words entered")),
+        1 => writeln!(output, "1 valid keyphrase found")?,
+        n => writeln!(output, "{} valid keyphrases found", n)?,
+    }
+
+    if options.reveal {
+        for keyphrase in &candidates {
+            writeln!(output, "{}", keyphrase.phrase())?;
+        }
+    } else {
+        writeln!(output, "rerun with --reveal to print them")?;
+    }
+
+    Ok(())
+}
+
+/// Convert the keyphrase read from `input` to another language
+///
+/// The phrase is read from stdin rather than the command line so it doesn't end up in the shell
+/// history.
+fn convert<R, O>(options: &ConvertOptions, input: &mut R, output: &mut O) -> Result<(), Error>
+where
+    R: BufRead,
+    O: Write,
+{
+    let phrase: String = read_line(input)?;
+    let keyphrase: KeyPhrase = KeyPhrase::from_phrase(phrase, options.from.clone())?;
+
+    writeln!(
+        output,
+        "{}",
+        keyphrase.to_language(options.to.clone()).phrase()
+    )?;
+
+    Ok(())
+}
+
+/// Print a backup sheet for the keyphrase read from `input`, or a blank template
+///
+/// With the `bip32` cargo feature the sheet shows the master fingerprint of the keyphrase without
+/// a password.
+fn sheet<R, O>(options: &SheetOptions, input: &mut R, output: &mut O) -> Result<(), Error>
+where
+    R: BufRead,
+    O: Write,
+{
+    let sheet: BackupSheet = if options.blank {
+        BackupSheet::blank(options.keyphrase_type, options.lang.clone())
+    } else {
+        let phrase: String = read_line(input)?;
+        let keyphrase: KeyPhrase = KeyPhrase::from_phrase(phrase, options.lang.clone())?;
+        let sheet: BackupSheet = BackupSheet::new(&keyphrase);
+
+        #[cfg(feature = "bip32")]
+        let sheet: BackupSheet = sheet.fingerprint(Seed::new(&keyphrase, "").master_fingerprint()?);
+
+        sheet
+    };
+
+    let sheet: BackupSheet = sheet.index_format(options.index_format);
+
+    if options.svg {
+        write!(output, "{}", sheet.to_svg())?;
+    } else {
+        write!(output, "{}", sheet.to_text())?;
+    }
+
+    Ok(())
+}
+
+/// Read a single trimmed line, failing on end of input
+fn read_line<R: BufRead>(input: &mut R) -> Result<String, Error> {
+    let mut line = String::new();
+
+    if input.read_line(&mut line)? == 0 {
+        return Err(Error::from("unexpected end of input"));
+    }
+
+    Ok(line.trim().to_string())
+}
+
+#[cfg(unix)]
+mod terminal {
+    /// Turns off terminal echo on stdin for as long as it is alive
+    ///
+    /// Does nothing if stdin is not a terminal, or if echo was asked for.
+    pub struct HiddenInput {
+        original: Option<libc::termios>,
+    }
+
+    impl HiddenInput {
+        pub fn new(echo: bool) -> HiddenInput {
+            if echo || unsafe { libc::isatty(libc::STDIN_FILENO) } != 1 {
+                return HiddenInput { original: None };
+            }
+
+            let mut term: libc::termios = unsafe { std::mem::zeroed() };
+
+            if unsafe { libc::tcgetattr(libc::STDIN_FILENO, &mut term) } != 0 {
+                return HiddenInput { original: None };
+            }
+
+            let original: libc::termios = term;
+
+            // Keep echoing the newline so prompts don't pile up on a single line
+            term.c_lflag &= !libc::ECHO;
+            term.c_lflag |= libc::ECHONL;
+
+            unsafe { libc::tcsetattr(libc::STDIN_FILENO, libc::TCSANOW, &term) };
+
+            HiddenInput {
+                original: Some(original),
+            }
+        }
+    }
+
+    impl Drop for HiddenInput {
+        fn drop(&mut self) {
+            if let Some(original) = self.original {
+                unsafe { libc::tcsetattr(libc::STDIN_FILENO, libc::TCSANOW, &original) };
+            }
+        }
+    }
+}
 
-    // get the HD wallet seed as raw bytes
-    // let seed_bytes: u[u8] = seed.as_bytes();
+#[cfg(not(unix))]
+mod terminal {
+    /// Terminal echo control is only supported on unix
+    pub struct HiddenInput;
 
-    // print the HD wallet seed as a hex string
-    // println!("{:#?}", seed_bytes);
-    // println!("{:#?}", seed_bytes.len());
+    impl HiddenInput {
+        pub fn new(_echo: bool) -> HiddenInput {
+            HiddenInput
+        }
+    }
 }
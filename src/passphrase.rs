@@ -0,0 +1,320 @@
+use crate::language::{Language, WordList};
+use crate::util::Bits11;
+use rand::{thread_rng, RngCore};
+
+/// How the words of a generated passphrase are capitalized
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Capitalization {
+    /// Words as they are in the word list, the default
+    Lowercase,
+    /// Every letter of every word in upper case
+    Uppercase,
+    /// The first letter of every word in upper case
+    FirstLetter,
+    /// The first letter of each word in upper case or not, with even odds
+    ///
+    /// Adds up to one bit of entropy per word, for words with a first letter that has a case.
+    Random,
+}
+
+/// Diceware style passphrase generator, drawing words uniformly from a [`Language`][Language]
+///
+/// The generated passphrase is meant as the second factor given to [`Seed::new()`][Seed::new()],
+/// next to a [`KeyPhrase`][KeyPhrase]. Every word is drawn independently from the word list, so
+/// words can repeat. Optionally a group of random digits is inserted between two words, or at
+/// either end.
+///
+/// # Example
+///
+/// ```
+/// use keyphrase::{Capitalization, Language, PassphraseGenerator};
+///
+/// let generator = PassphraseGenerator::new(6, Language::English)
+///     .separator("-")
+///     .capitalization(Capitalization::FirstLetter)
+///     .digits(2);
+///
+/// let passphrase: String = generator.generate();
+///
+/// assert_eq!(passphrase.split('-').count(), 7);
+/// assert!(generator.entropy_bits() > 75.0);
+/// ```
+///
+/// [Language]: ./enum.Language.html
+/// [KeyPhrase]: ./keyphrase/struct.KeyPhrase.html
+/// [Seed::new()]: ./seed/struct.Seed.html#method.new
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct PassphraseGenerator {
+    words: usize,
+    lang: Language,
+    separator: String,
+    capitalization: Capitalization,
+    digits: usize,
+}
+
+impl PassphraseGenerator {
+    /// Generate passphrases of `words` words from the word list of `lang`
+    ///
+    /// Words are separated by a space and kept in lower case, without digits.
+    pub fn new(words: usize, lang: Language) -> PassphraseGenerator {
+        PassphraseGenerator {
+            words,
+            lang,
+            separator: " ".to_string(),
+            capitalization: Capitalization::Lowercase,
+            digits: 0,
+        }
+    }
+
+    /// Put `separator` between words
+    ///
+    /// The entropy estimate assumes the words can be told apart again, so the separator should
+    /// not be empty, and should not contain letters or digits.
+    pub fn separator(mut self, separator: &str) -> PassphraseGenerator {
+        self.separator = separator.to_string();
+        self
+    }
+
+    /// Capitalize words with `capitalization`
+    pub fn capitalization(mut self, capitalization: Capitalization) -> PassphraseGenerator {
+        self.capitalization = capitalization;
+        self
+    }
+
+    /// Insert a group of `digits` random decimal digits at a random position between the words
+    ///
+    /// No digits are inserted for 0, the default.
+    pub fn digits(mut self, digits: usize) -> PassphraseGenerator {
+        self.digits = digits;
+        self
+    }
+
+    /// Get the entropy of the generated passphrases, in bits
+    ///
+    /// This is exact, as long as the words can be told apart: every word adds the bits of a
+    /// uniform choice from the word list, plus with [`Capitalization::Random`][Capitalization::Random]
+    /// one bit weighted by the share of words with a first letter that has a case. A group of
+    /// digits adds the bits of its digits and of its position.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use keyphrase::{Capitalization, Language, PassphraseGenerator};
+    ///
+    /// let generator = PassphraseGenerator::new(6, Language::English);
+    /// assert_eq!(generator.entropy_bits(), 66.0);
+    ///
+    /// let generator = generator.capitalization(Capitalization::Random);
+    /// assert_eq!(generator.entropy_bits(), 72.0);
+    ///
+    /// #[cfg(feature = "japanese")]
+    /// {
+    ///     let generator =
+    ///         PassphraseGenerator::new(6, Language::Japanese).capitalization(Capitalization::Random);
+    ///     assert_eq!(generator.entropy_bits(), 66.0);
+    /// }
+    /// ```
+    ///
+    /// [Capitalization::Random]: ./enum.Capitalization.html#variant.Random
+    pub fn entropy_bits(&self) -> f64 {
+        let wordlist: &WordList = self.lang.wordlist();
+        let length: usize = wordlist.len();
+
+        let mut word_bits: f64 = (length as f64).log2();
+
+        if self.capitalization == Capitalization::Random {
+            let cased: usize = (0..length)
+                .map(|index: usize| wordlist.get_word(Bits11::from(index as u16)))
+                .filter(|word: &&str| capitalize(word) != *word)
+                .count();
+
+            word_bits += cased as f64 / length as f64;
+        }
+
+        let mut bits: f64 = self.words as f64 * word_bits;
+
+        if self.digits > 0 {
+            bits += self.digits as f64 * 10f64.log2() + ((self.words + 1) as f64).log2();
+        }
+
+        bits
+    }
+
+    /// Generate a passphrase with the operating system's random number generator
+    pub fn generate(&self) -> String {
+        self.generate_with_rng(&mut thread_rng())
+    }
+
+    /// Generate a passphrase with the given random number generator
+    ///
+    /// Only use a cryptographically secure generator for passphrases that protect anything, a
+    /// seeded one is meant for tests.
+    pub fn generate_with_rng<R>(&self, rng: &mut R) -> String
+    where
+        R: RngCore + ?Sized,
+    {
+        let wordlist: &WordList = self.lang.wordlist();
+
+        let mut parts: Vec<String> = (0..self.words)
+            .map(|_| {
+                let index: usize = uniform_index(rng, wordlist.len());
+                let word: &str = wordlist.get_word(Bits11::from(index as u16));
+
+                match self.capitalization {
+                    Capitalization::Lowercase => word.to_string(),
+                    Capitalization::Uppercase => word.to_uppercase(),
+                    Capitalization::FirstLetter => capitalize(word),
+                    Capitalization::Random => {
+                        if uniform_index(rng, 2) == 1 {
+                            capitalize(word)
+                        } else {
+                            word.to_string()
+                        }
+                    }
+                }
+            })
+            .collect();
+
+        if self.digits > 0 {
+            let position: usize = uniform_index(rng, self.words + 1);
+            let digits: String = (0..self.digits)
+                .map(|_| (b'0' + uniform_index(rng, 10) as u8) as char)
+                .collect();
+
+            parts.insert(position, digits);
+        }
+
+        parts.join(&self.separator)
+    }
+}
+
+/// Upper case the first letter of `word`
+fn capitalize(word: &str) -> String {
+    let mut chars = word.chars();
+
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+/// Draw an index below `n` with even odds for every index
+///
+/// A random `u32` is only reduced modulo `n` when it is below the largest multiple of `n` that
+/// fits, anything above is drawn again. Otherwise the indices below `2^32 % n` would come up
+/// slightly more often.
+fn uniform_index<R>(rng: &mut R, n: usize) -> usize
+where
+    R: RngCore + ?Sized,
+{
+    assert!(
+        n > 0 && n as u64 <= 1 << 32,
+        "Can only draw from 1 to 2^32 values"
+    );
+
+    let n: u64 = n as u64;
+    let limit: u64 = (1 << 32) / n * n;
+
+    loop {
+        let value: u64 = rng.next_u32() as u64;
+
+        if value < limit {
+            return (value % n) as usize;
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use rand::Error as RandError;
+
+    /// Replays the given values, then panics
+    struct ReplayRng(Vec<u32>);
+
+    impl RngCore for ReplayRng {
+        fn next_u32(&mut self) -> u32 {
+            assert!(!self.0.is_empty(), "Ran out of values");
+
+            self.0.remove(0)
+        }
+
+        fn next_u64(&mut self) -> u64 {
+            self.next_u32() as u64
+        }
+
+        /// Takes one value for every 4 bytes, little endian
+        fn fill_bytes(&mut self, dest: &mut [u8]) {
+            for chunk in dest.chunks_mut(4) {
+                let bytes: [u8; 4] = self.next_u32().to_le_bytes();
+
+                chunk.copy_from_slice(&bytes[..chunk.len()]);
+            }
+        }
+
+        fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), RandError> {
+            self.fill_bytes(dest);
+
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn biased_values_are_drawn_again() {
+        // 2^32 = 429496729 * 10 + 6, so the last 6 values would favor 0 to 5
+        let mut rng = ReplayRng(vec![u32::MAX, 4_294_967_290, 4_294_967_289]);
+
+        assert_eq!(uniform_index(&mut rng, 10), 9);
+        assert!(rng.0.is_empty());
+
+        // 2048 divides 2^32, nothing is drawn again
+        let mut rng = ReplayRng(vec![u32::MAX]);
+
+        assert_eq!(uniform_index(&mut rng, 2048), 2047);
+    }
+
+    #[test]
+    fn replay_fills_bytes() {
+        let mut rng = ReplayRng(vec![0x0403_0201, 0x0000_0605]);
+        let mut bytes = [0u8; 6];
+
+        rng.fill_bytes(&mut bytes);
+
+        assert_eq!(bytes, [1, 2, 3, 4, 5, 6]);
+        assert!(rng.0.is_empty());
+    }
+
+    #[test]
+    fn generate_replayed_values() {
+        let generator = PassphraseGenerator::new(3, Language::English)
+            .separator(".")
+            .capitalization(Capitalization::Random)
+            .digits(2);
+
+        // Word and capitalization for each word, then position and digits, one drawn again
+        let mut rng = ReplayRng(vec![0, 1, 2047, 0, 1282, 1, 1, u32::MAX, 7, 3]);
+
+        assert_eq!(generator.generate_with_rng(&mut rng), "Abandon.73.zoo.Park");
+    }
+
+    #[test]
+    fn capitalization() {
+        let generator = PassphraseGenerator::new(2, Language::English);
+
+        for (capitalization, expected) in &[
+            (Capitalization::Lowercase, "abandon zoo"),
+            (Capitalization::Uppercase, "ABANDON ZOO"),
+            (Capitalization::FirstLetter, "Abandon Zoo"),
+        ] {
+            let mut rng = ReplayRng(vec![0, 2047]);
+
+            assert_eq!(
+                generator
+                    .clone()
+                    .capitalization(*capitalization)
+                    .generate_with_rng(&mut rng),
+                *expected
+            );
+        }
+    }
+}
@@ -1,6 +1,17 @@
-use crate::crypto::pbkdf2;
+#[cfg(feature = "bip32")]
+use crate::bip32::ExtendedPrivateKey;
+use crate::error::{Error, ErrorKind};
 use crate::keyphrase::KeyPhrase;
+use crate::seed_options::{SeedOptions, MAX_SEED_LENGTH, MIN_SEED_LENGTH};
+use crate::util::{from_hex, to_hex};
 use std::fmt;
+use std::str::FromStr;
+
+/// Start of the PBKDF2 salt, followed by the password
+const SALT_PREFIX: &str = "keyphrase";
+
+/// Byte after the salt prefix of domain separated seeds, which is not valid in UTF-8
+const DOMAIN_MARKER: u8 = 0xFF;
 
 /// The secret value used to derive HD wallet addresses from a [`KeyPhrase`][KeyPhrase] phrase.
 ///
@@ -9,16 +20,20 @@ use std::fmt;
 /// a valid, intact KeyPhrase can be used to derive HD wallet addresses.
 ///
 /// To get the raw byte value use [`Seed::as_bytes()`][Seed::as_bytes()]. These can be used to derive
-/// HD wallet addresses using another crate (deriving HD wallet addresses is outside the scope of this
-/// crate and the BIP39 standard).
+/// HD wallet addresses using another crate, with [`ExtendedPrivateKey::from_seed()`][ExtendedPrivateKey::from_seed()]
+/// when the `bip32` feature is enabled, or with [`Ed25519ExtendedKey::from_seed()`][Ed25519ExtendedKey::from_seed()]
+/// for ed25519 keys when the `slip10` feature is enabled.
 ///
 /// [KeyPhrase]: ./keyphrase/struct.KeyPhrase.html
 /// [Seed]: ./seed/struct.Seed.html
 /// [Seed::as_bytes()]: ./seed/struct.Seed.html#method.as_bytes
+/// [ExtendedPrivateKey::from_seed()]: ./bip32/struct.ExtendedPrivateKey.html#method.from_seed
+/// [Ed25519ExtendedKey::from_seed()]: ./slip10/struct.Ed25519ExtendedKey.html#method.from_seed
 
 #[derive(Clone)]
 pub struct Seed {
     bytes: Vec<u8>,
+    options: Option<SeedOptions>,
 }
 
 impl Seed {
@@ -40,10 +55,155 @@ impl Seed {
     /// let seed: Seed = Seed::new(&keyphrase, "");
     /// ```
     pub fn new(keyphrase: &KeyPhrase, password: &str) -> Self {
-        let salt: String = format!("keyphrase{}", password);
-        let bytes: Vec<u8> = pbkdf2(keyphrase.entropy(), &salt);
+        Seed::new_with_options(keyphrase, password, SeedOptions::default())
+            .expect("The default seed options are valid")
+    }
 
-        Self { bytes }
+    /// Generates the seed with another key derivation function or seed length
+    ///
+    /// The options are kept with the seed, see [`Seed::options()`][Seed::options()]. Returns an
+    /// `Error` if they are not valid, see [`SeedOptions::validate()`][SeedOptions::validate()].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use keyphrase::{KeyPhrase, Language, Pbkdf2Hash, Seed, SeedOptions};
+    ///
+    /// let phrase = "park remain person kitchen mule spell knee armed position rail grid ankle";
+    /// let keyphrase = KeyPhrase::from_phrase(phrase, Language::English).unwrap();
+    ///
+    /// let options = SeedOptions::new().pbkdf2(Pbkdf2Hash::Sha512, 100_000);
+    /// let seed: Seed = Seed::new_with_options(&keyphrase, "", options).unwrap();
+    ///
+    /// // Store this to derive the same seed again later
+    /// let recorded: String = seed.options().unwrap().to_string();
+    ///
+    /// let again = Seed::new_with_options(&keyphrase, "", recorded.parse().unwrap()).unwrap();
+    ///
+    /// assert_eq!(seed.as_bytes(), again.as_bytes());
+    /// ```
+    ///
+    /// [Seed::options()]: ./seed/struct.Seed.html#method.options
+    /// [SeedOptions::validate()]: ./struct.SeedOptions.html#method.validate
+    pub fn new_with_options(
+        keyphrase: &KeyPhrase,
+        password: &str,
+        options: SeedOptions,
+    ) -> Result<Self, Error> {
+        let salt: String = format!("{}{}", SALT_PREFIX, password);
+        let bytes: Vec<u8> = options.derive(keyphrase.entropy(), salt.as_bytes())?;
+
+        Ok(Self {
+            bytes,
+            options: Some(options),
+        })
+    }
+
+    /// Generates a seed that belongs to a single application or network
+    ///
+    /// Seeds derived from the same phrase and password under different domains are unrelated,
+    /// and never equal to the seed from [`Seed::new()`][Seed::new()]. The PBKDF2 salt is
+    ///
+    /// ```text
+    /// "keyphrase" || 0xFF || len(domain) || domain || len(password) || password
+    /// ```
+    ///
+    /// with the lengths as 4 byte big-endian byte counts of the UTF-8 strings. The `0xFF` byte never
+    /// occurs in UTF-8, so no password given to [`Seed::new()`][Seed::new()] gives the same salt,
+    /// and the length prefixes keep domains and passwords from running into each other.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use keyphrase::{KeyPhrase, KeyPhraseType, Language, Seed};
+    ///
+    /// let keyphrase = KeyPhrase::new(KeyPhraseType::Words12, Language::English);
+    ///
+    /// let mainnet: Seed = Seed::new_with_domain(&keyphrase, "earth-mainnet", "");
+    /// let testnet: Seed = Seed::new_with_domain(&keyphrase, "earth-testnet", "");
+    ///
+    /// assert_ne!(mainnet.as_bytes(), testnet.as_bytes());
+    /// assert_ne!(mainnet.as_bytes(), Seed::new(&keyphrase, "").as_bytes());
+    /// ```
+    ///
+    /// [Seed::new()]: ./seed/struct.Seed.html#method.new
+    pub fn new_with_domain(keyphrase: &KeyPhrase, domain: &str, password: &str) -> Self {
+        Seed::new_with_domain_and_options(keyphrase, domain, password, SeedOptions::default())
+            .expect("The default seed options are valid")
+    }
+
+    /// Generates a seed for a domain like [`Seed::new_with_domain()`][Seed::new_with_domain()],
+    /// with the given [`SeedOptions`][SeedOptions]
+    ///
+    /// [Seed::new_with_domain()]: ./seed/struct.Seed.html#method.new_with_domain
+    /// [SeedOptions]: ./struct.SeedOptions.html
+    pub fn new_with_domain_and_options(
+        keyphrase: &KeyPhrase,
+        domain: &str,
+        password: &str,
+        options: SeedOptions,
+    ) -> Result<Self, Error> {
+        let mut salt: Vec<u8> = SALT_PREFIX.as_bytes().to_vec();
+
+        salt.push(DOMAIN_MARKER);
+
+        for part in &[domain, password] {
+            salt.extend_from_slice(&(part.len() as u32).to_be_bytes());
+            salt.extend_from_slice(part.as_bytes());
+        }
+
+        let bytes: Vec<u8> = options.derive(keyphrase.entropy(), &salt)?;
+
+        Ok(Self {
+            bytes,
+            options: Some(options),
+        })
+    }
+
+    /// Rebuild a seed that was already derived from its raw bytes
+    ///
+    /// Returns an `Error` of kind `ErrorKind::InvalidSeedLength` unless there are 16 to 64 bytes.
+    /// The options it was derived with are unknown.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use keyphrase::{KeyPhrase, KeyPhraseType, Language, Seed};
+    ///
+    /// let keyphrase = KeyPhrase::new(KeyPhraseType::Words12, Language::English);
+    /// let seed: Seed = Seed::new(&keyphrase, "");
+    ///
+    /// let imported: Seed = Seed::from_bytes(seed.as_bytes()).unwrap();
+    ///
+    /// assert_eq!(imported.as_bytes(), seed.as_bytes());
+    /// assert_eq!(imported.options(), None);
+    /// ```
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, Error> {
+        if !(MIN_SEED_LENGTH..=MAX_SEED_LENGTH).contains(&bytes.len()) {
+            return Err(ErrorKind::InvalidSeedLength(bytes.len()).into());
+        }
+
+        Ok(Self {
+            bytes: bytes.to_vec(),
+            options: None,
+        })
+    }
+
+    /// Rebuild a seed that was already derived from hex, in either case and with an optional `0x`
+    ///
+    /// Returns an `Error` of kind `ErrorKind::InvalidHexCharacter` or `ErrorKind::InvalidHexLength`
+    /// if the hex is not valid, and checks the length like
+    /// [`Seed::from_bytes()`][Seed::from_bytes()]. This reads what `{:x}`, `{:X}` and `{:#x}`
+    /// write.
+    ///
+    /// [Seed::from_bytes()]: ./seed/struct.Seed.html#method.from_bytes
+    pub fn from_hex(hex: &str) -> Result<Self, Error> {
+        Seed::from_bytes(&from_hex(hex)?)
+    }
+
+    /// Get the options the seed was derived with, `None` for seeds rebuilt from bytes or hex
+    pub fn options(&self) -> Option<SeedOptions> {
+        self.options
     }
 
     /// Get the seed value as a byte slice
@@ -66,6 +226,30 @@ impl Seed {
     pub fn as_bytes(&self) -> &[u8] {
         &self.bytes
     }
+
+    /// Get the fingerprint of the BIP32 master key derived from this seed
+    ///
+    /// The fingerprint doesn't reveal the keys, but changes with both the phrase and the password.
+    /// Showing it after a restore lets users check they got the same wallet back.
+    ///
+    /// Only available with the `bip32` cargo feature.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use keyphrase::{KeyPhrase, Language, Seed};
+    ///
+    /// let phrase = "park remain person kitchen mule spell knee armed position rail grid ankle";
+    /// let keyphrase = KeyPhrase::from_phrase(phrase, Language::English).unwrap();
+    ///
+    /// let fingerprint: [u8; 4] = Seed::new(&keyphrase, "").master_fingerprint().unwrap();
+    ///
+    /// assert_ne!(fingerprint, Seed::new(&keyphrase, "TREZOR").master_fingerprint().unwrap());
+    /// ```
+    #[cfg(feature = "bip32")]
+    pub fn master_fingerprint(&self) -> Result<[u8; 4], Error> {
+        ExtendedPrivateKey::from_seed(self).map(|master: ExtendedPrivateKey| master.fingerprint())
+    }
 }
 
 impl AsRef<[u8]> for Seed {
@@ -74,6 +258,17 @@ impl AsRef<[u8]> for Seed {
     }
 }
 
+impl FromStr for Seed {
+    type Err = Error;
+
+    /// Parse a seed from hex, see [`Seed::from_hex()`][Seed::from_hex()]
+    ///
+    /// [Seed::from_hex()]: ./seed/struct.Seed.html#method.from_hex
+    fn from_str(hex: &str) -> Result<Seed, Error> {
+        Seed::from_hex(hex)
+    }
+}
+
 impl fmt::Debug for Seed {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         write!(f, "{:#X}", self)
@@ -86,11 +281,7 @@ impl fmt::LowerHex for Seed {
             f.write_str("0x")?;
         }
 
-        for byte in &self.bytes {
-            write!(f, "{:02x}", byte)?;
-        }
-
-        Ok(())
+        f.write_str(&to_hex(&self.bytes))
     }
 }
 
@@ -100,10 +291,7 @@ impl fmt::UpperHex for Seed {
             f.write_str("0x")?;
         }
 
-        for byte in &self.bytes {
-            write!(f, "{:02X}", byte)?;
-        }
-        Ok(())
+        f.write_str(&to_hex(&self.bytes).to_ascii_uppercase())
     }
 }
 
@@ -115,6 +303,7 @@ mod test {
     fn should_print_upper_hex_correctly() {
         let seed = Seed {
             bytes: vec![1, 10, 16, 255],
+            options: None,
         };
         let hex = format!("{:?}", seed);
         assert_eq!(hex, "0x010A10FF")
@@ -124,6 +313,7 @@ mod test {
     fn should_print_lower_hex_correctly() {
         let seed = Seed {
             bytes: vec![255, 16, 10, 1],
+            options: None,
         };
         let hex = format!("{:x}", seed);
         assert_eq!(hex, "ff100a01")
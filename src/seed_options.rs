@@ -0,0 +1,382 @@
+use crate::crypto::{self, PBKDF2_BYTES, PBKDF2_ROUNDS};
+use crate::error::{Error, ErrorKind};
+use std::fmt;
+use std::str::FromStr;
+
+/// Shortest seed that can be derived, in bytes
+pub(crate) const MIN_SEED_LENGTH: usize = 16;
+
+/// Longest seed that can be derived, in bytes
+pub(crate) const MAX_SEED_LENGTH: usize = 64;
+
+/// Most PBKDF2 iterations, well over what any wallet uses
+const MAX_PBKDF2_ROUNDS: u32 = 10_000_000;
+
+/// Most memory Argon2id may use, in KiB, 4 GiB
+#[cfg(feature = "argon2id")]
+const MAX_ARGON2_MEMORY: u32 = 4 * 1024 * 1024;
+
+/// Most Argon2id passes over the memory
+#[cfg(feature = "argon2id")]
+const MAX_ARGON2_TIME: u32 = 64;
+
+/// Most Argon2id lanes
+#[cfg(feature = "argon2id")]
+const MAX_ARGON2_PARALLELISM: u32 = 16;
+
+/// HMAC hash function used by PBKDF2
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Pbkdf2Hash {
+    /// HMAC-SHA512, the default
+    Sha512,
+    /// HMAC-SHA256
+    Sha256,
+}
+
+/// Key derivation function stretching the entropy of a keyphrase into a seed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SeedKdf {
+    /// PBKDF2 with the given hash and number of iterations
+    Pbkdf2 { hash: Pbkdf2Hash, rounds: u32 },
+    /// Argon2id with a memory cost in KiB, a time cost in passes and a number of lanes
+    ///
+    /// Only available with the `argon2id` cargo feature.
+    #[cfg(feature = "argon2id")]
+    Argon2id {
+        memory_cost: u32,
+        time_cost: u32,
+        parallelism: u32,
+    },
+}
+
+/// Parameters of the derivation of a [`Seed`][Seed] from a [`KeyPhrase`][KeyPhrase]
+///
+/// The defaults are PBKDF2 with HMAC-SHA512 and 2048 iterations, giving a 64 byte seed, the same
+/// as [`Seed::new()`][Seed::new()].
+///
+/// A seed can only be reproduced with the same options, so they should be stored along with the
+/// wallet. The `Display` form is a short string in the style of the PHC string format, which is
+/// read back with `FromStr`.
+///
+/// # Example
+///
+/// ```
+/// use keyphrase::{Pbkdf2Hash, SeedOptions};
+///
+/// let options = SeedOptions::new().pbkdf2(Pbkdf2Hash::Sha256, 100_000).length(32);
+///
+/// assert_eq!(options.to_string(), "$pbkdf2-sha256$i=100000,l=32");
+/// assert_eq!("$pbkdf2-sha256$i=100000,l=32".parse::<SeedOptions>().unwrap(), options);
+///
+/// assert_eq!(SeedOptions::default().to_string(), "$pbkdf2-sha512$i=2048,l=64");
+/// ```
+///
+/// [Seed]: ./seed/struct.Seed.html
+/// [KeyPhrase]: ./keyphrase/struct.KeyPhrase.html
+/// [Seed::new()]: ./seed/struct.Seed.html#method.new
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct SeedOptions {
+    kdf: SeedKdf,
+    length: usize,
+}
+
+impl SeedOptions {
+    /// Create the default options
+    pub fn new() -> SeedOptions {
+        SeedOptions {
+            kdf: SeedKdf::Pbkdf2 {
+                hash: Pbkdf2Hash::Sha512,
+                rounds: PBKDF2_ROUNDS,
+            },
+            length: PBKDF2_BYTES,
+        }
+    }
+
+    /// Use PBKDF2 with the given hash and number of iterations
+    pub fn pbkdf2(mut self, hash: Pbkdf2Hash, rounds: u32) -> SeedOptions {
+        self.kdf = SeedKdf::Pbkdf2 { hash, rounds };
+        self
+    }
+
+    /// Use Argon2id with a memory cost in KiB, a time cost in passes and a number of lanes
+    ///
+    /// Only available with the `argon2id` cargo feature.
+    #[cfg(feature = "argon2id")]
+    pub fn argon2id(mut self, memory_cost: u32, time_cost: u32, parallelism: u32) -> SeedOptions {
+        self.kdf = SeedKdf::Argon2id {
+            memory_cost,
+            time_cost,
+            parallelism,
+        };
+        self
+    }
+
+    /// Derive a seed of `length` bytes, from 16 to 64
+    pub fn length(mut self, length: usize) -> SeedOptions {
+        self.length = length;
+        self
+    }
+
+    /// Get the key derivation function
+    pub fn kdf(&self) -> SeedKdf {
+        self.kdf
+    }
+
+    /// Get the length of the derived seed, in bytes
+    pub fn seed_length(&self) -> usize {
+        self.length
+    }
+
+    /// Check the options can derive a seed
+    ///
+    /// Returns an `Error` of kind `ErrorKind::InvalidSeedLength` for lengths outside of 16 to
+    /// 64 bytes, and of kind `ErrorKind::InvalidSeedOptions` for parameters the key derivation
+    /// function rejects or that cost too much: more than 10 000 000 PBKDF2 rounds, or Argon2id
+    /// with more than 4 GiB of memory, 64 passes or 16 lanes.
+    pub fn validate(&self) -> Result<(), Error> {
+        if !(MIN_SEED_LENGTH..=MAX_SEED_LENGTH).contains(&self.length) {
+            return Err(ErrorKind::InvalidSeedLength(self.length).into());
+        }
+
+        match self.kdf {
+            SeedKdf::Pbkdf2 { rounds: 0, .. } => {
+                Err(ErrorKind::InvalidSeedOptions("PBKDF2 needs at least 1 round".into()).into())
+            }
+            SeedKdf::Pbkdf2 { rounds, .. } if rounds > MAX_PBKDF2_ROUNDS => {
+                Err(ErrorKind::InvalidSeedOptions(format!(
+                    "PBKDF2 takes at most {} rounds",
+                    MAX_PBKDF2_ROUNDS
+                ))
+                .into())
+            }
+            SeedKdf::Pbkdf2 { .. } => Ok(()),
+            #[cfg(feature = "argon2id")]
+            SeedKdf::Argon2id { .. } => self.argon2_params().map(|_| ()),
+        }
+    }
+
+    /// Stretch `input` into a seed
+    pub(crate) fn derive(&self, input: &[u8], salt: &[u8]) -> Result<Vec<u8>, Error> {
+        self.validate()?;
+
+        match self.kdf {
+            SeedKdf::Pbkdf2 { hash, rounds } => {
+                Ok(crypto::pbkdf2(input, salt, hash, rounds, self.length))
+            }
+            #[cfg(feature = "argon2id")]
+            SeedKdf::Argon2id { .. } => crypto::argon2id(input, salt, self.argon2_params()?)
+                .map_err(|err| ErrorKind::InvalidSeedOptions(err.to_string()).into()),
+        }
+    }
+
+    #[cfg(feature = "argon2id")]
+    fn argon2_params(&self) -> Result<argon2::Params, Error> {
+        match self.kdf {
+            SeedKdf::Argon2id {
+                memory_cost,
+                time_cost,
+                parallelism,
+            } => {
+                if memory_cost > MAX_ARGON2_MEMORY
+                    || time_cost > MAX_ARGON2_TIME
+                    || parallelism > MAX_ARGON2_PARALLELISM
+                {
+                    return Err(ErrorKind::InvalidSeedOptions(format!(
+                        "Argon2id takes at most m={},t={},p={}",
+                        MAX_ARGON2_MEMORY, MAX_ARGON2_TIME, MAX_ARGON2_PARALLELISM
+                    ))
+                    .into());
+                }
+
+                argon2::Params::new(memory_cost, time_cost, parallelism, Some(self.length))
+                    .map_err(|err| ErrorKind::InvalidSeedOptions(err.to_string()).into())
+            }
+            _ => unreachable!("only called for Argon2id"),
+        }
+    }
+}
+
+impl Default for SeedOptions {
+    fn default() -> SeedOptions {
+        SeedOptions::new()
+    }
+}
+
+impl fmt::Display for SeedOptions {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.kdf {
+            SeedKdf::Pbkdf2 { hash, rounds } => {
+                let name: &str = match hash {
+                    Pbkdf2Hash::Sha512 => "pbkdf2-sha512",
+                    Pbkdf2Hash::Sha256 => "pbkdf2-sha256",
+                };
+
+                write!(f, "${}$i={},l={}", name, rounds, self.length)
+            }
+            #[cfg(feature = "argon2id")]
+            SeedKdf::Argon2id {
+                memory_cost,
+                time_cost,
+                parallelism,
+            } => write!(
+                f,
+                "$argon2id$m={},t={},p={},l={}",
+                memory_cost, time_cost, parallelism, self.length
+            ),
+        }
+    }
+}
+
+impl FromStr for SeedOptions {
+    type Err = Error;
+
+    /// Parse options written by `Display`
+    ///
+    /// The parameters can be in any order, but all of them must be given. The options are
+    /// validated like by [`SeedOptions::validate()`][SeedOptions::validate()].
+    ///
+    /// [SeedOptions::validate()]: ./struct.SeedOptions.html#method.validate
+    fn from_str(options: &str) -> Result<SeedOptions, Error> {
+        let invalid = || ErrorKind::InvalidSeedOptions(options.to_string());
+
+        let mut parts = options.split('$');
+
+        let (name, params): (&str, &str) = match (parts.next(), parts.next(), parts.next()) {
+            (Some(""), Some(name), Some(params)) if parts.next().is_none() => (name, params),
+            _ => return Err(invalid().into()),
+        };
+
+        let param = |key: &str| -> Result<u32, Error> {
+            params
+                .split(',')
+                .filter_map(|pair: &str| pair.split_once('='))
+                .find(|(k, _): &(&str, &str)| *k == key)
+                .and_then(|(_, value): (&str, &str)| value.parse().ok())
+                .ok_or_else(|| invalid().into())
+        };
+
+        let kdf: SeedKdf = match name {
+            "pbkdf2-sha512" | "pbkdf2-sha256" => SeedKdf::Pbkdf2 {
+                hash: if name == "pbkdf2-sha512" {
+                    Pbkdf2Hash::Sha512
+                } else {
+                    Pbkdf2Hash::Sha256
+                },
+                rounds: param("i")?,
+            },
+            #[cfg(feature = "argon2id")]
+            "argon2id" => SeedKdf::Argon2id {
+                memory_cost: param("m")?,
+                time_cost: param("t")?,
+                parallelism: param("p")?,
+            },
+            _ => return Err(invalid().into()),
+        };
+
+        let options = SeedOptions {
+            kdf,
+            length: param("l")? as usize,
+        };
+
+        options.validate()?;
+
+        Ok(options)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn display_round_trip() {
+        let options = [
+            SeedOptions::new(),
+            SeedOptions::new().pbkdf2(Pbkdf2Hash::Sha256, 1).length(16),
+            SeedOptions::new().pbkdf2(Pbkdf2Hash::Sha512, MAX_PBKDF2_ROUNDS),
+        ];
+
+        for options in &options {
+            assert_eq!(
+                &options.to_string().parse::<SeedOptions>().unwrap(),
+                options
+            );
+        }
+
+        let reordered: SeedOptions = "$pbkdf2-sha256$l=32,i=10".parse().unwrap();
+        assert_eq!(
+            reordered,
+            SeedOptions::new().pbkdf2(Pbkdf2Hash::Sha256, 10).length(32)
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "argon2id")]
+    fn display_argon2id() {
+        let options = SeedOptions::new().argon2id(65536, 3, 4).length(32);
+
+        assert_eq!(options.to_string(), "$argon2id$m=65536,t=3,p=4,l=32");
+        assert_eq!(options.to_string().parse::<SeedOptions>().unwrap(), options);
+    }
+
+    #[test]
+    fn params_are_checked() {
+        let error: Error = "$pbkdf2-sha512$i=4294967295,l=64"
+            .parse::<SeedOptions>()
+            .unwrap_err();
+        assert!(matches!(error.kind(), ErrorKind::InvalidSeedOptions(_)));
+
+        let error: Error = SeedOptions::new()
+            .pbkdf2(Pbkdf2Hash::Sha256, MAX_PBKDF2_ROUNDS + 1)
+            .validate()
+            .unwrap_err();
+        assert!(matches!(error.kind(), ErrorKind::InvalidSeedOptions(_)));
+    }
+
+    #[test]
+    #[cfg(feature = "argon2id")]
+    fn argon2id_params_are_checked() {
+        let error: Error = "$argon2id$m=4294967295,t=4294967295,p=16777215,l=64"
+            .parse::<SeedOptions>()
+            .unwrap_err();
+        assert!(matches!(error.kind(), ErrorKind::InvalidSeedOptions(_)));
+
+        for options in &[
+            SeedOptions::new().argon2id(MAX_ARGON2_MEMORY + 1, 1, 1),
+            SeedOptions::new().argon2id(65536, MAX_ARGON2_TIME + 1, 1),
+            SeedOptions::new().argon2id(65536, 1, MAX_ARGON2_PARALLELISM + 1),
+        ] {
+            let error: Error = options.validate().unwrap_err();
+            assert!(matches!(error.kind(), ErrorKind::InvalidSeedOptions(_)));
+        }
+
+        assert!(SeedOptions::new()
+            .argon2id(MAX_ARGON2_MEMORY, MAX_ARGON2_TIME, MAX_ARGON2_PARALLELISM)
+            .validate()
+            .is_ok());
+    }
+
+    #[test]
+    fn reject_invalid_options() {
+        for options in &[
+            "",
+            "pbkdf2-sha512$i=2048,l=64",
+            "$pbkdf2-sha512$i=2048",
+            "$pbkdf2-sha512$i=2048,l=64$",
+            "$pbkdf2-sha1$i=2048,l=64",
+            "$pbkdf2-sha512$i=-1,l=64",
+            "$pbkdf2-sha512$i=0,l=64",
+        ] {
+            let error: Error = options.parse::<SeedOptions>().unwrap_err();
+            assert!(matches!(error.kind(), ErrorKind::InvalidSeedOptions(_)));
+        }
+
+        let error: Error = "$pbkdf2-sha512$i=2048,l=65"
+            .parse::<SeedOptions>()
+            .unwrap_err();
+        assert!(matches!(error.kind(), ErrorKind::InvalidSeedLength(65)));
+
+        let error: Error = SeedOptions::new().length(15).validate().unwrap_err();
+        assert!(matches!(error.kind(), ErrorKind::InvalidSeedLength(15)));
+    }
+}
@@ -0,0 +1,270 @@
+//! [SeedQR](https://github.com/SeedSigner/seedsigner/blob/dev/docs/seed_qr/README.md) encoding.
+//!
+//! A standard SeedQR holds the 0-based word list index of every word as 4 decimal digits, and a
+//! CompactSeedQR holds the raw entropy bytes. The format is defined for the English word list,
+//! other languages only work with software that uses the same list.
+//!
+//! Rendering to a QR code is only available with the `qr` cargo feature.
+//!
+
+use crate::error::{Error, ErrorKind};
+use crate::keyphrase::KeyPhrase;
+use crate::language::Language;
+#[cfg(feature = "qr")]
+use qrcode::bits::Bits;
+#[cfg(feature = "qr")]
+use qrcode::{Color, EcLevel, QrCode, QrResult, Version};
+#[cfg(feature = "qr")]
+use std::fmt::Write;
+
+/// Number of digits holding a word index
+const DIGITS_PER_WORD: usize = 4;
+
+/// Width of the light border around a rendered QR code, in modules
+#[cfg(feature = "qr")]
+const QUIET_ZONE: usize = 4;
+
+/// A QR code as a square of dark and light modules
+///
+/// Only available with the `qr` cargo feature.
+#[cfg(feature = "qr")]
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub struct QrMatrix {
+    width: usize,
+    modules: Vec<bool>,
+}
+
+#[cfg(feature = "qr")]
+impl QrMatrix {
+    /// Encode the smallest QR code with error correction level L that fits the data
+    fn encode<F>(push_data: F) -> QrMatrix
+    where
+        F: Fn(&mut Bits) -> QrResult<()>,
+    {
+        let code: QrCode = (1..=40)
+            .find_map(|version: i16| {
+                let mut bits = Bits::new(Version::Normal(version));
+
+                push_data(&mut bits).ok()?;
+                bits.push_terminator(EcLevel::L).ok()?;
+
+                QrCode::with_bits(bits, EcLevel::L).ok()
+            })
+            .expect("SeedQR data always fits in a QR code");
+
+        QrMatrix {
+            width: code.width(),
+            modules: code
+                .into_colors()
+                .into_iter()
+                .map(|color: Color| color == Color::Dark)
+                .collect(),
+        }
+    }
+
+    /// Get the number of modules on each side, without the quiet zone
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    /// Whether the module in column `x` and row `y` is dark
+    ///
+    /// # Panics
+    ///
+    /// Panics if `x` or `y` are not smaller than the width.
+    pub fn is_dark(&self, x: usize, y: usize) -> bool {
+        assert!(x < self.width && y < self.width, "module out of range");
+
+        self.modules[y * self.width + x]
+    }
+
+    /// Render as text, two characters per module with `█` for dark modules
+    ///
+    /// Dark modules are drawn in the foreground color, so the result only scans when printed
+    /// dark on light. A quiet zone of 4 modules is included.
+    pub fn to_text(&self) -> String {
+        let size: usize = self.width + 2 * QUIET_ZONE;
+        let mut text = String::with_capacity(size * (size * 2 * 3 + 1));
+
+        for y in 0..size {
+            for x in 0..size {
+                text.push_str(if self.is_dark_padded(x, y) {
+                    "██"
+                } else {
+                    "  "
+                });
+            }
+
+            text.push('\n');
+        }
+
+        text
+    }
+
+    /// Render as an SVG image, `module_size` user units per module
+    ///
+    /// Dark modules are merged into a single path over a white background, and a quiet zone of 4
+    /// modules is included.
+    pub fn to_svg(&self, module_size: usize) -> String {
+        let size: usize = self.width + 2 * QUIET_ZONE;
+        let mut path = String::new();
+
+        for y in 0..self.width {
+            let mut x: usize = 0;
+
+            while x < self.width {
+                if !self.is_dark(x, y) {
+                    x += 1;
+                    continue;
+                }
+
+                let start: usize = x;
+
+                while x < self.width && self.is_dark(x, y) {
+                    x += 1;
+                }
+
+                write!(
+                    path,
+                    "M{},{}h{}v1h-{}z",
+                    start + QUIET_ZONE,
+                    y + QUIET_ZONE,
+                    x - start,
+                    x - start
+                )
+                .unwrap();
+            }
+        }
+
+        format!(
+            concat!(
+                "<svg xmlns=\"http://www.w3.org/2000/svg\" version=\"1.1\" ",
+                "width=\"{pixels}\" height=\"{pixels}\" viewBox=\"0 0 {size} {size}\" ",
+                "shape-rendering=\"crispEdges\">\n",
+                "<rect width=\"{size}\" height=\"{size}\" fill=\"#fff\"/>\n",
+                "<path d=\"{path}\" fill=\"#000\"/>\n",
+                "</svg>\n"
+            ),
+            pixels = size * module_size,
+            size = size,
+            path = path
+        )
+    }
+
+    /// Whether the module at the given position, counted from the edge of the quiet zone, is dark
+    fn is_dark_padded(&self, x: usize, y: usize) -> bool {
+        let inside = |i: usize| i >= QUIET_ZONE && i < QUIET_ZONE + self.width;
+
+        inside(x) && inside(y) && self.is_dark(x - QUIET_ZONE, y - QUIET_ZONE)
+    }
+}
+
+#[cfg(feature = "qr")]
+impl std::fmt::Debug for QrMatrix {
+    /// Only prints the width, the modules encode the keyphrase
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.debug_struct("QrMatrix")
+            .field("width", &self.width)
+            .finish()
+    }
+}
+
+impl KeyPhrase {
+    /// Get the content of a standard SeedQR, the 0-based index of every word as 4 digits
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use keyphrase::{KeyPhrase, Language};
+    ///
+    /// let phrase = "forum undo fragile fade shy sign arrest garment culture tube off merit";
+    /// let keyphrase = KeyPhrase::from_phrase(phrase, Language::English).unwrap();
+    ///
+    /// let digits: String = keyphrase.to_seedqr_digits();
+    ///
+    /// assert_eq!(digits, "073318950739065415961602009907670428187212261116");
+    /// assert_eq!(KeyPhrase::from_seedqr_digits(&digits, Language::English).unwrap().phrase(), phrase);
+    /// ```
+    pub fn to_seedqr_digits(&self) -> String {
+        self.indices()
+            .iter()
+            .map(|index: &u16| format!("{:04}", index))
+            .collect()
+    }
+
+    /// Read a [`KeyPhrase`][KeyPhrase] from the content of a standard SeedQR
+    ///
+    /// The checksum of the phrase is verified. Returns an `Error` of kind
+    /// `ErrorKind::InvalidSeedQrLength` if the digits don't split into whole words.
+    ///
+    /// [KeyPhrase]: ./keyphrase/struct.KeyPhrase.html
+    pub fn from_seedqr_digits(digits: &str, lang: Language) -> Result<KeyPhrase, Error> {
+        if let Some(c) = digits.chars().find(|c: &char| !c.is_ascii_digit()) {
+            return Err(ErrorKind::InvalidSeedQrCharacter(c).into());
+        }
+
+        if !digits.len().is_multiple_of(DIGITS_PER_WORD) {
+            return Err(ErrorKind::InvalidSeedQrLength(digits.len()).into());
+        }
+
+        let indices: Vec<u16> = digits
+            .as_bytes()
+            .chunks(DIGITS_PER_WORD)
+            .map(|chunk: &[u8]| {
+                chunk.iter().fold(0, |index: u16, digit: &u8| {
+                    index * 10 + u16::from(digit - b'0')
+                })
+            })
+            .collect();
+
+        KeyPhrase::from_indices(&indices, lang)
+    }
+
+    /// Get the content of a CompactSeedQR, the entropy of the keyphrase
+    pub fn to_compact_seedqr_bytes(&self) -> Vec<u8> {
+        self.entropy().to_vec()
+    }
+
+    /// Read a [`KeyPhrase`][KeyPhrase] from the content of a CompactSeedQR
+    ///
+    /// [KeyPhrase]: ./keyphrase/struct.KeyPhrase.html
+    pub fn from_compact_seedqr_bytes(bytes: &[u8], lang: Language) -> Result<KeyPhrase, Error> {
+        KeyPhrase::from_entropy(bytes, lang)
+    }
+
+    /// Render a standard SeedQR, the digits in numeric mode
+    ///
+    /// A 12 word keyphrase gives a 25x25 code and a 24 word keyphrase a 29x29 code.
+    ///
+    /// Only available with the `qr` cargo feature.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use keyphrase::{KeyPhrase, KeyPhraseType, Language};
+    ///
+    /// let keyphrase = KeyPhrase::new(KeyPhraseType::Words12, Language::English);
+    /// let qr = keyphrase.to_seedqr();
+    ///
+    /// assert_eq!(qr.width(), 25);
+    ///
+    /// let svg: String = qr.to_svg(8);
+    /// assert!(svg.starts_with("<svg"));
+    /// ```
+    #[cfg(feature = "qr")]
+    pub fn to_seedqr(&self) -> QrMatrix {
+        let digits: String = self.to_seedqr_digits();
+
+        QrMatrix::encode(|bits: &mut Bits| bits.push_numeric_data(digits.as_bytes()))
+    }
+
+    /// Render a CompactSeedQR, the entropy in byte mode
+    ///
+    /// A 12 word keyphrase gives a 21x21 code and a 24 word keyphrase a 25x25 code.
+    ///
+    /// Only available with the `qr` cargo feature.
+    #[cfg(feature = "qr")]
+    pub fn to_compact_seedqr(&self) -> QrMatrix {
+        QrMatrix::encode(|bits: &mut Bits| bits.push_byte_data(self.entropy()))
+    }
+}
@@ -0,0 +1,431 @@
+use crate::index_format::IndexFormat;
+use crate::keyphrase::KeyPhrase;
+use crate::keyphrase_type::KeyPhraseType;
+use crate::language::Language;
+use std::fmt::{self, Write};
+use unicode_normalization::UnicodeNormalization;
+
+/// Width of the page in millimeters, A4
+const PAGE_WIDTH: f64 = 210.0;
+
+/// Height of the page in millimeters, A4
+const PAGE_HEIGHT: f64 = 297.0;
+
+/// Distance from the edges of the page to the content, in millimeters
+const MARGIN: f64 = 20.0;
+
+/// Vertical position of the first table row, in millimeters
+const TABLE_TOP: f64 = 62.0;
+
+/// Largest height of a table row, in millimeters
+const MAX_ROW_HEIGHT: f64 = 9.0;
+
+/// Largest font size of the table, in millimeters
+const FONT_SIZE: f64 = 4.5;
+
+/// Largest font size of the table relative to the row height, so long tables shrink the font
+const FONT_ROW_RATIO: f64 = 0.75;
+
+/// Advance of a character relative to the font size
+const CHAR_RATIO: f64 = 0.6;
+
+/// Largest side of a check box, in millimeters
+const BOX_SIZE: f64 = 4.0;
+
+/// Space between check boxes and between rows of boxes, in millimeters
+const BOX_GAP: f64 = 1.0;
+
+/// Number of bits in a word
+const WORD_BITS: usize = 11;
+
+/// Number of characters of the word blank on a blank template
+const BLANK_WORD_CHARS: usize = 14;
+
+/// A printable backup sheet for a [`KeyPhrase`][KeyPhrase], as plain text or SVG
+///
+/// The sheet lists every word with its number and word list index next to a check box, to tick
+/// off while checking the backup against the original. A master fingerprint can be added, so a
+/// restore can be verified without revealing any keys.
+///
+/// A blank template has empty fields to fill in by hand instead, and a box for every bit of every
+/// word, for keyphrases generated with dice.
+///
+/// # Example
+///
+/// ```
+/// use keyphrase::{BackupSheet, KeyPhrase, Language};
+///
+/// let phrase = "park remain person kitchen mule spell knee armed position rail grid ankle";
+/// let keyphrase = KeyPhrase::from_phrase(phrase, Language::English).unwrap();
+///
+/// let sheet = BackupSheet::new(&keyphrase);
+/// let text: String = sheet.to_text();
+///
+/// assert!(text.contains(" 1  park      1283   [ ]"));
+/// assert!(sheet.to_svg().starts_with("<svg"));
+/// ```
+///
+/// [KeyPhrase]: ./keyphrase/struct.KeyPhrase.html
+#[derive(Clone)]
+pub struct BackupSheet {
+    words: Option<Vec<(String, u16)>>,
+    keyphrase_type: KeyPhraseType,
+    lang: Language,
+    fingerprint: Option<[u8; 4]>,
+    index_format: IndexFormat,
+}
+
+impl BackupSheet {
+    /// Create a sheet holding the words of `keyphrase`
+    ///
+    /// Indices are written 1-based, the sheet has no fingerprint until one is set with
+    /// [`BackupSheet::fingerprint()`][BackupSheet::fingerprint()].
+    ///
+    /// [BackupSheet::fingerprint()]: ./struct.BackupSheet.html#method.fingerprint
+    pub fn new(keyphrase: &KeyPhrase) -> BackupSheet {
+        // Words are composed again, so accents take up a single column when printed
+        let words: Vec<(String, u16)> = keyphrase
+            .phrase()
+            .split(' ')
+            .map(|word: &str| word.nfc().collect())
+            .zip(keyphrase.indices())
+            .collect();
+
+        BackupSheet {
+            keyphrase_type: keyphrase.keyphrase_type(),
+            words: Some(words),
+            lang: keyphrase.language(),
+            fingerprint: None,
+            index_format: IndexFormat::OneBased,
+        }
+    }
+
+    /// Create a blank template to fill in by hand
+    pub fn blank(keyphrase_type: KeyPhraseType, lang: Language) -> BackupSheet {
+        BackupSheet {
+            words: None,
+            keyphrase_type,
+            lang,
+            fingerprint: None,
+            index_format: IndexFormat::OneBased,
+        }
+    }
+
+    /// Show the master fingerprint of the wallet
+    ///
+    /// With the `bip32` cargo feature it is given by `Seed::master_fingerprint()`. It depends on
+    /// the password, so it also tells whether the right password was used.
+    pub fn fingerprint(mut self, fingerprint: [u8; 4]) -> BackupSheet {
+        self.fingerprint = Some(fingerprint);
+        self
+    }
+
+    /// Write the word indices in the given format instead of 1-based decimal
+    pub fn index_format(mut self, index_format: IndexFormat) -> BackupSheet {
+        self.index_format = index_format;
+        self
+    }
+
+    /// Render the sheet as plain text
+    pub fn to_text(&self) -> String {
+        let mut text = String::new();
+
+        for line in self.header() {
+            text.push_str(&line);
+            text.push('\n');
+        }
+
+        text.push('\n');
+
+        let word_width: usize = self.word_width();
+        let index_width: usize = self.index_width();
+
+        write!(
+            text,
+            "{:>2}  {:<word_width$}  {:<index_width$}  ",
+            "#",
+            "Word",
+            "Index",
+            word_width = word_width,
+            index_width = index_width
+        )
+        .unwrap();
+
+        if self.words.is_none() {
+            write!(text, "{:<width$}  ", "Bits", width = WORD_BITS).unwrap();
+        }
+
+        text.push_str("Checked\n");
+
+        for (number, word, index) in self.rows() {
+            write!(
+                text,
+                "{:>2}  {:<word_width$}  {:<index_width$}  ",
+                number,
+                word,
+                index,
+                word_width = word_width,
+                index_width = index_width
+            )
+            .unwrap();
+
+            if self.words.is_none() {
+                write!(text, "{}  ", "_".repeat(WORD_BITS)).unwrap();
+            }
+
+            text.push_str("[ ]\n");
+        }
+
+        text.push('\n');
+
+        for line in self.footer() {
+            text.push_str(&line);
+            text.push('\n');
+        }
+
+        text
+    }
+
+    /// Render the sheet as an A4 page in SVG, measured in millimeters
+    pub fn to_svg(&self) -> String {
+        let mut svg = String::new();
+
+        write!(
+            svg,
+            concat!(
+                "<svg xmlns=\"http://www.w3.org/2000/svg\" version=\"1.1\" ",
+                "width=\"{width}mm\" height=\"{height}mm\" viewBox=\"0 0 {width} {height}\">\n",
+                "<rect width=\"{width}\" height=\"{height}\" fill=\"#fff\"/>\n",
+                "<g font-family=\"monospace\" fill=\"#000\">\n"
+            ),
+            width = PAGE_WIDTH,
+            height = PAGE_HEIGHT
+        )
+        .unwrap();
+
+        let header: Vec<String> = self.header();
+        let mut y: f64 = MARGIN + 8.0;
+
+        svg_text(&mut svg, MARGIN, y, 8.0, &header[0]);
+
+        for line in &header[2..] {
+            y += 7.0;
+            svg_text(&mut svg, MARGIN, y, 5.0, line);
+        }
+
+        let rows: Vec<(usize, String, String)> = self.rows();
+        let footer: Vec<String> = self.footer();
+        let row_height: f64 = MAX_ROW_HEIGHT.min(
+            (PAGE_HEIGHT - MARGIN - TABLE_TOP - 8.0 * footer.len() as f64)
+                / (rows.len() + 1) as f64,
+        );
+
+        // Past 24 words the rows get too low for the full size font and boxes
+        let font_size: f64 = FONT_SIZE.min(row_height * FONT_ROW_RATIO);
+        let char_width: f64 = font_size * CHAR_RATIO;
+        let box_size: f64 = BOX_SIZE.min(row_height - BOX_GAP);
+
+        // Column positions, the bit boxes only appear on blank templates
+        let word_x: f64 = MARGIN + 10.0;
+        let index_x: f64 = word_x + 55.0;
+        let bits_x: f64 = index_x + self.index_width() as f64 * char_width + 8.0;
+        let check_x: f64 = PAGE_WIDTH - MARGIN - box_size;
+
+        y = TABLE_TOP;
+
+        svg_text(&mut svg, MARGIN, y, font_size, "#");
+        svg_text(&mut svg, word_x, y, font_size, "Word");
+        svg_text(&mut svg, index_x, y, font_size, "Index");
+
+        if self.words.is_none() {
+            svg_text(&mut svg, bits_x, y, font_size, "Bits");
+        }
+
+        let checked: &str = "Checked";
+        let checked_x: f64 = check_x + box_size - checked.len() as f64 * char_width;
+
+        svg_text(&mut svg, checked_x, y, font_size, checked);
+
+        for (number, word, index) in &rows {
+            y += row_height;
+
+            svg_text(&mut svg, MARGIN, y, font_size, &number.to_string());
+
+            if self.words.is_some() {
+                svg_text(&mut svg, word_x, y, font_size, word);
+                svg_text(&mut svg, index_x, y, font_size, index);
+            } else {
+                svg_line(&mut svg, word_x, index_x - 5.0, y + 1.0);
+                svg_line(&mut svg, index_x, bits_x - 5.0, y + 1.0);
+
+                for bit in 0..WORD_BITS {
+                    svg_box(
+                        &mut svg,
+                        bits_x + bit as f64 * (box_size + BOX_GAP),
+                        y,
+                        box_size,
+                    );
+                }
+            }
+
+            svg_box(&mut svg, check_x, y, box_size);
+        }
+
+        y += row_height;
+
+        for line in &footer {
+            y += 8.0;
+            svg_text(&mut svg, MARGIN, y, FONT_SIZE, line);
+        }
+
+        svg.push_str("</g>\n</svg>\n");
+
+        svg
+    }
+
+    /// Title and description lines, the second line underlines the title in text
+    fn header(&self) -> Vec<String> {
+        let title: &str = if self.words.is_some() {
+            "KeyPhrase Backup"
+        } else {
+            "KeyPhrase Backup Template"
+        };
+
+        let language: String = if self.lang.name() == self.lang.native_name() {
+            self.lang.name().to_string()
+        } else {
+            format!("{} ({})", self.lang.name(), self.lang.native_name())
+        };
+
+        let fingerprint: String = match self.fingerprint {
+            Some(fingerprint) => fingerprint
+                .iter()
+                .map(|byte: &u8| format!("{:02x}", byte))
+                .collect(),
+            None => "_".repeat(8),
+        };
+
+        vec![
+            title.to_string(),
+            "=".repeat(title.len()),
+            format!("Language:     {}", language),
+            format!("Words:        {}", self.keyphrase_type),
+            format!("Fingerprint:  {}", fingerprint),
+        ]
+    }
+
+    /// Lines below the table
+    fn footer(&self) -> Vec<String> {
+        let mut footer: Vec<String> = Vec::new();
+
+        if self.words.is_none() {
+            footer.push("Fill each row of bits with dice rolls, odd for 1 and even for 0.".into());
+            let checksum_bits: u8 = self.keyphrase_type.checksum_bits();
+            let word_count: usize = self.keyphrase_type.word_count();
+
+            // Only the checksum of 48 word phrases is longer than a word
+            footer.push(if checksum_bits <= 11 {
+                format!(
+                    "The last {} bits of word {} are the checksum, leave them to software.",
+                    checksum_bits, word_count
+                )
+            } else {
+                format!(
+                    "The last {} bits of words {} and {} are the checksum, leave them to software.",
+                    checksum_bits,
+                    word_count - 1,
+                    word_count
+                )
+            });
+        }
+
+        footer.push("Checked on:   ______________".into());
+
+        footer
+    }
+
+    /// Number, word and formatted index of every row, blanks on a template
+    fn rows(&self) -> Vec<(usize, String, String)> {
+        match &self.words {
+            Some(words) => words
+                .iter()
+                .enumerate()
+                .map(|(i, (word, index)): (usize, &(String, u16))| {
+                    (i + 1, word.clone(), self.index_format.format(*index))
+                })
+                .collect(),
+            None => (1..=self.keyphrase_type.word_count())
+                .map(|number: usize| {
+                    (
+                        number,
+                        "_".repeat(BLANK_WORD_CHARS),
+                        "_".repeat(self.index_width()),
+                    )
+                })
+                .collect(),
+        }
+    }
+
+    fn word_width(&self) -> usize {
+        match &self.words {
+            Some(words) => words
+                .iter()
+                .map(|(word, _): &(String, u16)| word.chars().count())
+                .max()
+                .unwrap_or(0)
+                .max("Word".len()),
+            None => BLANK_WORD_CHARS,
+        }
+    }
+
+    fn index_width(&self) -> usize {
+        self.index_format.format(0).len().max("Index".len())
+    }
+}
+
+impl fmt::Debug for BackupSheet {
+    /// Only prints the sheet layout, the words are left out
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("BackupSheet")
+            .field("blank", &self.words.is_none())
+            .field("keyphrase_type", &self.keyphrase_type)
+            .field("lang", &self.lang)
+            .field("index_format", &self.index_format)
+            .finish()
+    }
+}
+
+fn svg_text(svg: &mut String, x: f64, y: f64, size: f64, text: &str) {
+    let escaped: String = text
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;");
+
+    writeln!(
+        svg,
+        "<text x=\"{:.1}\" y=\"{:.1}\" font-size=\"{:.1}\">{}</text>",
+        x, y, size, escaped
+    )
+    .unwrap();
+}
+
+fn svg_box(svg: &mut String, x: f64, y: f64, size: f64) {
+    writeln!(
+        svg,
+        "<rect x=\"{:.1}\" y=\"{:.1}\" width=\"{:.1}\" height=\"{:.1}\" fill=\"none\" stroke=\"#000\" stroke-width=\"0.3\"/>",
+        x,
+        y - size + 0.5,
+        size,
+        size
+    )
+    .unwrap();
+}
+
+fn svg_line(svg: &mut String, x1: f64, x2: f64, y: f64) {
+    writeln!(
+        svg,
+        "<line x1=\"{:.1}\" y1=\"{:.1}\" x2=\"{:.1}\" y2=\"{:.1}\" stroke=\"#000\" stroke-width=\"0.3\"/>",
+        x1, y, x2, y
+    )
+    .unwrap();
+}
@@ -0,0 +1,222 @@
+//! [SLIP-10](https://github.com/satoshilabs/slips/blob/master/slip-0010.md) hierarchical
+//! deterministic key derivation on ed25519, starting from a [`Seed`][Seed].
+//!
+//! Unlike BIP32 on secp256k1, ed25519 only supports hardened derivation, so there are no
+//! extended public keys.
+//!
+//! Only available with the `slip10` cargo feature.
+//!
+//! [Seed]: ../seed/struct.Seed.html
+//!
+
+use crate::derivation::{
+    fingerprint, hash160, hmac_sha512, ChildNumber, DerivationPath, MAX_SEED_BYTES, MIN_SEED_BYTES,
+};
+use crate::error::{Error, ErrorKind};
+use crate::seed::Seed;
+use ed25519_dalek::{SigningKey, VerifyingKey};
+use std::fmt;
+
+/// HMAC key used to derive the master key from a seed
+const MASTER_HMAC_KEY: &[u8] = b"ed25519 seed";
+
+/// An ed25519 signing key together with the chain code needed to derive its children
+///
+/// The master key is derived from a [`Seed`][Seed] with
+/// [`Ed25519ExtendedKey::from_seed()`][Ed25519ExtendedKey::from_seed()]. Every child number in a
+/// derivation must be hardened.
+///
+/// # Example
+///
+/// ```
+/// use ed25519_dalek::Signer;
+/// use keyphrase::{Ed25519ExtendedKey, KeyPhrase, Language, Seed};
+///
+/// let phrase = "park remain person kitchen mule spell knee armed position rail grid ankle";
+/// let keyphrase = KeyPhrase::from_phrase(phrase, Language::English).unwrap();
+/// let seed = Seed::new(&keyphrase, "");
+///
+/// let master = Ed25519ExtendedKey::from_seed(&seed).unwrap();
+/// let account = master.derive_path(&"m/44'/501'/0'".parse().unwrap()).unwrap();
+///
+/// let signature = account.signing_key().sign(b"hello");
+///
+/// assert!(account.verifying_key().verify_strict(b"hello", &signature).is_ok());
+/// assert!(master.derive_path(&"m/44'/501'/0".parse().unwrap()).is_err());
+/// ```
+///
+/// [Seed]: ../seed/struct.Seed.html
+/// [Ed25519ExtendedKey::from_seed()]: ./struct.Ed25519ExtendedKey.html#method.from_seed
+#[derive(Clone)]
+pub struct Ed25519ExtendedKey {
+    signing_key: SigningKey,
+    chain_code: [u8; 32],
+    depth: u8,
+    parent_fingerprint: [u8; 4],
+    child_number: ChildNumber,
+}
+
+impl Ed25519ExtendedKey {
+    /// Derive the master key from a [`Seed`][Seed]
+    ///
+    /// [Seed]: ../seed/struct.Seed.html
+    pub fn from_seed(seed: &Seed) -> Result<Ed25519ExtendedKey, Error> {
+        Ed25519ExtendedKey::new_master(seed.as_bytes())
+    }
+
+    /// Derive the master key from raw seed bytes
+    ///
+    /// Seeds are between 128 and 512 bits long, other lengths return an `Error` of kind
+    /// `ErrorKind::InvalidSeedLength`.
+    pub fn new_master(seed: &[u8]) -> Result<Ed25519ExtendedKey, Error> {
+        if seed.len() < MIN_SEED_BYTES || seed.len() > MAX_SEED_BYTES {
+            return Err(ErrorKind::InvalidSeedLength(seed.len()).into());
+        }
+
+        // Any 32 bytes make a valid ed25519 private key, so unlike BIP32 there is nothing to check
+        let (key, chain_code): ([u8; 32], [u8; 32]) = hmac_sha512(MASTER_HMAC_KEY, &[seed]);
+
+        Ok(Ed25519ExtendedKey {
+            signing_key: SigningKey::from_bytes(&key),
+            chain_code,
+            depth: 0,
+            parent_fingerprint: [0; 4],
+            child_number: ChildNumber::from(0),
+        })
+    }
+
+    /// Derive a hardened child key
+    ///
+    /// Asking for a normal child returns an `Error` of kind `ErrorKind::NormalEd25519Derivation`.
+    pub fn derive_child(&self, child: ChildNumber) -> Result<Ed25519ExtendedKey, Error> {
+        if !child.is_hardened() {
+            return Err(ErrorKind::NormalEd25519Derivation(u32::from(child)).into());
+        }
+
+        let depth: u8 = self
+            .depth
+            .checked_add(1)
+            .ok_or(ErrorKind::MaxDepthExceeded)?;
+
+        let (key, chain_code): ([u8; 32], [u8; 32]) = hmac_sha512(
+            &self.chain_code,
+            &[
+                &[0],
+                self.signing_key.as_bytes(),
+                &u32::from(child).to_be_bytes(),
+            ],
+        );
+
+        Ok(Ed25519ExtendedKey {
+            signing_key: SigningKey::from_bytes(&key),
+            chain_code,
+            depth,
+            parent_fingerprint: self.fingerprint(),
+            child_number: child,
+        })
+    }
+
+    /// Derive the descendant key at the end of a [`DerivationPath`][DerivationPath]
+    ///
+    /// The path is taken to start at this key, and can only contain hardened child numbers.
+    ///
+    /// [DerivationPath]: ./struct.DerivationPath.html
+    pub fn derive_path(&self, path: &DerivationPath) -> Result<Ed25519ExtendedKey, Error> {
+        path.iter().try_fold(
+            self.clone(),
+            |key: Ed25519ExtendedKey, child: &ChildNumber| key.derive_child(*child),
+        )
+    }
+
+    /// Get the keypair for signing
+    pub fn signing_key(&self) -> &SigningKey {
+        &self.signing_key
+    }
+
+    /// Get the public key for verifying signatures
+    pub fn verifying_key(&self) -> VerifyingKey {
+        self.signing_key.verifying_key()
+    }
+
+    /// Get the 32 byte private key
+    pub fn private_key_bytes(&self) -> [u8; 32] {
+        self.signing_key.to_bytes()
+    }
+
+    /// Get the 32 byte public key
+    ///
+    /// SLIP-10 writes ed25519 public keys with a leading `0x00` byte to match the length of
+    /// compressed secp256k1 keys, that byte is left out here.
+    pub fn public_key_bytes(&self) -> [u8; 32] {
+        self.verifying_key().to_bytes()
+    }
+
+    /// Get the chain code
+    pub fn chain_code(&self) -> &[u8; 32] {
+        &self.chain_code
+    }
+
+    /// Get the number of derivation steps from the master key, `0` for the master key itself
+    pub fn depth(&self) -> u8 {
+        self.depth
+    }
+
+    /// Get the fingerprint of the parent key, all zeros for the master key
+    pub fn parent_fingerprint(&self) -> [u8; 4] {
+        self.parent_fingerprint
+    }
+
+    /// Get the child number this key was derived with, `0` for the master key
+    pub fn child_number(&self) -> ChildNumber {
+        self.child_number
+    }
+
+    /// Get the key identifier, the HASH160 of the public key with its leading `0x00` byte
+    pub fn identifier(&self) -> [u8; 20] {
+        let mut public_key = [0u8; 33];
+
+        public_key[1..].copy_from_slice(&self.public_key_bytes());
+
+        hash160(&public_key)
+    }
+
+    /// Get the key fingerprint, the first 4 bytes of the identifier
+    pub fn fingerprint(&self) -> [u8; 4] {
+        fingerprint(&self.identifier())
+    }
+}
+
+impl fmt::Debug for Ed25519ExtendedKey {
+    /// Only prints public information, the private key and chain code are left out
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Ed25519ExtendedKey")
+            .field("fingerprint", &self.fingerprint())
+            .field("depth", &self.depth)
+            .field("parent_fingerprint", &self.parent_fingerprint)
+            .field("child_number", &self.child_number)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn normal_derivation_fails() {
+        let master: Ed25519ExtendedKey = Ed25519ExtendedKey::new_master(&[7; 32]).unwrap();
+
+        assert!(master
+            .derive_child(ChildNumber::normal(0).unwrap())
+            .is_err());
+        assert!(master
+            .derive_child(ChildNumber::hardened(0).unwrap())
+            .is_ok());
+    }
+
+    #[test]
+    fn invalid_seed_length() {
+        assert!(Ed25519ExtendedKey::new_master(&[0; 15]).is_err());
+        assert!(Ed25519ExtendedKey::new_master(&[0; 65]).is_err());
+    }
+}
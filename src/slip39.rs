@@ -0,0 +1,940 @@
+//! [SLIP-39](https://github.com/satoshilabs/slips/blob/master/slip-0039.md) Shamir backup shares.
+//!
+//! A secret, usually the entropy of a [`KeyPhrase`][KeyPhrase], is encrypted with a passphrase
+//! and split in two levels: into groups, of which a threshold is needed, and each group into
+//! member shares, of which again a threshold is needed. Every share is written with the
+//! SLIP-39 word list and protected by an RS1024 checksum.
+//!
+//! [KeyPhrase]: ../keyphrase/struct.KeyPhrase.html
+//!
+
+use crate::crypto::gen_random_bytes;
+use crate::error::{Error, ErrorKind};
+use crate::keyphrase::KeyPhrase;
+use crate::language::Language;
+use hmac::{Hmac, Mac};
+use once_cell::sync::Lazy;
+use sha2::Sha256;
+use std::collections::BTreeMap;
+use std::fmt;
+use std::str::FromStr;
+
+/// Number of bits encoded by a single word
+const RADIX_BITS: usize = 10;
+
+/// Number of words in the word list
+const RADIX: u32 = 1 << RADIX_BITS;
+
+/// Number of words holding the identifier, extendable flag and iteration exponent
+const ID_EXP_WORDS: usize = 2;
+
+/// Number of words holding the group and member indices and thresholds
+const GROUP_WORDS: usize = 2;
+
+/// Number of words holding the RS1024 checksum
+const CHECKSUM_WORDS: usize = 3;
+
+/// Number of words in a share that are not part of the share value
+const METADATA_WORDS: usize = ID_EXP_WORDS + GROUP_WORDS + CHECKSUM_WORDS;
+
+/// Shortest secret that can be split, in bytes
+const MIN_SECRET_BYTES: usize = 16;
+
+/// Fewest words in a share, holding a 128 bit secret
+const MIN_SHARE_WORDS: usize = METADATA_WORDS + (MIN_SECRET_BYTES * 8).div_ceil(RADIX_BITS);
+
+/// Most groups, and most members in a group
+const MAX_SHARE_COUNT: u8 = 16;
+
+/// Number of digest bytes used to check the recovered secret
+const DIGEST_BYTES: usize = 4;
+
+/// x coordinate of the shared secret
+const SECRET_INDEX: u8 = 255;
+
+/// x coordinate of the digest of the shared secret
+const DIGEST_INDEX: u8 = 254;
+
+/// PBKDF2 iterations of all Feistel rounds together, shifted left by the iteration exponent
+const BASE_ITERATION_COUNT: u32 = 10_000;
+
+/// Number of Feistel rounds used to encrypt the secret
+const ROUND_COUNT: u8 = 4;
+
+/// Iteration exponent used by [`Share::split()`][Share::split()]
+///
+/// [Share::split()]: ./struct.Share.html#method.split
+const DEFAULT_ITERATION_EXPONENT: u8 = 1;
+
+/// RS1024 customization string, also the salt prefix of non-extendable shares
+const CUSTOMIZATION: &[u8] = b"shamir";
+
+/// RS1024 customization string of extendable shares
+const CUSTOMIZATION_EXTENDABLE: &[u8] = b"shamir_extendable";
+
+/// Generator of the RS1024 checksum
+const RS1024_GEN: [u32; 10] = [
+    0x00E0_E040,
+    0x01C1_C080,
+    0x0383_8100,
+    0x0707_0200,
+    0x0E0E_0009,
+    0x1C0C_2412,
+    0x3808_6C24,
+    0x3090_FC48,
+    0x21B1_F890,
+    0x03F3_F120,
+];
+
+/// The SLIP-39 word list, sorted so words can be found with a binary search
+static WORDLIST: Lazy<Vec<&'static str>> =
+    sync_lazy! { include_str!("langs/slip39.txt").split_whitespace().collect() };
+
+/// Exponent and logarithm tables of GF(256) with the Rijndael polynomial and generator 3
+static GF256: Lazy<([u8; 255], [u8; 256])> = sync_lazy! {
+    let mut exp = [0u8; 255];
+    let mut log = [0u8; 256];
+    let mut poly: u16 = 1;
+
+    for (i, value) in exp.iter_mut().enumerate() {
+        *value = poly as u8;
+        log[poly as usize] = i as u8;
+
+        poly = (poly << 1) ^ poly;
+
+        if poly & 0x100 != 0 {
+            poly ^= 0x11B;
+        }
+    }
+
+    (exp, log)
+};
+
+/// A group of member shares, `threshold` of the `count` members are needed to recover it
+///
+/// [`Share::split()`][Share::split()] takes one for every group.
+///
+/// [Share::split()]: ./struct.Share.html#method.split
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ShareGroup {
+    threshold: u8,
+    count: u8,
+}
+
+impl ShareGroup {
+    /// Create a group of `count` members, `threshold` of which are needed to recover it
+    ///
+    /// The values are checked when splitting. A threshold of `1` only works with a single member,
+    /// since every member would hold the same share.
+    pub fn new(threshold: u8, count: u8) -> ShareGroup {
+        ShareGroup { threshold, count }
+    }
+
+    /// Get the number of members needed to recover the group
+    pub fn threshold(self) -> u8 {
+        self.threshold
+    }
+
+    /// Get the number of members in the group
+    pub fn count(self) -> u8 {
+        self.count
+    }
+}
+
+/// A single SLIP-39 share, written as a phrase of 20 or more words
+///
+/// # Example
+///
+/// ```
+/// use keyphrase::{Share, ShareGroup};
+///
+/// let secret = [0x42; 16];
+///
+/// // Two of three groups, the first needs both its members, the others their single member
+/// let groups = [ShareGroup::new(2, 2), ShareGroup::new(1, 1), ShareGroup::new(1, 1)];
+/// let shares = Share::split(&secret, "TREZOR", 2, &groups).unwrap();
+///
+/// let phrases: Vec<String> = vec![
+///     shares[0][0].to_string(),
+///     shares[0][1].to_string(),
+///     shares[2][0].to_string(),
+/// ];
+///
+/// let parsed: Vec<Share> = phrases.iter().map(|phrase| phrase.parse().unwrap()).collect();
+///
+/// assert_eq!(Share::combine(&parsed, "TREZOR").unwrap(), secret);
+/// assert!(Share::combine(&parsed[..2], "TREZOR").is_err());
+/// ```
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub struct Share {
+    identifier: u16,
+    extendable: bool,
+    iteration_exponent: u8,
+    group_index: u8,
+    group_threshold: u8,
+    group_count: u8,
+    member_index: u8,
+    member_threshold: u8,
+    value: Vec<u8>,
+}
+
+impl Share {
+    /// Encrypt `secret` with `passphrase` and split it into groups of shares
+    ///
+    /// `group_threshold` of the `groups` are needed to recover the secret. The result holds the
+    /// member shares of every group, in order. Shares are extendable and use an iteration
+    /// exponent of 1, see [`Share::split_with()`][Share::split_with()] to change either.
+    ///
+    /// [Share::split_with()]: ./struct.Share.html#method.split_with
+    pub fn split(
+        secret: &[u8],
+        passphrase: &str,
+        group_threshold: u8,
+        groups: &[ShareGroup],
+    ) -> Result<Vec<Vec<Share>>, Error> {
+        Share::split_with(
+            secret,
+            passphrase,
+            group_threshold,
+            groups,
+            DEFAULT_ITERATION_EXPONENT,
+            true,
+        )
+    }
+
+    /// Encrypt `secret` with `passphrase` and split it into groups of shares
+    ///
+    /// The passphrase is stretched with `10000 << iteration_exponent` PBKDF2 iterations. More
+    /// shares can later be added to an `extendable` backup without changing the secret it
+    /// recovers for a given passphrase.
+    ///
+    /// The secret must be at least 16 bytes long and have an even length, which holds for the
+    /// entropy of every [`KeyPhrase`][KeyPhrase].
+    ///
+    /// [KeyPhrase]: ../keyphrase/struct.KeyPhrase.html
+    pub fn split_with(
+        secret: &[u8],
+        passphrase: &str,
+        group_threshold: u8,
+        groups: &[ShareGroup],
+        iteration_exponent: u8,
+        extendable: bool,
+    ) -> Result<Vec<Vec<Share>>, Error> {
+        if secret.len() < MIN_SECRET_BYTES || !secret.len().is_multiple_of(2) {
+            return Err(ErrorKind::InvalidSecretLength(secret.len()).into());
+        }
+
+        if iteration_exponent > 15 {
+            return Err(ErrorKind::InvalidIterationExponent(iteration_exponent).into());
+        }
+
+        check_threshold(group_threshold, groups.len())?;
+
+        for group in groups {
+            check_threshold(group.threshold, group.count as usize)?;
+
+            if group.threshold == 1 && group.count > 1 {
+                return Err(ErrorKind::InvalidThreshold(group.threshold, group.count).into());
+            }
+        }
+
+        let random: Vec<u8> = gen_random_bytes(2);
+        let identifier: u16 = u16::from_be_bytes([random[0], random[1]]) & 0x7FFF;
+
+        let encrypted: Vec<u8> = encrypt(
+            secret,
+            passphrase,
+            iteration_exponent,
+            identifier,
+            extendable,
+        )?;
+        let group_secrets: Vec<Vec<u8>> =
+            split_secret(group_threshold, groups.len() as u8, &encrypted);
+
+        let shares: Vec<Vec<Share>> = groups
+            .iter()
+            .zip(group_secrets)
+            .enumerate()
+            .map(|(group_index, (group, group_secret))| {
+                split_secret(group.threshold, group.count, &group_secret)
+                    .into_iter()
+                    .enumerate()
+                    .map(|(member_index, value)| Share {
+                        identifier,
+                        extendable,
+                        iteration_exponent,
+                        group_index: group_index as u8,
+                        group_threshold,
+                        group_count: groups.len() as u8,
+                        member_index: member_index as u8,
+                        member_threshold: group.threshold,
+                        value,
+                    })
+                    .collect()
+            })
+            .collect();
+
+        Ok(shares)
+    }
+
+    /// Recover the secret from enough shares and decrypt it with `passphrase`
+    ///
+    /// There is no way to tell a wrong passphrase from a right one, a wrong passphrase simply
+    /// gives a different secret.
+    pub fn combine(shares: &[Share], passphrase: &str) -> Result<Vec<u8>, Error> {
+        let first: &Share = shares.first().ok_or(ErrorKind::NotEnoughShares(1, 0))?;
+
+        let mut groups: BTreeMap<u8, BTreeMap<u8, &Share>> = BTreeMap::new();
+
+        for share in shares {
+            if share.identifier != first.identifier
+                || share.extendable != first.extendable
+                || share.iteration_exponent != first.iteration_exponent
+                || share.group_threshold != first.group_threshold
+                || share.group_count != first.group_count
+                || share.value.len() != first.value.len()
+            {
+                return Err(ErrorKind::MismatchedShares.into());
+            }
+
+            let members: &mut BTreeMap<u8, &Share> = groups.entry(share.group_index).or_default();
+
+            if let Some(member) = members.values().next() {
+                if member.member_threshold != share.member_threshold {
+                    return Err(ErrorKind::MismatchedShares.into());
+                }
+            }
+
+            match members.insert(share.member_index, share) {
+                Some(other) if other.value != share.value => {
+                    return Err(ErrorKind::MismatchedShares.into());
+                }
+                _ => {}
+            }
+        }
+
+        let mut group_secrets: Vec<(u8, Vec<u8>)> = Vec::new();
+        let mut missing_members: Option<(u8, usize)> = None;
+
+        for (group_index, members) in &groups {
+            let threshold: u8 = members.values().next().unwrap().member_threshold;
+
+            if members.len() < threshold as usize {
+                missing_members.get_or_insert((threshold, members.len()));
+                continue;
+            }
+
+            let points: Vec<(u8, &[u8])> = members
+                .values()
+                .take(threshold as usize)
+                .map(|share: &&Share| (share.member_index, &share.value[..]))
+                .collect();
+
+            group_secrets.push((*group_index, recover_secret(threshold, &points)?));
+        }
+
+        if group_secrets.len() < first.group_threshold as usize {
+            let (needed, given): (u8, usize) =
+                missing_members.unwrap_or((first.group_threshold, group_secrets.len()));
+
+            return Err(ErrorKind::NotEnoughShares(needed, given).into());
+        }
+
+        let points: Vec<(u8, &[u8])> = group_secrets
+            .iter()
+            .take(first.group_threshold as usize)
+            .map(|(index, secret): &(u8, Vec<u8>)| (*index, &secret[..]))
+            .collect();
+
+        let encrypted: Vec<u8> = recover_secret(first.group_threshold, &points)?;
+
+        decrypt(
+            &encrypted,
+            passphrase,
+            first.iteration_exponent,
+            first.identifier,
+            first.extendable,
+        )
+    }
+
+    /// Parse a share from its phrase
+    ///
+    /// Words must be separated by whitespace. Returns an `Error` of kind
+    /// `ErrorKind::InvalidWord` for words that are not in the SLIP-39 word list, and of kind
+    /// `ErrorKind::InvalidShareChecksum` if the words were not written down correctly. A share
+    /// of a group past the group count returns an `Error` of kind `ErrorKind::InvalidGroupIndex`.
+    pub fn from_phrase(phrase: &str) -> Result<Share, Error> {
+        let words: Vec<u16> = phrase
+            .split_whitespace()
+            .enumerate()
+            .map(|(position, word): (usize, &str)| {
+                WORDLIST
+                    .binary_search(&word.to_lowercase().as_str())
+                    .map(|index: usize| index as u16)
+                    .map_err(|_| ErrorKind::InvalidWord { position })
+            })
+            .collect::<Result<Vec<u16>, ErrorKind>>()?;
+
+        if words.len() < MIN_SHARE_WORDS {
+            return Err(ErrorKind::InvalidShareWordCount(words.len()).into());
+        }
+
+        let id_exp: u32 = (u32::from(words[0]) << RADIX_BITS) | u32::from(words[1]);
+        let extendable: bool = id_exp & 0x10 != 0;
+
+        if rs1024_polymod(customization(extendable), &words) != 1 {
+            return Err(ErrorKind::InvalidShareChecksum.into());
+        }
+
+        let group: u32 = (u32::from(words[2]) << RADIX_BITS) | u32::from(words[3]);
+        let nibble = |shift: u32| ((group >> shift) & 0xF) as u8;
+
+        let share = Share {
+            identifier: (id_exp >> 5) as u16,
+            extendable,
+            iteration_exponent: (id_exp & 0xF) as u8,
+            group_index: nibble(16),
+            group_threshold: nibble(12) + 1,
+            group_count: nibble(8) + 1,
+            member_index: nibble(4),
+            member_threshold: nibble(0) + 1,
+            value: words_to_bytes(
+                &words[ID_EXP_WORDS + GROUP_WORDS..words.len() - CHECKSUM_WORDS],
+            )?,
+        };
+
+        if share.group_threshold > share.group_count {
+            return Err(
+                ErrorKind::InvalidThreshold(share.group_threshold, share.group_count).into(),
+            );
+        }
+
+        if share.group_index >= share.group_count {
+            return Err(ErrorKind::InvalidGroupIndex(share.group_index, share.group_count).into());
+        }
+
+        Ok(share)
+    }
+
+    /// Get the random identifier shared by all shares of a secret
+    pub fn identifier(&self) -> u16 {
+        self.identifier
+    }
+
+    /// Whether more shares can be added to the backup later
+    pub fn is_extendable(&self) -> bool {
+        self.extendable
+    }
+
+    /// Get the exponent of the PBKDF2 iteration count used to encrypt the secret
+    pub fn iteration_exponent(&self) -> u8 {
+        self.iteration_exponent
+    }
+
+    /// Get the index of the group this share belongs to, starting at `0`
+    pub fn group_index(&self) -> u8 {
+        self.group_index
+    }
+
+    /// Get the number of groups needed to recover the secret
+    pub fn group_threshold(&self) -> u8 {
+        self.group_threshold
+    }
+
+    /// Get the total number of groups
+    pub fn group_count(&self) -> u8 {
+        self.group_count
+    }
+
+    /// Get the index of this share within its group, starting at `0`
+    pub fn member_index(&self) -> u8 {
+        self.member_index
+    }
+
+    /// Get the number of members needed to recover the group
+    pub fn member_threshold(&self) -> u8 {
+        self.member_threshold
+    }
+
+    /// The word indices of the share, including the checksum
+    fn to_words(&self) -> Vec<u16> {
+        let id_exp: u32 = (u32::from(self.identifier) << 5)
+            | (u32::from(self.extendable) << 4)
+            | u32::from(self.iteration_exponent);
+        let group: u32 = (u32::from(self.group_index) << 16)
+            | (u32::from(self.group_threshold - 1) << 12)
+            | (u32::from(self.group_count - 1) << 8)
+            | (u32::from(self.member_index) << 4)
+            | u32::from(self.member_threshold - 1);
+
+        let mut words: Vec<u16> = vec![
+            (id_exp >> RADIX_BITS) as u16,
+            (id_exp % RADIX) as u16,
+            (group >> RADIX_BITS) as u16,
+            (group % RADIX) as u16,
+        ];
+
+        words.extend(bytes_to_words(&self.value));
+
+        let customization: &[u8] = customization(self.extendable);
+        let mut data: Vec<u16> = words.clone();
+
+        data.extend_from_slice(&[0; CHECKSUM_WORDS]);
+
+        let checksum: u32 = rs1024_polymod(customization, &data) ^ 1;
+
+        words.extend(
+            (0..CHECKSUM_WORDS)
+                .rev()
+                .map(|i: usize| ((checksum >> (RADIX_BITS * i)) % RADIX) as u16),
+        );
+
+        words
+    }
+}
+
+impl fmt::Display for Share {
+    /// Write the share as its phrase
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let words: Vec<&str> = self
+            .to_words()
+            .into_iter()
+            .map(|word: u16| WORDLIST[word as usize])
+            .collect();
+
+        f.write_str(&words.join(" "))
+    }
+}
+
+impl fmt::Debug for Share {
+    /// Only prints the share metadata, the share value is left out
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Share")
+            .field("identifier", &self.identifier)
+            .field("extendable", &self.extendable)
+            .field("iteration_exponent", &self.iteration_exponent)
+            .field("group_index", &self.group_index)
+            .field("group_threshold", &self.group_threshold)
+            .field("group_count", &self.group_count)
+            .field("member_index", &self.member_index)
+            .field("member_threshold", &self.member_threshold)
+            .finish()
+    }
+}
+
+impl FromStr for Share {
+    type Err = Error;
+
+    fn from_str(phrase: &str) -> Result<Share, Error> {
+        Share::from_phrase(phrase)
+    }
+}
+
+impl KeyPhrase {
+    /// Split the entropy of this [`KeyPhrase`][KeyPhrase] into SLIP-39 shares
+    ///
+    /// See [`Share::split()`][Share::split()] for the meaning of the arguments.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use keyphrase::{KeyPhrase, KeyPhraseType, Language, Share, ShareGroup};
+    ///
+    /// let keyphrase = KeyPhrase::new(KeyPhraseType::Words24, Language::English);
+    ///
+    /// // A single group of five shares, any three of which recover the keyphrase
+    /// let shares = keyphrase.to_shares("", 1, &[ShareGroup::new(3, 5)]).unwrap();
+    ///
+    /// let recovered = KeyPhrase::from_shares(&shares[0][1..4], "", Language::English).unwrap();
+    ///
+    /// assert_eq!(recovered.phrase(), keyphrase.phrase());
+    /// ```
+    ///
+    /// [KeyPhrase]: ./keyphrase/struct.KeyPhrase.html
+    /// [Share::split()]: ./struct.Share.html#method.split
+    pub fn to_shares(
+        &self,
+        passphrase: &str,
+        group_threshold: u8,
+        groups: &[ShareGroup],
+    ) -> Result<Vec<Vec<Share>>, Error> {
+        Share::split(self.entropy(), passphrase, group_threshold, groups)
+    }
+
+    /// Recover a [`KeyPhrase`][KeyPhrase] in the given language from SLIP-39 shares
+    ///
+    /// [KeyPhrase]: ./keyphrase/struct.KeyPhrase.html
+    pub fn from_shares(
+        shares: &[Share],
+        passphrase: &str,
+        lang: Language,
+    ) -> Result<KeyPhrase, Error> {
+        KeyPhrase::from_entropy(&Share::combine(shares, passphrase)?, lang)
+    }
+}
+
+fn check_threshold(threshold: u8, count: usize) -> Result<(), Error> {
+    if threshold == 0 || threshold as usize > count || count > MAX_SHARE_COUNT as usize {
+        return Err(ErrorKind::InvalidThreshold(threshold, count.min(255) as u8).into());
+    }
+
+    Ok(())
+}
+
+fn customization(extendable: bool) -> &'static [u8] {
+    if extendable {
+        CUSTOMIZATION_EXTENDABLE
+    } else {
+        CUSTOMIZATION
+    }
+}
+
+fn rs1024_polymod(customization: &[u8], words: &[u16]) -> u32 {
+    let values = customization
+        .iter()
+        .map(|byte: &u8| u32::from(*byte))
+        .chain(words.iter().map(|word: &u16| u32::from(*word)));
+
+    let mut checksum: u32 = 1;
+
+    for value in values {
+        let top: u32 = checksum >> 20;
+
+        checksum = ((checksum & 0xF_FFFF) << RADIX_BITS) ^ value;
+
+        for (i, gen) in RS1024_GEN.iter().enumerate() {
+            if (top >> i) & 1 != 0 {
+                checksum ^= gen;
+            }
+        }
+    }
+
+    checksum
+}
+
+/// Split bytes into 10 bit words, padding with zero bits at the front
+fn bytes_to_words(bytes: &[u8]) -> Vec<u16> {
+    let word_count: usize = (bytes.len() * 8).div_ceil(RADIX_BITS);
+    let mut words: Vec<u16> = Vec::with_capacity(word_count);
+    let mut acc: u32 = 0;
+    let mut acc_bits: usize = word_count * RADIX_BITS - bytes.len() * 8;
+
+    for byte in bytes {
+        acc = (acc << 8) | u32::from(*byte);
+        acc_bits += 8;
+
+        while acc_bits >= RADIX_BITS {
+            acc_bits -= RADIX_BITS;
+            words.push((acc >> acc_bits) as u16);
+            acc &= (1 << acc_bits) - 1;
+        }
+    }
+
+    words
+}
+
+/// Join 10 bit words back into bytes, the padding at the front must be zero and at most 8 bits
+fn words_to_bytes(words: &[u16]) -> Result<Vec<u8>, Error> {
+    // Secrets have an even number of bytes, so the padding is what's left over from 16 bits
+    let padding: usize = (words.len() * RADIX_BITS) % 16;
+
+    if padding > 8 {
+        return Err(ErrorKind::InvalidShareWordCount(words.len() + METADATA_WORDS).into());
+    }
+
+    let mut bytes: Vec<u8> = Vec::with_capacity((words.len() * RADIX_BITS - padding) / 8);
+    let mut acc: u32 = 0;
+    let mut acc_bits: usize = 0;
+    let mut skip: usize = padding;
+
+    for word in words {
+        acc = (acc << RADIX_BITS) | u32::from(*word);
+        acc_bits += RADIX_BITS;
+
+        if skip > 0 {
+            if acc >> (acc_bits - skip) != 0 {
+                return Err(ErrorKind::InvalidSharePadding.into());
+            }
+
+            acc_bits -= skip;
+            skip = 0;
+        }
+
+        while acc_bits >= 8 {
+            acc_bits -= 8;
+            bytes.push((acc >> acc_bits) as u8);
+            acc &= (1 << acc_bits) - 1;
+        }
+    }
+
+    Ok(bytes)
+}
+
+/// Evaluate at `x` the polynomial of lowest degree through `points`
+///
+/// Every byte position is interpolated separately, all values must have the same length and all
+/// x coordinates must be distinct.
+fn interpolate(points: &[(u8, &[u8])], x: u8) -> Vec<u8> {
+    if let Some((_, value)) = points.iter().find(|(index, _)| *index == x) {
+        return value.to_vec();
+    }
+
+    let (exp, log): &([u8; 255], [u8; 256]) = &GF256;
+    let log_product: u32 = points
+        .iter()
+        .map(|(index, _)| u32::from(log[(index ^ x) as usize]))
+        .sum();
+
+    let mut result: Vec<u8> = vec![0; points[0].1.len()];
+
+    for (index, value) in points {
+        // Logarithm of the Lagrange basis polynomial of this point, evaluated at x
+        let log_denominator: u32 = points
+            .iter()
+            .filter(|(other, _)| other != index)
+            .map(|(other, _)| u32::from(log[(index ^ other) as usize]))
+            .sum::<u32>()
+            + u32::from(log[(index ^ x) as usize]);
+        let log_basis: u32 = (log_product + 255 * points.len() as u32 - log_denominator) % 255;
+
+        for (out, byte) in result.iter_mut().zip(value.iter()) {
+            if *byte != 0 {
+                *out ^= exp[((u32::from(log[*byte as usize]) + log_basis) % 255) as usize];
+            }
+        }
+    }
+
+    result
+}
+
+fn digest(random: &[u8], secret: &[u8]) -> [u8; DIGEST_BYTES] {
+    let mut mac = Hmac::<Sha256>::new_varkey(random).expect("HMAC accepts keys of any length");
+    mac.input(secret);
+
+    let mut digest = [0u8; DIGEST_BYTES];
+
+    digest.copy_from_slice(&mac.result().code()[..DIGEST_BYTES]);
+
+    digest
+}
+
+/// Split `secret` into `count` shares, `threshold` of which are needed to recover it
+fn split_secret(threshold: u8, count: u8, secret: &[u8]) -> Vec<Vec<u8>> {
+    if threshold == 1 {
+        return vec![secret.to_vec(); count as usize];
+    }
+
+    let random_count: u8 = threshold - 2;
+    let random_shares: Vec<Vec<u8>> = (0..random_count)
+        .map(|_| gen_random_bytes(secret.len()))
+        .collect();
+
+    let random: Vec<u8> = gen_random_bytes(secret.len() - DIGEST_BYTES);
+    let mut digest_share: Vec<u8> = digest(&random, secret).to_vec();
+
+    digest_share.extend_from_slice(&random);
+
+    let mut points: Vec<(u8, &[u8])> = random_shares
+        .iter()
+        .enumerate()
+        .map(|(index, value)| (index as u8, &value[..]))
+        .collect();
+
+    points.push((DIGEST_INDEX, &digest_share));
+    points.push((SECRET_INDEX, secret));
+
+    (0..count)
+        .map(|index: u8| interpolate(&points, index))
+        .collect()
+}
+
+/// Recover a secret from `threshold` shares and check its digest
+fn recover_secret(threshold: u8, points: &[(u8, &[u8])]) -> Result<Vec<u8>, Error> {
+    if threshold == 1 {
+        return Ok(points[0].1.to_vec());
+    }
+
+    let secret: Vec<u8> = interpolate(points, SECRET_INDEX);
+    let digest_share: Vec<u8> = interpolate(points, DIGEST_INDEX);
+
+    if digest_share[..DIGEST_BYTES] != digest(&digest_share[DIGEST_BYTES..], &secret) {
+        return Err(ErrorKind::InvalidShareDigest.into());
+    }
+
+    Ok(secret)
+}
+
+/// One round of the Feistel network, PBKDF2-HMAC-SHA256 keyed with the round and passphrase
+fn round_function(
+    round: u8,
+    passphrase: &[u8],
+    iteration_exponent: u8,
+    salt: &[u8],
+    data: &[u8],
+) -> Vec<u8> {
+    let mut password: Vec<u8> = vec![round];
+    let mut round_salt: Vec<u8> = salt.to_vec();
+    let mut output: Vec<u8> = vec![0; data.len()];
+
+    password.extend_from_slice(passphrase);
+    round_salt.extend_from_slice(data);
+
+    let iterations: u32 = (BASE_ITERATION_COUNT << iteration_exponent) / u32::from(ROUND_COUNT);
+
+    pbkdf2::pbkdf2::<Hmac<Sha256>>(&password, &round_salt, iterations as usize, &mut output);
+
+    output
+}
+
+/// Run the Feistel network over `input`, with the rounds in the given order
+fn feistel(
+    input: &[u8],
+    passphrase: &str,
+    iteration_exponent: u8,
+    identifier: u16,
+    extendable: bool,
+    rounds: &[u8],
+) -> Result<Vec<u8>, Error> {
+    if !passphrase
+        .bytes()
+        .all(|byte: u8| (32..=126).contains(&byte))
+    {
+        return Err(ErrorKind::InvalidPassphrase.into());
+    }
+
+    let salt: Vec<u8> = if extendable {
+        Vec::new()
+    } else {
+        let mut salt: Vec<u8> = CUSTOMIZATION.to_vec();
+        salt.extend_from_slice(&identifier.to_be_bytes());
+        salt
+    };
+
+    let half: usize = input.len() / 2;
+    let mut left: Vec<u8> = input[..half].to_vec();
+    let mut right: Vec<u8> = input[half..].to_vec();
+
+    for round in rounds {
+        let f: Vec<u8> = round_function(
+            *round,
+            passphrase.as_bytes(),
+            iteration_exponent,
+            &salt,
+            &right,
+        );
+        let mixed: Vec<u8> = left.iter().zip(f).map(|(l, f)| l ^ f).collect();
+
+        left = std::mem::replace(&mut right, mixed);
+    }
+
+    right.extend_from_slice(&left);
+
+    Ok(right)
+}
+
+fn encrypt(
+    secret: &[u8],
+    passphrase: &str,
+    iteration_exponent: u8,
+    identifier: u16,
+    extendable: bool,
+) -> Result<Vec<u8>, Error> {
+    let rounds: Vec<u8> = (0..ROUND_COUNT).collect();
+
+    feistel(
+        secret,
+        passphrase,
+        iteration_exponent,
+        identifier,
+        extendable,
+        &rounds,
+    )
+}
+
+fn decrypt(
+    encrypted: &[u8],
+    passphrase: &str,
+    iteration_exponent: u8,
+    identifier: u16,
+    extendable: bool,
+) -> Result<Vec<u8>, Error> {
+    let rounds: Vec<u8> = (0..ROUND_COUNT).rev().collect();
+
+    feistel(
+        encrypted,
+        passphrase,
+        iteration_exponent,
+        identifier,
+        extendable,
+        &rounds,
+    )
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn wordlist_is_sorted() {
+        assert_eq!(WORDLIST.len(), RADIX as usize);
+        assert!(WORDLIST.windows(2).all(|pair| pair[0] < pair[1]));
+    }
+
+    #[test]
+    fn words_round_trip() {
+        for len in &[16, 20, 24, 28, 32] {
+            let bytes: Vec<u8> = gen_random_bytes(*len);
+            let words: Vec<u16> = bytes_to_words(&bytes);
+
+            assert_eq!(words.len(), (len * 8).div_ceil(10));
+            assert_eq!(words_to_bytes(&words).unwrap(), bytes);
+        }
+
+        // 16 bytes use 2 bits of padding, which must be zero
+        let mut words: Vec<u16> = bytes_to_words(&[0xFF; 16]);
+        words[0] |= 0x200;
+
+        assert!(words_to_bytes(&words).is_err());
+    }
+
+    #[test]
+    fn interpolate_through_points() {
+        let secret: Vec<u8> = gen_random_bytes(16);
+        let shares: Vec<Vec<u8>> = split_secret(3, 5, &secret);
+
+        let points: Vec<(u8, &[u8])> = vec![(4, &shares[4]), (1, &shares[1]), (2, &shares[2])];
+
+        assert_eq!(recover_secret(3, &points).unwrap(), secret);
+        assert_eq!(interpolate(&points, 0), shares[0]);
+    }
+
+    #[test]
+    fn reject_group_index_out_of_range() {
+        let share = Share {
+            identifier: 7,
+            extendable: true,
+            iteration_exponent: 0,
+            group_index: 2,
+            group_threshold: 1,
+            group_count: 2,
+            member_index: 0,
+            member_threshold: 1,
+            value: vec![0; 16],
+        };
+
+        let error: Error = share.to_string().parse::<Share>().unwrap_err();
+
+        assert!(matches!(error.kind(), ErrorKind::InvalidGroupIndex(2, 2)));
+    }
+
+    #[test]
+    fn feistel_round_trip() {
+        let secret: Vec<u8> = gen_random_bytes(16);
+        let encrypted: Vec<u8> = encrypt(&secret, "TREZOR", 0, 7, false).unwrap();
+
+        assert_ne!(encrypted, secret);
+        assert_eq!(decrypt(&encrypted, "TREZOR", 0, 7, false).unwrap(), secret);
+        assert_ne!(decrypt(&encrypted, "TREZOR", 0, 8, false).unwrap(), secret);
+        assert!(encrypt(&secret, "naïve", 0, 7, false).is_err());
+    }
+}
@@ -1,3 +1,5 @@
+use crate::error::{Error, ErrorKind};
+
 pub(crate) trait IterExt: Iterator {
     fn join<R>(&mut self, glue: &str) -> R
     where
@@ -49,7 +51,7 @@ impl Bits for u8 {
     }
 }
 
-impl<'a> Bits for &'a u8 {
+impl Bits for &u8 {
     const SIZE: usize = 8;
 
     fn bits(self) -> u32 {
@@ -90,7 +92,7 @@ impl BitWriter {
     pub fn with_capacity(capacity: usize) -> Self {
         let mut bytes = capacity / 8;
 
-        if capacity % 8 != 0 {
+        if !capacity.is_multiple_of(8) {
             bytes += 1;
         }
 
@@ -184,9 +186,84 @@ where
     }
 }
 
-/// Extract the first `bits` from the `source` byte
-pub(crate) fn checksum(source: u8, bits: u8) -> u8 {
-    debug_assert!(bits <= 8, "Can operate on 8-bit integers only");
+/// Extract the first `bits` from the `source` bytes
+pub(crate) fn checksum(source: [u8; 2], bits: u8) -> u16 {
+    debug_assert!(
+        (1..=16).contains(&bits),
+        "Can operate on 16-bit integers only"
+    );
+
+    u16::from_be_bytes(source) >> (16 - bits)
+}
+
+/// Levenshtein distance between two strings, counted in `char`s
+pub(crate) fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut row: Vec<usize> = (0..=b.len()).collect();
+
+    for (i, ca) in a.chars().enumerate() {
+        let mut diagonal: usize = row[0];
+        row[0] = i + 1;
+
+        for (j, cb) in b.iter().enumerate() {
+            let above: usize = row[j + 1];
+            let substitution: usize = if ca == *cb { diagonal } else { diagonal + 1 };
+
+            row[j + 1] = substitution.min(above + 1).min(row[j] + 1);
+            diagonal = above;
+        }
+    }
+
+    row[b.len()]
+}
+
+/// Write bytes as lowercase hex
+pub(crate) fn to_hex(bytes: &[u8]) -> String {
+    bytes
+        .iter()
+        .map(|byte: &u8| format!("{:02x}", byte))
+        .collect()
+}
 
-    source >> (8 - bits)
+/// Read hex in either case, with an optional `0x` prefix
+pub(crate) fn from_hex(hex: &str) -> Result<Vec<u8>, Error> {
+    let digits: &str = hex
+        .strip_prefix("0x")
+        .or_else(|| hex.strip_prefix("0X"))
+        .unwrap_or(hex);
+
+    if let Some(c) = digits.chars().find(|c: &char| !c.is_ascii_hexdigit()) {
+        return Err(ErrorKind::InvalidHexCharacter(c).into());
+    }
+
+    if !digits.len().is_multiple_of(2) {
+        return Err(ErrorKind::InvalidHexLength(digits.len()).into());
+    }
+
+    Ok((0..digits.len())
+        .step_by(2)
+        .map(|i: usize| u8::from_str_radix(&digits[i..i + 2], 16).expect("Checked hex digits"))
+        .collect())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn hex_round_trip() {
+        assert_eq!(to_hex(&[0x00, 0xab, 0xff]), "00abff");
+        assert_eq!(from_hex("00abff").unwrap(), [0x00, 0xab, 0xff]);
+        assert_eq!(from_hex("00ABFF").unwrap(), [0x00, 0xab, 0xff]);
+        assert_eq!(from_hex("0x00abff").unwrap(), [0x00, 0xab, 0xff]);
+        assert!(from_hex("").unwrap().is_empty());
+
+        let error: Error = from_hex("abc").unwrap_err();
+        assert!(matches!(error.kind(), ErrorKind::InvalidHexLength(3)));
+
+        for (hex, invalid) in &[("zz", 'z'), ("+1", '+'), ("é0", 'é'), ("0x0x", 'x')] {
+            let error: Error = from_hex(hex).unwrap_err();
+            assert!(matches!(error.kind(), ErrorKind::InvalidHexCharacter(c) if c == invalid));
+        }
+    }
 }
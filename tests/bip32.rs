@@ -0,0 +1,328 @@
+#![cfg(feature = "bip32")]
+
+use keyphrase::{
+    DerivationPath, Encoding, ExtendedKeyVersion, ExtendedPrivateKey, ExtendedPublicKey, KeyPhrase,
+    Language, Seed,
+};
+
+struct TestVector {
+    path: &'static str,
+    chain_code: &'static str,
+    private_key: &'static str,
+    public_key: &'static str,
+    parent_fingerprint: &'static str,
+    xprv: &'static str,
+    xpub: &'static str,
+}
+
+fn check_vectors(seed: &str, vectors: &[TestVector]) {
+    let master: ExtendedPrivateKey =
+        ExtendedPrivateKey::new_master(&Encoding::Hex.decode(seed).unwrap()).unwrap();
+
+    for vector in vectors {
+        let path: DerivationPath = vector.path.parse().unwrap();
+        let key: ExtendedPrivateKey = master.derive_path(&path).unwrap();
+        let public: ExtendedPublicKey = key.extended_public_key();
+
+        assert_eq!(
+            Encoding::Hex.encode(key.chain_code()),
+            vector.chain_code,
+            "{}",
+            vector.path
+        );
+        assert_eq!(
+            Encoding::Hex.encode(&key.private_key_bytes()),
+            vector.private_key,
+            "{}",
+            vector.path
+        );
+        assert_eq!(
+            Encoding::Hex.encode(&key.public_key_bytes()),
+            vector.public_key,
+            "{}",
+            vector.path
+        );
+        assert_eq!(
+            Encoding::Hex.encode(&key.parent_fingerprint()),
+            vector.parent_fingerprint,
+            "{}",
+            vector.path
+        );
+        assert_eq!(key.depth() as usize, path.as_ref().len());
+        assert_eq!(
+            Encoding::Hex.encode(&public.public_key_bytes()),
+            vector.public_key
+        );
+        assert_eq!(public.chain_code(), key.chain_code());
+
+        assert_eq!(key.to_base58(ExtendedKeyVersion::MAINNET), vector.xprv);
+        assert_eq!(public.to_string(), vector.xpub);
+
+        let parsed: ExtendedPrivateKey = vector.xprv.parse().unwrap();
+        assert_eq!(parsed.private_key_bytes(), key.private_key_bytes());
+        assert_eq!(parsed.chain_code(), key.chain_code());
+        assert_eq!(parsed.parent_fingerprint(), key.parent_fingerprint());
+        assert_eq!(parsed.child_number(), key.child_number());
+        assert_eq!(vector.xpub.parse::<ExtendedPublicKey>().unwrap(), public);
+    }
+
+    // Every normal derivation step must also work from the parent public key
+    for pair in vectors.windows(2) {
+        let parent: DerivationPath = pair[0].path.parse().unwrap();
+        let child: DerivationPath = pair[1].path.parse().unwrap();
+        let last = child.as_ref()[child.as_ref().len() - 1];
+
+        if last.is_hardened() {
+            continue;
+        }
+
+        let public: ExtendedPublicKey = master
+            .derive_path(&parent)
+            .unwrap()
+            .extended_public_key()
+            .derive_child(last)
+            .unwrap();
+
+        assert_eq!(
+            Encoding::Hex.encode(&public.public_key_bytes()),
+            pair[1].public_key
+        );
+        assert_eq!(
+            Encoding::Hex.encode(public.chain_code()),
+            pair[1].chain_code
+        );
+        assert_eq!(
+            Encoding::Hex.encode(&public.parent_fingerprint()),
+            pair[1].parent_fingerprint
+        );
+    }
+}
+
+#[test]
+fn bip32_test_vector_1() {
+    let seed: &str = "000102030405060708090a0b0c0d0e0f";
+
+    check_vectors(
+        seed,
+        &[
+            TestVector {
+                path: "m",
+                chain_code: "873dff81c02f525623fd1fe5167eac3a55a049de3d314bb42ee227ffed37d508",
+                private_key: "e8f32e723decf4051aefac8e2c93c9c5b214313817cdb01a1494b917c8436b35",
+                public_key: "0339a36013301597daef41fbe593a02cc513d0b55527ec2df1050e2e8ff49c85c2",
+                parent_fingerprint: "00000000",
+                xprv: "xprv9s21ZrQH143K3QTDL4LXw2F7HEK3wJUD2nW2nRk4stbPy6cq3jPPqjiChkVvvNKmPGJxWUtg6LnF5kejMRNNU3TGtRBeJgk33yuGBxrMPHi",
+                xpub: "xpub661MyMwAqRbcFtXgS5sYJABqqG9YLmC4Q1Rdap9gSE8NqtwybGhePY2gZ29ESFjqJoCu1Rupje8YtGqsefD265TMg7usUDFdp6W1EGMcet8",
+            },
+            TestVector {
+                path: "m/0'",
+                chain_code: "47fdacbd0f1097043b78c63c20c34ef4ed9a111d980047ad16282c7ae6236141",
+                private_key: "edb2e14f9ee77d26dd93b4ecede8d16ed408ce149b6cd80b0715a2d911a0afea",
+                public_key: "035a784662a4a20a65bf6aab9ae98a6c068a81c52e4b032c0fb5400c706cfccc56",
+                parent_fingerprint: "3442193e",
+                xprv: "xprv9uHRZZhk6KAJC1avXpDAp4MDc3sQKNxDiPvvkX8Br5ngLNv1TxvUxt4cV1rGL5hj6KCesnDYUhd7oWgT11eZG7XnxHrnYeSvkzY7d2bhkJ7",
+                xpub: "xpub68Gmy5EdvgibQVfPdqkBBCHxA5htiqg55crXYuXoQRKfDBFA1WEjWgP6LHhwBZeNK1VTsfTFUHCdrfp1bgwQ9xv5ski8PX9rL2dZXvgGDnw",
+            },
+            TestVector {
+                path: "m/0'/1",
+                chain_code: "2a7857631386ba23dacac34180dd1983734e444fdbf774041578e9b6adb37c19",
+                private_key: "3c6cb8d0f6a264c91ea8b5030fadaa8e538b020f0a387421a12de9319dc93368",
+                public_key: "03501e454bf00751f24b1b489aa925215d66af2234e3891c3b21a52bedb3cd711c",
+                parent_fingerprint: "5c1bd648",
+                xprv: "xprv9wTYmMFdV23N2TdNG573QoEsfRrWKQgWeibmLntzniatZvR9BmLnvSxqu53Kw1UmYPxLgboyZQaXwTCg8MSY3H2EU4pWcQDnRnrVA1xe8fs",
+                xpub: "xpub6ASuArnXKPbfEwhqN6e3mwBcDTgzisQN1wXN9BJcM47sSikHjJf3UFHKkNAWbWMiGj7Wf5uMash7SyYq527Hqck2AxYysAA7xmALppuCkwQ",
+            },
+            TestVector {
+                path: "m/0'/1/2'",
+                chain_code: "04466b9cc8e161e966409ca52986c584f07e9dc81f735db683c3ff6ec7b1503f",
+                private_key: "cbce0d719ecf7431d88e6a89fa1483e02e35092af60c042b1df2ff59fa424dca",
+                public_key: "0357bfe1e341d01c69fe5654309956cbea516822fba8a601743a012a7896ee8dc2",
+                parent_fingerprint: "bef5a2f9",
+                xprv: "xprv9z4pot5VBttmtdRTWfWQmoH1taj2axGVzFqSb8C9xaxKymcFzXBDptWmT7FwuEzG3ryjH4ktypQSAewRiNMjANTtpgP4mLTj34bhnZX7UiM",
+                xpub: "xpub6D4BDPcP2GT577Vvch3R8wDkScZWzQzMMUm3PWbmWvVJrZwQY4VUNgqFJPMM3No2dFDFGTsxxpG5uJh7n7epu4trkrX7x7DogT5Uv6fcLW5",
+            },
+            TestVector {
+                path: "m/0'/1/2'/2",
+                chain_code: "cfb71883f01676f587d023cc53a35bc7f88f724b1f8c2892ac1275ac822a3edd",
+                private_key: "0f479245fb19a38a1954c5c7c0ebab2f9bdfd96a17563ef28a6a4b1a2a764ef4",
+                public_key: "02e8445082a72f29b75ca48748a914df60622a609cacfce8ed0e35804560741d29",
+                parent_fingerprint: "ee7ab90c",
+                xprv: "xprvA2JDeKCSNNZky6uBCviVfJSKyQ1mDYahRjijr5idH2WwLsEd4Hsb2Tyh8RfQMuPh7f7RtyzTtdrbdqqsunu5Mm3wDvUAKRHSC34sJ7in334",
+                xpub: "xpub6FHa3pjLCk84BayeJxFW2SP4XRrFd1JYnxeLeU8EqN3vDfZmbqBqaGJAyiLjTAwm6ZLRQUMv1ZACTj37sR62cfN7fe5JnJ7dh8zL4fiyLHV",
+            },
+            TestVector {
+                path: "m/0'/1/2'/2/1000000000",
+                chain_code: "c783e67b921d2beb8f6b389cc646d7263b4145701dadd2161548a8b078e65e9e",
+                private_key: "471b76e389e528d6de6d816857e012c5455051cad6660850e58372a6c3e6e7c8",
+                public_key: "022a471424da5e657499d1ff51cb43c47481a03b1e77f951fe64cec9f5a48f7011",
+                parent_fingerprint: "d880d7d8",
+                xprv: "xprvA41z7zogVVwxVSgdKUHDy1SKmdb533PjDz7J6N6mV6uS3ze1ai8FHa8kmHScGpWmj4WggLyQjgPie1rFSruoUihUZREPSL39UNdE3BBDu76",
+                xpub: "xpub6H1LXWLaKsWFhvm6RVpEL9P4KfRZSW7abD2ttkWP3SSQvnyA8FSVqNTEcYFgJS2UaFcxupHiYkro49S8yGasTvXEYBVPamhGW6cFJodrTHy",
+            },
+        ],
+    );
+}
+
+#[test]
+fn bip32_test_vector_2() {
+    let seed: &str =
+        "fffcf9f6f3f0edeae7e4e1dedbd8d5d2cfccc9c6c3c0bdbab7b4b1aeaba8a5a29f9c999693908d8a8784817e7b7875726f6c696663605d5a5754514e4b484542";
+
+    check_vectors(
+        seed,
+        &[
+            TestVector {
+                path: "m",
+                chain_code: "60499f801b896d83179a4374aeb7822aaeaceaa0db1f85ee3e904c4defbd9689",
+                private_key: "4b03d6fc340455b363f51020ad3ecca4f0850280cf436c70c727923f6db46c3e",
+                public_key: "03cbcaa9c98c877a26977d00825c956a238e8dddfbd322cce4f74b0b5bd6ace4a7",
+                parent_fingerprint: "00000000",
+                xprv: "xprv9s21ZrQH143K31xYSDQpPDxsXRTUcvj2iNHm5NUtrGiGG5e2DtALGdso3pGz6ssrdK4PFmM8NSpSBHNqPqm55Qn3LqFtT2emdEXVYsCzC2U",
+                xpub: "xpub661MyMwAqRbcFW31YEwpkMuc5THy2PSt5bDMsktWQcFF8syAmRUapSCGu8ED9W6oDMSgv6Zz8idoc4a6mr8BDzTJY47LJhkJ8UB7WEGuduB",
+            },
+            TestVector {
+                path: "m/0",
+                chain_code: "f0909affaa7ee7abe5dd4e100598d4dc53cd709d5a5c2cac40e7412f232f7c9c",
+                private_key: "abe74a98f6c7eabee0428f53798f0ab8aa1bd37873999041703c742f15ac7e1e",
+                public_key: "02fc9e5af0ac8d9b3cecfe2a888e2117ba3d089d8585886c9c826b6b22a98d12ea",
+                parent_fingerprint: "bd16bee5",
+                xprv: "xprv9vHkqa6EV4sPZHYqZznhT2NPtPCjKuDKGY38FBWLvgaDx45zo9WQRUT3dKYnjwih2yJD9mkrocEZXo1ex8G81dwSM1fwqWpWkeS3v86pgKt",
+                xpub: "xpub69H7F5d8KSRgmmdJg2KhpAK8SR3DjMwAdkxj3ZuxV27CprR9LgpeyGmXUbC6wb7ERfvrnKZjXoUmmDznezpbZb7ap6r1D3tgFxHmwMkQTPH",
+            },
+            TestVector {
+                path: "m/0/2147483647'",
+                chain_code: "be17a268474a6bb9c61e1d720cf6215e2a88c5406c4aee7b38547f585c9a37d9",
+                private_key: "877c779ad9687164e9c2f4f0f4ff0340814392330693ce95a58fe18fd52e6e93",
+                public_key: "03c01e7425647bdefa82b12d9bad5e3e6865bee0502694b94ca58b666abc0a5c3b",
+                parent_fingerprint: "5a61ff8e",
+                xprv: "xprv9wSp6B7kry3Vj9m1zSnLvN3xH8RdsPP1Mh7fAaR7aRLcQMKTR2vidYEeEg2mUCTAwCd6vnxVrcjfy2kRgVsFawNzmjuHc2YmYRmagcEPdU9",
+                xpub: "xpub6ASAVgeehLbnwdqV6UKMHVzgqAG8Gr6riv3Fxxpj8ksbH9ebxaEyBLZ85ySDhKiLDBrQSARLq1uNRts8RuJiHjaDMBU4Zn9h8LZNnBC5y4a",
+            },
+            TestVector {
+                path: "m/0/2147483647'/1",
+                chain_code: "f366f48f1ea9f2d1d3fe958c95ca84ea18e4c4ddb9366c336c927eb246fb38cb",
+                private_key: "704addf544a06e5ee4bea37098463c23613da32020d604506da8c0518e1da4b7",
+                public_key: "03a7d1d856deb74c508e05031f9895dab54626251b3806e16b4bd12e781a7df5b9",
+                parent_fingerprint: "d8ab4937",
+                xprv: "xprv9zFnWC6h2cLgpmSA46vutJzBcfJ8yaJGg8cX1e5StJh45BBciYTRXSd25UEPVuesF9yog62tGAQtHjXajPPdbRCHuWS6T8XA2ECKADdw4Ef",
+                xpub: "xpub6DF8uhdarytz3FWdA8TvFSvvAh8dP3283MY7p2V4SeE2wyWmG5mg5EwVvmdMVCQcoNJxGoWaU9DCWh89LojfZ537wTfunKau47EL2dhHKon",
+            },
+            TestVector {
+                path: "m/0/2147483647'/1/2147483646'",
+                chain_code: "637807030d55d01f9a0cb3a7839515d796bd07706386a6eddf06cc29a65a0e29",
+                private_key: "f1c7c871a54a804afe328b4c83a1c33b8e5ff48f5087273f04efa83b247d6a2d",
+                public_key: "02d2b36900396c9282fa14628566582f206a5dd0bcc8d5e892611806cafb0301f0",
+                parent_fingerprint: "78412e3a",
+                xprv: "xprvA1RpRA33e1JQ7ifknakTFpgNXPmW2YvmhqLQYMmrj4xJXXWYpDPS3xz7iAxn8L39njGVyuoseXzU6rcxFLJ8HFsTjSyQbLYnMpCqE2VbFWc",
+                xpub: "xpub6ERApfZwUNrhLCkDtcHTcxd75RbzS1ed54G1LkBUHQVHQKqhMkhgbmJbZRkrgZw4koxb5JaHWkY4ALHY2grBGRjaDMzQLcgJvLJuZZvRcEL",
+            },
+            TestVector {
+                path: "m/0/2147483647'/1/2147483646'/2",
+                chain_code: "9452b549be8cea3ecb7a84bec10dcfd94afe4d129ebfd3b3cb58eedf394ed271",
+                private_key: "bb7d39bdb83ecf58f2fd82b6d918341cbef428661ef01ab97c28a4842125ac23",
+                public_key: "024d902e1a2fc7a8755ab5b694c575fce742c48d9ff192e63df5193e4c7afe1f9c",
+                parent_fingerprint: "31a507b8",
+                xprv: "xprvA2nrNbFZABcdryreWet9Ea4LvTJcGsqrMzxHx98MMrotbir7yrKCEXw7nadnHM8Dq38EGfSh6dqA9QWTyefMLEcBYJUuekgW4BYPJcr9E7j",
+                xpub: "xpub6FnCn6nSzZAw5Tw7cgR9bi15UV96gLZhjDstkXXxvCLsUXBGXPdSnLFbdpq8p9HmGsApME5hQTZ3emM2rnY5agb9rXpVGyy3bdW6EEgAtqt",
+            },
+        ],
+    );
+}
+
+#[test]
+fn bip32_test_vector_3() {
+    let seed: &str =
+        "4b381541583be4423346c643850da4b320e46a87ae3d2a4e6da11eba819cd4acba45d239319ac14f863b8d5ab5a0d0c64d2e8a1e7d1457df2e5a3c51c73235be";
+
+    check_vectors(
+        seed,
+        &[
+            TestVector {
+                path: "m",
+                chain_code: "01d28a3e53cffa419ec122c968b3259e16b65076495494d97cae10bbfec3c36f",
+                private_key: "00ddb80b067e0d4993197fe10f2657a844a384589847602d56f0c629c81aae32",
+                public_key: "03683af1ba5743bdfc798cf814efeeab2735ec52d95eced528e692b8e34c4e5669",
+                parent_fingerprint: "00000000",
+                xprv: "xprv9s21ZrQH143K25QhxbucbDDuQ4naNntJRi4KUfWT7xo4EKsHt2QJDu7KXp1A3u7Bi1j8ph3EGsZ9Xvz9dGuVrtHHs7pXeTzjuxBrCmmhgC6",
+                xpub: "xpub661MyMwAqRbcEZVB4dScxMAdx6d4nFc9nvyvH3v4gJL378CSRZiYmhRoP7mBy6gSPSCYk6SzXPTf3ND1cZAceL7SfJ1Z3GC8vBgp2epUt13",
+            },
+            TestVector {
+                path: "m/0'",
+                chain_code: "e5fea12a97b927fc9dc3d2cb0d1ea1cf50aa5a1fdc1f933e8906bb38df3377bd",
+                private_key: "491f7a2eebc7b57028e0d3faa0acda02e75c33b03c48fb288c41e2ea44e1daef",
+                public_key: "026557fdda1d5d43d79611f784780471f086d58e8126b8c40acb82272a7712e7f2",
+                parent_fingerprint: "41d63b50",
+                xprv: "xprv9uPDJpEQgRQfDcW7BkF7eTya6RPxXeJCqCJGHuCJ4GiRVLzkTXBAJMu2qaMWPrS7AANYqdq6vcBcBUdJCVVFceUvJFjaPdGZ2y9WACViL4L",
+                xpub: "xpub68NZiKmJWnxxS6aaHmn81bvJeTESw724CRDs6HbuccFQN9Ku14VQrADWgqbhhTHBaohPX4CjNLf9fq9MYo6oDaPPLPxSb7gwQN3ih19Zm4Y",
+            },
+        ],
+    );
+}
+
+#[test]
+fn hardened_path_from_public_key_fails() {
+    let master: ExtendedPrivateKey = ExtendedPrivateKey::new_master(&[1; 64]).unwrap();
+    let path: DerivationPath = "m/0/1'".parse().unwrap();
+
+    assert!(master.extended_public_key().derive_path(&path).is_err());
+    assert!(master.derive_path(&path).is_ok());
+}
+
+#[test]
+fn testnet_serialization() {
+    let master: ExtendedPrivateKey = ExtendedPrivateKey::new_master(&[1; 64]).unwrap();
+    let key: ExtendedPrivateKey = master.derive_path(&"m/44'/1'/0'".parse().unwrap()).unwrap();
+    let public: ExtendedPublicKey = key.extended_public_key();
+
+    let tprv: String = key.to_base58(ExtendedKeyVersion::TESTNET);
+    let tpub: String = public.to_base58(ExtendedKeyVersion::TESTNET);
+
+    assert!(tprv.starts_with("tprv"));
+    assert!(tpub.starts_with("tpub"));
+    assert_eq!(
+        tprv.parse::<ExtendedPrivateKey>()
+            .unwrap()
+            .private_key_bytes(),
+        key.private_key_bytes()
+    );
+    assert_eq!(tpub.parse::<ExtendedPublicKey>().unwrap(), public);
+
+    assert!(ExtendedPublicKey::from_base58(&tpub, ExtendedKeyVersion::MAINNET).is_err());
+    assert!(ExtendedPrivateKey::from_base58(&tpub, ExtendedKeyVersion::TESTNET).is_err());
+}
+
+#[test]
+fn custom_version_bytes() {
+    // SLIP-132 zprv/zpub, used for native segwit accounts
+    let version: ExtendedKeyVersion = ExtendedKeyVersion::new(0x04b2_430c, 0x04b2_4746);
+    let master: ExtendedPrivateKey = ExtendedPrivateKey::new_master(&[1; 64]).unwrap();
+
+    let zprv: String = master.to_base58(version);
+    let zpub: String = master.extended_public_key().to_base58(version);
+
+    assert!(zprv.starts_with("zprv"));
+    assert!(zpub.starts_with("zpub"));
+    assert!(zprv.parse::<ExtendedPrivateKey>().is_err());
+    assert_eq!(
+        ExtendedPublicKey::from_base58(&zpub, version).unwrap(),
+        master.extended_public_key()
+    );
+}
+
+#[test]
+fn seed_master_fingerprint() {
+    let phrase: &str = "park remain person kitchen mule spell knee armed position rail grid ankle";
+    let keyphrase: KeyPhrase = KeyPhrase::from_phrase(phrase, Language::English).unwrap();
+    let seed: Seed = Seed::new(&keyphrase, "");
+
+    assert_eq!(
+        seed.master_fingerprint().unwrap(),
+        ExtendedPrivateKey::from_seed(&seed).unwrap().fingerprint()
+    );
+    assert_ne!(
+        seed.master_fingerprint().unwrap(),
+        Seed::new(&keyphrase, "password")
+            .master_fingerprint()
+            .unwrap()
+    );
+}
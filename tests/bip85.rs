@@ -0,0 +1,110 @@
+#![cfg(feature = "bip85")]
+
+use keyphrase::{Encoding, ExtendedPrivateKey, KeyPhrase, KeyPhraseType, Language, Seed};
+
+/// Master key of the BIP85 reference vectors
+const XPRV: &str = "xprv9s21ZrQH143K2LBWUUQRFXhucrQqBpKdRRxNVq2zBqsx8HVqFk2uYo8kmbaLLHRdqtQpUm98uKfu3vca1LqdGhUtyoFnCNkfmXRyPXLjbKb";
+
+fn master() -> ExtendedPrivateKey {
+    XPRV.parse().unwrap()
+}
+
+fn check_keyphrase(keyphrase_type: KeyPhraseType, entropy: &str, phrase: &str) {
+    let keyphrase: KeyPhrase = master()
+        .derive_child_keyphrase(0, keyphrase_type, Language::English)
+        .unwrap();
+
+    assert_eq!(Encoding::Hex.encode(keyphrase.entropy()), entropy);
+    assert_eq!(keyphrase.phrase(), phrase);
+}
+
+#[test]
+fn bip85_bip39_12_words() {
+    check_keyphrase(
+        KeyPhraseType::Words12,
+        "6250b68daf746d12a24d58b4787a714b",
+        "girl mad pet galaxy egg matter matrix prison refuse sense ordinary nose",
+    );
+}
+
+#[test]
+fn bip85_bip39_18_words() {
+    check_keyphrase(
+        KeyPhraseType::Words18,
+        "938033ed8b12698449d4bbca3c853c66b293ea1b1ce9d9dc",
+        "near account window bike charge season chef number sketch tomorrow excuse sniff circle vital hockey outdoor supply token",
+    );
+}
+
+#[test]
+fn bip85_bip39_24_words() {
+    check_keyphrase(
+        KeyPhraseType::Words24,
+        "ae131e2312cdc61331542efe0d1077bac5ea803adf24b313a4f0e48e9c51f37f",
+        "puppy ocean match cereal symbol another shed magic wrap hammer bulb intact gadget divorce twin tonight reason outdoor destroy simple truth cigar social volcano",
+    );
+}
+
+#[test]
+fn bip85_bip39_language_changes_entropy() {
+    let english: KeyPhrase = master()
+        .derive_child_keyphrase(0, KeyPhraseType::Words12, Language::English)
+        .unwrap();
+    let japanese: KeyPhrase = master()
+        .derive_child_keyphrase(0, KeyPhraseType::Words12, Language::Japanese)
+        .unwrap();
+
+    assert_eq!(
+        Encoding::Hex.encode(japanese.entropy()),
+        "2536954d9c7b38f2b3a70e8aab996381"
+    );
+    assert_ne!(english.entropy(), japanese.entropy());
+}
+
+#[test]
+fn bip85_hex() {
+    assert_eq!(
+        master().derive_child_hex(0, 64).unwrap(),
+        "492db4698cf3b73a5a24998aa3e9d7fa96275d85724a91e71aa2d645442f878555d078fd1f1f67e368976f04137b1f7a0d19232136ca50c44614af72b5582a5c"
+    );
+}
+
+#[test]
+fn bip85_password_base64() {
+    assert_eq!(
+        master().derive_child_password(0, 21).unwrap(),
+        "dKLoepugzdVJvdL56ogNV"
+    );
+}
+
+#[test]
+fn bip85_from_seed() {
+    let phrase: &str = "park remain person kitchen mule spell knee armed position rail grid ankle";
+    let keyphrase: KeyPhrase = KeyPhrase::from_phrase(phrase, Language::English).unwrap();
+    let seed: Seed = Seed::new(&keyphrase, "");
+    let master: ExtendedPrivateKey = ExtendedPrivateKey::from_seed(&seed).unwrap();
+
+    let first: KeyPhrase = seed
+        .derive_child_keyphrase(0, KeyPhraseType::Words12, Language::English)
+        .unwrap();
+    let second: KeyPhrase = seed
+        .derive_child_keyphrase(1, KeyPhraseType::Words12, Language::English)
+        .unwrap();
+
+    assert_eq!(
+        first.phrase(),
+        master
+            .derive_child_keyphrase(0, KeyPhraseType::Words12, Language::English)
+            .unwrap()
+            .phrase()
+    );
+    assert_ne!(first.phrase(), second.phrase());
+    assert_eq!(
+        seed.derive_child_hex(3, 32).unwrap(),
+        master.derive_child_hex(3, 32).unwrap()
+    );
+    assert_eq!(
+        seed.derive_child_password(3, 40).unwrap(),
+        master.derive_child_password(3, 40).unwrap()
+    );
+}
@@ -0,0 +1,78 @@
+use keyphrase::codec;
+use keyphrase::{Encoding, Error, ErrorKind, Language};
+
+/// Data, checksum length in bits and words
+const VECTORS: &[(&str, u8, &str)] = &[
+    ("", 11, "abandon note"),
+    ("68656c6c6f", 11, "absent half clock brand ten clown"),
+    ("deadbeef", 0, "above team hospital rookie"),
+    ("ff", 1, "ability yellow"),
+    (
+        "000102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f",
+        16,
+        "advice abandon amount liar amount expire adjust cage candy arch gather drum bullet absurd math era live bid rhythm alien crouch range attend journey wagon audit",
+    ),
+];
+
+#[test]
+fn codec_vectors() {
+    for (data, checksum_bits, words) in VECTORS {
+        let data: Vec<u8> = Encoding::Hex.decode(data).unwrap();
+
+        assert_eq!(
+            codec::encode_with_checksum(&data, Language::English, *checksum_bits).unwrap(),
+            *words
+        );
+        assert_eq!(
+            codec::decode_with_checksum(words, Language::English, *checksum_bits).unwrap(),
+            data
+        );
+    }
+}
+
+#[test]
+fn codec_every_language() {
+    let data: &[u8] = b"pairing secret";
+
+    for lang in Language::all() {
+        let words: String = codec::encode(data, lang.clone()).unwrap();
+
+        assert_eq!(codec::decode(&words, lang.clone()).unwrap(), data);
+    }
+}
+
+#[test]
+fn codec_accepts_any_whitespace() {
+    let words: &str = "  absent half\nclock\tbrand  ten clown ";
+
+    assert_eq!(codec::decode(words, Language::English).unwrap(), b"hello");
+}
+
+#[test]
+fn codec_detects_errors() {
+    // Changed data word
+    let error: Error =
+        codec::decode("absent half clock brand tent clown", Language::English).unwrap_err();
+    assert!(matches!(error.kind(), ErrorKind::InvalidChecksum));
+
+    // Changed length word
+    let error: Error =
+        codec::decode("actor half clock brand ten clown", Language::English).unwrap_err();
+    assert!(matches!(error.kind(), ErrorKind::InvalidWordLength(6)));
+
+    // Missing word
+    let error: Error = codec::decode("absent half clock brand ten", Language::English).unwrap_err();
+    assert!(matches!(error.kind(), ErrorKind::InvalidWordLength(5)));
+
+    // Set padding bit, "ability yellow" with a 1 bit checksum has 2 bits of padding
+    let error: Error =
+        codec::decode_with_checksum("ability zebra", Language::English, 1).unwrap_err();
+    assert!(matches!(error.kind(), ErrorKind::InvalidChecksum));
+
+    let error: Error =
+        codec::decode("absent half clock brand ten clowns", Language::English).unwrap_err();
+    assert!(matches!(
+        error.kind(),
+        ErrorKind::InvalidWord { position: 5 }
+    ));
+}
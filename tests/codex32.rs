@@ -0,0 +1,223 @@
+use keyphrase::{Codex32Share, Encoding, Error, ErrorKind, KeyPhrase, KeyPhraseType, Language};
+
+fn parse(strings: &[&str]) -> Vec<Codex32Share> {
+    strings
+        .iter()
+        .map(|string: &&str| string.parse().expect("Valid codex32 string"))
+        .collect()
+}
+
+#[test]
+fn codex32_vector_1() {
+    let share: Codex32Share = "ms10testsxxxxxxxxxxxxxxxxxxxxxxxxxx4nzvca9cmczlw"
+        .parse()
+        .unwrap();
+
+    assert_eq!(share.threshold(), 0);
+    assert_eq!(share.identifier(), "test");
+    assert_eq!(share.share_index(), 's');
+    assert_eq!(
+        Encoding::Hex.encode(&share.data()),
+        "318c6318c6318c6318c6318c6318c631"
+    );
+    assert_eq!(
+        share.to_string(),
+        "ms10testsxxxxxxxxxxxxxxxxxxxxxxxxxx4nzvca9cmczlw"
+    );
+    assert_eq!(
+        Encoding::Hex.encode(&Codex32Share::combine(&[share]).unwrap()),
+        "318c6318c6318c6318c6318c6318c631"
+    );
+}
+
+#[test]
+fn codex32_vector_2() {
+    let shares: Vec<Codex32Share> = parse(&[
+        "MS12NAMEA320ZYXWVUTSRQPNMLKJHGFEDCAXRPP870HKKQRM",
+        "MS12NAMECACDEFGHJKLMNPQRSTUVWXYZ023FTR2GDZMPY6PN",
+    ]);
+
+    assert_eq!(
+        Codex32Share::interpolate(&shares, 'd').unwrap().to_string(),
+        "ms12namedll4f8jlh4e5vdvuldlfxu2jhdnlsm97xvenrxeg"
+    );
+    assert_eq!(
+        Codex32Share::interpolate(&shares, 's').unwrap().to_string(),
+        "ms12names6xqguzttxkeqnjsjzv4jv3nz5k3kwgsphuh6evw"
+    );
+    assert_eq!(
+        Encoding::Hex.encode(&Codex32Share::combine(&shares).unwrap()),
+        "d1808e096b35b209ca12132b264662a5"
+    );
+}
+
+#[test]
+fn codex32_vector_3() {
+    let secret: &str = "ms13cashsllhdmn9m42vcsamx24zrxgs3qqjzqud4m0d6nln";
+    let shares: Vec<Codex32Share> = parse(&[
+        "ms13casha320zyxwvutsrqpnmlkjhgfedca2a8d0zehn8a0t",
+        "ms13cashcacdefghjklmnpqrstuvwxyz023949xq35my48dr",
+        secret,
+    ]);
+
+    assert_eq!(
+        Codex32Share::interpolate(&shares, 'd').unwrap().to_string(),
+        "ms13cashd0wsedstcdcts64cd7wvy4m90lm28w4ffupqs7rm"
+    );
+    assert_eq!(
+        Codex32Share::interpolate(&shares, 'e').unwrap().to_string(),
+        "ms13casheekgpemxzshcrmqhaydlp6yhms3ws7320xyxsar9"
+    );
+    assert_eq!(
+        Codex32Share::interpolate(&shares, 'f').unwrap().to_string(),
+        "ms13cashf8jh6sdrkpyrsp5ut94pj8ktehhw2hfvyrj48704"
+    );
+    assert_eq!(
+        Encoding::Hex.encode(&Codex32Share::combine(&shares).unwrap()),
+        "ffeeddccbbaa99887766554433221100"
+    );
+
+    // Only two of the three needed shares
+    let error: Error = Codex32Share::combine(&shares[..2]).unwrap_err();
+
+    assert!(matches!(error.kind(), ErrorKind::NotEnoughShares(3, 2)));
+}
+
+#[test]
+fn codex32_vector_4() {
+    let master_secret: &str = "ffeeddccbbaa99887766554433221100ffeeddccbbaa99887766554433221100";
+
+    let share: Codex32Share =
+        Codex32Share::new_secret(&Encoding::Hex.decode(master_secret).unwrap(), "leet").unwrap();
+
+    assert_eq!(
+        share.to_string(),
+        "ms10leetsllhdmn9m42vcsamx24zrxgs3qrl7ahwvhw4fnzrhve25gvezzyqqtum9pgv99ycma"
+    );
+
+    // Every choice of padding bits encodes the same secret
+    for string in &[
+        "ms10leetsllhdmn9m42vcsamx24zrxgs3qrl7ahwvhw4fnzrhve25gvezzyqpj82dp34u6lqtd",
+        "ms10leetsllhdmn9m42vcsamx24zrxgs3qrl7ahwvhw4fnzrhve25gvezzyqzsrs4pnh7jmpj5",
+        "ms10leetsllhdmn9m42vcsamx24zrxgs3qrl7ahwvhw4fnzrhve25gvezzyqrfcpap2w8dqezy",
+        "ms10leetsllhdmn9m42vcsamx24zrxgs3qrl7ahwvhw4fnzrhve25gvezzyqy5tdvphn6znrf0",
+        "ms10leetsllhdmn9m42vcsamx24zrxgs3qrl7ahwvhw4fnzrhve25gvezzyq9dsuypw2ragmel",
+        "ms10leetsllhdmn9m42vcsamx24zrxgs3qrl7ahwvhw4fnzrhve25gvezzyqx05xupvgp4v6qx",
+        "ms10leetsllhdmn9m42vcsamx24zrxgs3qrl7ahwvhw4fnzrhve25gvezzyq8k0h5p43c2hzsk",
+        "ms10leetsllhdmn9m42vcsamx24zrxgs3qrl7ahwvhw4fnzrhve25gvezzyqgum7hplmjtr8ks",
+        "ms10leetsllhdmn9m42vcsamx24zrxgs3qrl7ahwvhw4fnzrhve25gvezzyqf9q0lpxzt5clxq",
+        "ms10leetsllhdmn9m42vcsamx24zrxgs3qrl7ahwvhw4fnzrhve25gvezzyq28y48pyqfuu7le",
+        "ms10leetsllhdmn9m42vcsamx24zrxgs3qrl7ahwvhw4fnzrhve25gvezzyqt7ly0paesr8x0f",
+        "ms10leetsllhdmn9m42vcsamx24zrxgs3qrl7ahwvhw4fnzrhve25gvezzyqvrvg7pqydv5uyz",
+        "ms10leetsllhdmn9m42vcsamx24zrxgs3qrl7ahwvhw4fnzrhve25gvezzyqd6hekpea5n0y5j",
+        "ms10leetsllhdmn9m42vcsamx24zrxgs3qrl7ahwvhw4fnzrhve25gvezzyqwcnrwpmlkmt9dt",
+        "ms10leetsllhdmn9m42vcsamx24zrxgs3qrl7ahwvhw4fnzrhve25gvezzyq0pgjxpzx0ysaam",
+    ] {
+        let share: Codex32Share = string.parse().unwrap();
+
+        assert_eq!(Encoding::Hex.encode(&share.data()), master_secret);
+    }
+}
+
+#[test]
+fn codex32_vector_5_long_checksum() {
+    let string: &str = "MS100C8VSM32ZXFGUHPCHTLUPZRY9X8GF2TVDW0S3JN54KHCE6MUA7LQPZYGSFJD6AN074RXVCEMLH8WU3TK925ACDEFGHJKLMNPQRSTUVWXY06FHPV80UNDVARHRAK";
+    let share: Codex32Share = string.parse().unwrap();
+
+    assert_eq!(share.identifier(), "0c8v");
+    assert_eq!(
+        Encoding::Hex.encode(&share.data()),
+        "dc5423251cb87175ff8110c8531d0952d8d73e1194e95b5f19d6f9df7c01111104c9baecdfea8cccc677fb9ddc8aec5553b86e528bcadfdcc201c17c638c47e9"
+    );
+    assert_eq!(share.to_string(), string.to_lowercase());
+}
+
+#[test]
+fn codex32_invalid_strings() {
+    let cases: &[&str] = &[
+        // Last checksum character changed
+        "ms10testsxxxxxxxxxxxxxxxxxxxxxxxxxx4nzvca9cmczlq",
+        // Mixed case
+        "ms10testsxxxxxxxxxxxxxxxxxxxxxxxxxx4nzvca9cmczLW",
+        // Wrong human readable part
+        "mx10testsxxxxxxxxxxxxxxxxxxxxxxxxxx4nzvca9cmczlw",
+        // Not in the bech32 alphabet
+        "ms10testsxxxxxxxxxxxxxxxxxxxxxxxxxx4nzvca9cmczlb",
+        // Too short
+        "ms10testsxxxxxxxxxxxxxxxxxxxxxxxxx4nzvca9cmczlw",
+    ];
+
+    let errors: Vec<Error> = cases
+        .iter()
+        .map(|string: &&str| string.parse::<Codex32Share>().unwrap_err())
+        .collect();
+
+    assert!(matches!(errors[0].kind(), ErrorKind::InvalidShareChecksum));
+    assert!(matches!(errors[1].kind(), ErrorKind::MixedCaseCodex32));
+    assert!(matches!(errors[2].kind(), ErrorKind::InvalidCodex32Prefix));
+    assert!(matches!(
+        errors[3].kind(),
+        ErrorKind::InvalidCodex32Character('b')
+    ));
+    assert!(matches!(
+        errors[4].kind(),
+        ErrorKind::InvalidCodex32Length(_)
+    ));
+}
+
+#[test]
+fn codex32_mismatched_shares() {
+    let shares: Vec<Codex32Share> = parse(&[
+        "MS12NAMEA320ZYXWVUTSRQPNMLKJHGFEDCAXRPP870HKKQRM",
+        "ms13cashcacdefghjklmnpqrstuvwxyz023949xq35my48dr",
+    ]);
+
+    let error: Error = Codex32Share::combine(&shares).unwrap_err();
+
+    assert!(matches!(error.kind(), ErrorKind::MismatchedShares));
+}
+
+#[test]
+fn codex32_keyphrase_round_trip() {
+    for keyphrase_type in &[KeyPhraseType::Words12, KeyPhraseType::Words24] {
+        let keyphrase = KeyPhrase::new(*keyphrase_type, Language::English);
+
+        let secret: Codex32Share = keyphrase.to_codex32_secret("test").unwrap();
+        let recovered = KeyPhrase::from_codex32(&[secret], Language::English).unwrap();
+
+        assert_eq!(recovered.phrase(), keyphrase.phrase());
+
+        let shares: Vec<Codex32Share> = keyphrase.to_codex32_shares(3, "test", 5).unwrap();
+        let indices: Vec<char> = shares.iter().map(Codex32Share::share_index).collect();
+
+        assert_eq!(indices, vec!['a', 'c', 'd', 'e', 'f']);
+
+        let recovered = KeyPhrase::from_codex32(
+            &[shares[4].clone(), shares[1].clone(), shares[2].clone()],
+            Language::English,
+        )
+        .unwrap();
+
+        assert_eq!(recovered.phrase(), keyphrase.phrase());
+    }
+}
+
+#[test]
+fn codex32_invalid_split() {
+    let entropy: [u8; 16] = [0x42; 16];
+
+    let error: Error = Codex32Share::split(&entropy, 1, "test", 3).unwrap_err();
+    assert!(matches!(error.kind(), ErrorKind::InvalidThreshold(1, 3)));
+
+    let error: Error = Codex32Share::split(&entropy, 3, "test", 2).unwrap_err();
+    assert!(matches!(error.kind(), ErrorKind::InvalidThreshold(3, 2)));
+
+    let error: Error = Codex32Share::split(&entropy, 2, "tes", 3).unwrap_err();
+    assert!(matches!(
+        error.kind(),
+        ErrorKind::InvalidCodex32Identifier(_)
+    ));
+
+    let error: Error = Codex32Share::new_secret(&entropy[..15], "test").unwrap_err();
+    assert!(matches!(error.kind(), ErrorKind::InvalidSecretLength(15)));
+}
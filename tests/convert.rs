@@ -0,0 +1,113 @@
+use keyphrase::{KeyPhrase, KeyPhraseType, Language};
+use std::io::Write;
+use std::process::{Command, Output, Stdio};
+
+const LANGUAGES: &[Language; 10] = &[
+    Language::English,
+    Language::ChineseSimplified,
+    Language::ChineseTraditional,
+    Language::Czech,
+    Language::French,
+    Language::Italian,
+    Language::Japanese,
+    Language::Korean,
+    Language::Portuguese,
+    Language::Spanish,
+];
+
+fn convert_language(from: Language) {
+    let types: &[KeyPhraseType; 5] = &[
+        KeyPhraseType::Words12,
+        KeyPhraseType::Words15,
+        KeyPhraseType::Words18,
+        KeyPhraseType::Words21,
+        KeyPhraseType::Words24,
+    ];
+
+    for mtype in types {
+        let m1: KeyPhrase = KeyPhrase::new(*mtype, from.clone());
+
+        for to in LANGUAGES {
+            let m2: KeyPhrase = m1.to_language(to.clone());
+            let m3: KeyPhrase =
+                KeyPhrase::from_phrase(m2.phrase(), to.clone()).expect("Can create a KeyPhrase");
+
+            assert_eq!(m1.entropy(), m2.entropy());
+            assert_eq!(m1.entropy(), m3.entropy());
+            assert_eq!(m1.phrase(), m3.to_language(from.clone()).phrase());
+        }
+    }
+}
+
+#[test]
+fn convert_english() {
+    convert_language(Language::English);
+}
+
+#[test]
+fn convert_chinese_simplified() {
+    convert_language(Language::ChineseSimplified);
+}
+
+#[test]
+fn convert_chinese_traditional() {
+    convert_language(Language::ChineseTraditional);
+}
+
+#[test]
+fn convert_czech() {
+    convert_language(Language::Czech);
+}
+
+#[test]
+fn convert_french() {
+    convert_language(Language::French);
+}
+
+#[test]
+fn convert_italian() {
+    convert_language(Language::Italian);
+}
+
+#[test]
+fn convert_japanese() {
+    convert_language(Language::Japanese);
+}
+
+#[test]
+fn convert_korean() {
+    convert_language(Language::Korean);
+}
+
+#[test]
+fn convert_portuguese() {
+    convert_language(Language::Portuguese);
+}
+
+#[test]
+fn convert_spanish() {
+    convert_language(Language::Spanish);
+}
+
+#[test]
+fn convert_command() {
+    let phrase: &str = "park remain person kitchen mule spell knee armed position rail grid ankle";
+    let expected: String = KeyPhrase::from_phrase(phrase, Language::English)
+        .unwrap()
+        .to_language(Language::French)
+        .into_phrase();
+
+    let mut child = Command::new(env!("CARGO_BIN_EXE_keyphrase"))
+        .args(["convert", "--to", "french"])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
+        .expect("Can start the keyphrase binary");
+
+    writeln!(child.stdin.take().unwrap(), "{}", phrase).unwrap();
+
+    let output: Output = child.wait_with_output().unwrap();
+
+    assert!(output.status.success());
+    assert_eq!(String::from_utf8(output.stdout).unwrap(), expected + "\n");
+}
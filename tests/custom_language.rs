@@ -0,0 +1,131 @@
+use keyphrase::{CustomLanguage, Error, ErrorKind, KeyPhrase, KeyPhraseType, Language};
+use std::sync::{Arc, Weak};
+
+fn words() -> Vec<String> {
+    (0..2048).map(|i: usize| format!("word{}", i)).collect()
+}
+
+fn custom_language(path: &str, lang: Language) {
+    let custom: CustomLanguage = CustomLanguage::from_file(path).expect("Can load word list");
+    let custom: Language = Language::from(custom);
+
+    for _ in 0..100 {
+        let m1: KeyPhrase = KeyPhrase::new(KeyPhraseType::Words24, lang.clone());
+        let m2: KeyPhrase =
+            KeyPhrase::from_phrase(m1.phrase(), custom.clone()).expect("Valid phrase");
+
+        assert_eq!(m1.entropy(), m2.entropy());
+        assert_eq!(m1.phrase(), m1.to_language(custom.clone()).phrase());
+    }
+}
+
+#[test]
+fn custom_english() {
+    custom_language("src/langs/english.txt", Language::English);
+}
+
+#[test]
+fn custom_japanese() {
+    custom_language("src/langs/japanese.txt", Language::Japanese);
+}
+
+#[test]
+fn custom_from_str() {
+    let list: String = words().join("\r\n");
+    let custom: CustomLanguage = list.parse().expect("Can parse word list");
+    let lang: Language = Language::Custom(Arc::new(custom));
+
+    let keyphrase: KeyPhrase = KeyPhrase::from_entropy(&[0; 16], lang).unwrap();
+
+    assert_eq!(
+        keyphrase.phrase(),
+        "word0 word0 word0 word0 word0 word0 word0 word0 word0 word0 word0 word3"
+    );
+}
+
+#[test]
+fn custom_language_is_freed() {
+    let custom: Arc<CustomLanguage> = Arc::new(CustomLanguage::from_words(words()).unwrap());
+    let weak: Weak<CustomLanguage> = Arc::downgrade(&custom);
+
+    let keyphrase: KeyPhrase = KeyPhrase::new(KeyPhraseType::Words12, Language::Custom(custom));
+    let copy: KeyPhrase = keyphrase.clone();
+
+    drop(keyphrase);
+    assert!(weak.upgrade().is_some());
+
+    drop(copy);
+    assert!(weak.upgrade().is_none());
+}
+
+#[test]
+fn reject_wrong_length() {
+    let mut list: Vec<String> = words();
+    list.pop();
+
+    let error: Error = CustomLanguage::from_words(list).unwrap_err();
+
+    assert!(matches!(
+        error.kind(),
+        ErrorKind::InvalidWordListLength(2047)
+    ));
+}
+
+#[test]
+fn reject_duplicate_word() {
+    let mut list: Vec<String> = words();
+    list[100] = "word5".to_string();
+
+    let error: Error = CustomLanguage::from_words(list).unwrap_err();
+
+    assert!(matches!(error.kind(), ErrorKind::DuplicateWord(word) if word == "word5"));
+}
+
+#[test]
+fn reject_unnormalized_word() {
+    let mut list: Vec<String> = words();
+    // precomposed "é" is NFC, NFKD splits it into "e" and a combining accent
+    list[7] = "caf\u{e9}".to_string();
+
+    let error: Error = CustomLanguage::from_words(list).unwrap_err();
+
+    assert!(matches!(error.kind(), ErrorKind::UnnormalizedWord(_)));
+}
+
+#[test]
+fn accept_normalized_word() {
+    let mut list: Vec<String> = words();
+    list[7] = "cafe\u{301}".to_string();
+
+    assert!(CustomLanguage::from_words(list).is_ok());
+}
+
+#[test]
+fn reject_whitespace_in_word() {
+    let mut list: Vec<String> = words();
+    list[3] = "two words".to_string();
+
+    let error: Error = CustomLanguage::from_words(list).unwrap_err();
+
+    assert!(matches!(error.kind(), ErrorKind::WhitespaceInWord(_)));
+}
+
+#[test]
+fn reject_empty_word() {
+    let mut list: Vec<String> = words();
+    list[42] = String::new();
+
+    let error: Error = list.join("\n").parse::<CustomLanguage>().unwrap_err();
+
+    assert!(matches!(error.kind(), ErrorKind::EmptyWord(42)));
+}
+
+#[test]
+fn check_unique_prefix() {
+    let english: CustomLanguage = CustomLanguage::from_file("src/langs/english.txt").unwrap();
+
+    assert!(english.check_unique_prefix(4).is_ok());
+
+    let error: Error = english.check_unique_prefix(3).unwrap_err();
+    assert!(matches!(error.kind(), ErrorKind::AmbiguousPrefix(_, _, 3)));
+}
@@ -0,0 +1,251 @@
+use keyphrase::{
+    ElectrumOldSeed, ElectrumOldWordList, ElectrumSeed, ElectrumSeedType, Error, ErrorKind,
+    KeyPhrase, Language, PhraseFormat, Seed,
+};
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+
+/// Electrum's old seed vector from test_mnemonic.py
+const OLD_VECTOR: (&str, &str) = (
+    "acb740e454c3134901d7c8f16497cc1c",
+    "powerful random nobody notice nothing important anyway look away hidden message over",
+);
+
+/// Index of every word of `OLD_VECTOR` in Electrum's old word list
+const OLD_VECTOR_INDICES: [usize; 12] = [1130, 1131, 601, 269, 63, 600, 507, 10, 21, 486, 1025, 37];
+
+/// Stand-in for Electrum's old word list, which is not part of the crate
+///
+/// The words of `OLD_VECTOR` are at their real index, word `i` is `w{i}` everywhere else.
+fn old_wordlist() -> ElectrumOldWordList {
+    let mut words: Vec<String> = (0..1626).map(|i| format!("w{}", i)).collect();
+
+    for (word, index) in OLD_VECTOR.1.split(' ').zip(&OLD_VECTOR_INDICES) {
+        words[*index] = word.to_string();
+    }
+
+    ElectrumOldWordList::from_words(words).unwrap()
+}
+
+// Vectors from Electrum's test_mnemonic.py
+#[test]
+fn electrum_vectors() {
+    for (phrase, seed_type, passphrase, seed) in &[
+        (
+            "wild father tree among universe such mobile favorite target dynamic credit identify",
+            ElectrumSeedType::Segwit,
+            "",
+            "aac2a6302e48577ab4b46f23dbae0774e2e62c796f797d0a1b5faeb528301e3064342dafb79069e7c4c6b8c38ae11d7a973bec0d4f70626f8cc5184a8d0b0756",
+        ),
+        (
+            "wild father tree among universe such mobile favorite target dynamic credit identify",
+            ElectrumSeedType::Segwit,
+            "Did you ever hear the tragedy of Darth Plagueis the Wise?",
+            "4aa29f2aeb0127efb55138ab9e7be83b36750358751906f86c662b21a1ea1370f949e6d1a12fa56d3d93cadda93038c76ac8118597364e46f5156fde6183c82f",
+        ),
+        (
+            "なのか ひろい しなん まなぶ つぶす さがす おしゃれ かわく おいかける けさき かいとう さたん",
+            ElectrumSeedType::Standard,
+            "",
+            "d3eaf0e44ddae3a5769cb08a26918e8b308258bcb057bb704c6f69713245c0b35cb92c03df9c9ece5eff826091b4e74041e010b701d44d610976ce8bfb66a8ad",
+        ),
+        (
+            "なのか ひろい しなん まなぶ つぶす さがす おしゃれ かわく おいかける けさき かいとう さたん",
+            ElectrumSeedType::Standard,
+            "Satoshi Nakamoto",
+            "e682c9a0b1b2b41e21b1688cb1e9522dd2c77c2d6d2c8e5fbf2f04ed2a3de85f391fee156744122fd7849f4b18548025df95df4a5133acce9ca28b80e4c3e99c",
+        ),
+        (
+            "almíbar tibio superar vencer hacha peatón príncipe matar consejo polen vehículo odisea",
+            ElectrumSeedType::Standard,
+            "",
+            "18bffd573a960cc775bbd80ed60b7dc00bc8796a186edebe7fc7cf1f316da0fe937852a969c5c79ded8255cdf54409537a16339fbe33fb9161af793ea47faa7a",
+        ),
+        (
+            "眼 悲 叛 改 节 跃 衡 响 疆 股 遂 冬",
+            ElectrumSeedType::Segwit,
+            "",
+            "0b9077db7b5a50dbb6f61821e2d35e255068a5847e221138048a20e12d80b673ce306b6fe7ac174ebc6751e11b7037be6ee9f17db8040bb44f8466d519ce2abf",
+        ),
+    ] {
+        let electrum = ElectrumSeed::from_phrase(phrase).unwrap();
+
+        assert_eq!(electrum.seed_type(), *seed_type);
+        assert_eq!(electrum.phrase(), *phrase);
+        assert_eq!(format!("{:x}", electrum.to_seed(passphrase)), *seed);
+    }
+}
+
+#[test]
+fn phrase_is_normalized() {
+    let phrase =
+        "wild father tree among universe such mobile favorite target dynamic credit identify";
+    let messy =
+        "  Wild FATHER tree among\tuniverse such mobile favorite target dynamic credit identify\n";
+
+    let electrum = ElectrumSeed::from_phrase(messy).unwrap();
+
+    assert_eq!(electrum.phrase(), messy);
+    assert_eq!(
+        electrum.to_seed("").as_bytes(),
+        ElectrumSeed::from_phrase(phrase)
+            .unwrap()
+            .to_seed("")
+            .as_bytes()
+    );
+}
+
+#[test]
+fn reject_invalid_phrase() {
+    let phrase = "park remain person kitchen mule spell knee armed position rail grid ankle";
+    let error: Error = ElectrumSeed::from_phrase(phrase).unwrap_err();
+
+    assert!(matches!(error.kind(), ErrorKind::InvalidElectrumSeed));
+}
+
+#[test]
+fn generate_every_type() {
+    let mut rng = StdRng::seed_from_u64(50);
+
+    for seed_type in &[
+        ElectrumSeedType::Standard,
+        ElectrumSeedType::Segwit,
+        ElectrumSeedType::TwoFactor,
+    ] {
+        let electrum = ElectrumSeed::new_with_rng(*seed_type, Language::English, &mut rng);
+
+        assert_eq!(electrum.seed_type(), *seed_type);
+        assert_eq!(electrum.phrase().split(' ').count(), 12);
+        assert_eq!(
+            ElectrumSeed::from_phrase(electrum.phrase())
+                .unwrap()
+                .seed_type(),
+            *seed_type
+        );
+    }
+}
+
+#[test]
+fn old_seed_from_hex() {
+    let electrum = ElectrumOldSeed::from_hex("acb740e454c3134901d7c8f16497cc1c").unwrap();
+
+    assert_eq!(
+        electrum.master_private_key(),
+        [
+            0x21, 0xb8, 0x80, 0xfd, 0xa2, 0xfd, 0x30, 0x08, 0x18, 0x34, 0x68, 0x3a, 0x70, 0x49,
+            0xac, 0x9e, 0x39, 0x41, 0xa4, 0x2a, 0xdb, 0xc3, 0xa4, 0x61, 0x6c, 0x9a, 0x92, 0x75,
+            0xaa, 0x96, 0x0c, 0x0d
+        ]
+    );
+
+    let error: Error = ElectrumOldSeed::from_hex("acb740e454c3134901d7c8f16497cc").unwrap_err();
+    assert!(matches!(
+        error.kind(),
+        ErrorKind::InvalidElectrumSeedLength(15)
+    ));
+}
+
+#[test]
+fn old_seed_vector() {
+    let wordlist: ElectrumOldWordList = old_wordlist();
+    let (hex, phrase) = OLD_VECTOR;
+
+    let electrum = ElectrumOldSeed::from_phrase(phrase, &wordlist).unwrap();
+
+    assert_eq!(electrum.to_hex(), hex);
+    assert_eq!(electrum.to_phrase(&wordlist), phrase);
+}
+
+#[test]
+fn old_seed_round_trip() {
+    let wordlist: ElectrumOldWordList = old_wordlist();
+
+    for hex in &[
+        "acb740e454c3134901d7c8f16497cc1c",
+        "ffffffff00000000ffffffff00000000ffffffff00000000ffffffff00000000",
+    ] {
+        let electrum = ElectrumOldSeed::from_hex(hex).unwrap();
+        let phrase: String = electrum.to_phrase(&wordlist);
+
+        assert_eq!(phrase.split(' ').count(), hex.len() / 8 * 3);
+        assert_eq!(
+            ElectrumOldSeed::from_phrase(&phrase, &wordlist)
+                .unwrap()
+                .to_hex(),
+            *hex
+        );
+    }
+}
+
+#[test]
+fn reject_invalid_old_phrase() {
+    let wordlist: ElectrumOldWordList = old_wordlist();
+
+    let error: Error = ElectrumOldSeed::from_phrase("w0 w1 w2", &wordlist).unwrap_err();
+    assert!(matches!(error.kind(), ErrorKind::InvalidWordLength(3)));
+
+    let error: Error =
+        ElectrumOldSeed::from_phrase("w0 w0 w0 w0 w0 w0 w0 w0 w0 w0 w0 w9999", &wordlist)
+            .unwrap_err();
+    assert!(matches!(
+        error.kind(),
+        ErrorKind::InvalidWord { position: 11 }
+    ));
+
+    // Three words of n^3 - n, more than 32 bits
+    let error: Error =
+        ElectrumOldSeed::from_phrase("w0 w1625 w1624 w0 w0 w0 w0 w0 w0 w0 w0 w0", &wordlist)
+            .unwrap_err();
+    assert!(matches!(error.kind(), ErrorKind::InvalidElectrumSeed));
+
+    let error: Error = ElectrumOldWordList::from_words(vec!["w"; 1625]).unwrap_err();
+    assert!(matches!(
+        error.kind(),
+        ErrorKind::InvalidWordListLength(1625)
+    ));
+}
+
+#[test]
+fn detect_ambiguous_phrase() {
+    // Valid BIP39, and its Electrum version hash happens to start with 01
+    let phrase = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon mass";
+
+    assert_eq!(
+        PhraseFormat::detect(phrase, Language::English),
+        [
+            PhraseFormat::Bip39,
+            PhraseFormat::Electrum(ElectrumSeedType::Standard)
+        ]
+    );
+
+    // Both restore a different wallet
+    let bip39: Seed = Seed::new(
+        &KeyPhrase::from_phrase(phrase, Language::English).unwrap(),
+        "",
+    );
+    let electrum: Seed = ElectrumSeed::from_phrase(phrase).unwrap().to_seed("");
+
+    assert_eq!(
+        format!("{:x}", electrum),
+        "4d16dbe7e877b8ef59633e92cd9afa10b90b9e36e0502327a5c080e706159f0d4547cf006faa1e6b74f3852e30b705105a055d20367d8e369dac2902d01c34da"
+    );
+    assert_ne!(bip39.as_bytes(), electrum.as_bytes());
+}
+
+#[test]
+fn detect_old_seeds() {
+    let wordlist: ElectrumOldWordList = old_wordlist();
+    let hex = "acb740e454c3134901d7c8f16497cc1c";
+    let phrase: String = ElectrumOldSeed::from_hex(hex).unwrap().to_phrase(&wordlist);
+
+    assert_eq!(
+        PhraseFormat::detect(hex, Language::English),
+        [PhraseFormat::ElectrumOld]
+    );
+    assert!(PhraseFormat::detect(&phrase, Language::English).is_empty());
+    assert_eq!(
+        PhraseFormat::detect_with_old_words(&phrase, Language::English, &wordlist),
+        [PhraseFormat::ElectrumOld]
+    );
+    assert!(PhraseFormat::detect("not a seed", Language::English).is_empty());
+}
@@ -0,0 +1,103 @@
+use keyphrase::{Encoding, KeyPhrase, KeyPhraseType, Language, Seed};
+
+const ENCODINGS: &[Encoding; 3] = &[Encoding::Hex, Encoding::Base64, Encoding::Base58];
+
+/// Entropy of every length where each byte takes every value, at every position
+fn entropies() -> Vec<Vec<u8>> {
+    let mut entropies: Vec<Vec<u8>> = Vec::new();
+
+    for len in &[16, 20, 24, 28, 32] {
+        for value in 0..=255u8 {
+            entropies.push(vec![value; *len]);
+
+            // Over all values, every position takes every value next to different neighbours
+            let shifted: Vec<u8> = (0..*len as u8).map(|i: u8| value.wrapping_add(i)).collect();
+            entropies.push(shifted);
+        }
+
+        let mut zeros_then_value: Vec<u8> = vec![0; *len];
+        zeros_then_value[*len - 1] = 1;
+        entropies.push(zeros_then_value);
+    }
+
+    entropies
+}
+
+#[test]
+fn keyphrase_hex_formatting_round_trips() {
+    for entropy in entropies() {
+        let keyphrase: KeyPhrase = KeyPhrase::from_entropy(&entropy, Language::English).unwrap();
+
+        let lower: String = format!("{:x}", keyphrase);
+        let upper: String = format!("{:X}", keyphrase);
+
+        assert_eq!(lower.len(), entropy.len() * 2);
+        assert_eq!(upper, lower.to_uppercase());
+
+        for hex in &[lower, upper, format!("{:#x}", keyphrase)] {
+            let restored = KeyPhrase::from_hex_entropy(hex, Language::English).unwrap();
+
+            assert_eq!(restored.entropy(), &entropy[..]);
+        }
+    }
+}
+
+#[test]
+fn keyphrase_entropy_round_trips() {
+    for entropy in entropies() {
+        let keyphrase: KeyPhrase = KeyPhrase::from_entropy(&entropy, Language::English).unwrap();
+
+        for encoding in ENCODINGS {
+            let encoded: String = keyphrase.encode_entropy(*encoding);
+            let restored: KeyPhrase =
+                KeyPhrase::decode_entropy(&encoded, *encoding, Language::English).unwrap();
+
+            assert_eq!(restored.entropy(), &entropy[..], "{:?}", encoding);
+        }
+    }
+}
+
+#[test]
+fn random_keyphrase_entropy_round_trips() {
+    for _ in 0..256 {
+        let keyphrase = KeyPhrase::new(KeyPhraseType::Words24, Language::English);
+
+        for encoding in ENCODINGS {
+            let encoded: String = keyphrase.encode_entropy(*encoding);
+            let restored: KeyPhrase =
+                KeyPhrase::decode_entropy(&encoded, *encoding, Language::English).unwrap();
+
+            assert_eq!(restored.phrase(), keyphrase.phrase());
+        }
+    }
+}
+
+#[test]
+fn seed_round_trips() {
+    for len in &[16, 32, 64] {
+        for value in 0..=255u8 {
+            let bytes: Vec<u8> = (0..*len as u8).map(|i: u8| value ^ i).collect();
+            let seed: Seed = Seed::from_bytes(&bytes).unwrap();
+
+            for encoding in ENCODINGS {
+                let restored: Seed = Seed::decode(&seed.encode(*encoding), *encoding).unwrap();
+
+                assert_eq!(restored.as_bytes(), &bytes[..]);
+            }
+
+            assert_eq!(format!("{:x}", seed).len(), len * 2);
+            assert_eq!(
+                format!("{:X}", seed).parse::<Seed>().unwrap().as_bytes(),
+                &bytes[..]
+            );
+        }
+    }
+}
+
+#[test]
+fn decoded_entropy_is_checked() {
+    let encoded: String = Encoding::Base58.encode(&[0xff; 15]);
+
+    assert!(KeyPhrase::decode_entropy(&encoded, Encoding::Base58, Language::English).is_err());
+    assert!(Seed::decode(&Encoding::Base64.encode(&[0; 8]), Encoding::Base64).is_err());
+}
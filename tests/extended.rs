@@ -0,0 +1,162 @@
+use keyphrase::{Encoding, IndexFormat, KeyPhrase, KeyPhraseType, Language, LengthPolicy};
+
+/// Entropy and phrase, with one checksum bit for every 32 bits of entropy
+const VECTORS: &[(&str, &str)] = &[
+    ("33e46bb1", "crop cash unable"),
+    ("0000000000000000", "abandon abandon abandon abandon abandon able"),
+    (
+        "ffffffffffffffffffffffff",
+        "zoo zoo zoo zoo zoo zoo zoo zoo zebra",
+    ),
+    (
+        "000102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f202122232425262728292a2b2c2d2e2f303132333435363738393a3b3c3d3e3f",
+        "abandon amount liar amount expire adjust cage candy arch gather drum bullet absurd math era live bid rhythm alien crouch range attend journey tomato cancel baby simple engage give neglect pigeon earth club harvest mesh gather basket book speak plug damp scrub excess island sense pair zoo run",
+    ),
+    (
+        "7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f",
+        "legal winner thank year wave sausage worth useful legal winner thank year wave sausage worth useful legal winner thank year wave sausage worth useful legal winner thank year wave sausage worth useful legal winner thank year wave sausage worth useful legal winner thank year wave sausage zebra advice",
+    ),
+];
+
+#[test]
+fn extended_vectors() {
+    for (entropy, phrase) in VECTORS {
+        let entropy: Vec<u8> = Encoding::Hex.decode(entropy).unwrap();
+
+        let keyphrase: KeyPhrase = KeyPhrase::from_entropy_with_policy(
+            &entropy,
+            Language::English,
+            LengthPolicy::EXTENDED,
+        )
+        .unwrap();
+        assert_eq!(keyphrase.phrase(), *phrase);
+
+        let keyphrase: KeyPhrase =
+            KeyPhrase::from_phrase_with_policy(*phrase, Language::English, LengthPolicy::EXTENDED)
+                .unwrap();
+        assert_eq!(keyphrase.entropy(), &entropy[..]);
+        assert_eq!(keyphrase.keyphrase_type().entropy_bits(), entropy.len() * 8);
+
+        // Strict BIP39 lengths are the default
+        assert!(KeyPhrase::from_phrase(*phrase, Language::English).is_err());
+        assert!(KeyPhrase::from_entropy(&entropy, Language::English).is_err());
+    }
+}
+
+#[test]
+fn extended_checksum() {
+    // The 16 bit checksum of a 48 word phrase spans the last two words
+    let (_, phrase) = VECTORS[4];
+
+    for (from, to) in &[
+        (" zebra advice", " zebra add"),
+        (" zebra advice", " zoo advice"),
+    ] {
+        let tampered: String = phrase.replace(from, to);
+
+        assert!(KeyPhrase::from_phrase_with_policy(
+            tampered,
+            Language::English,
+            LengthPolicy::EXTENDED
+        )
+        .is_err());
+    }
+
+    assert!(KeyPhrase::from_phrase_with_policy(
+        "crop cash unfair",
+        Language::English,
+        LengthPolicy::EXTENDED
+    )
+    .is_err());
+}
+
+#[test]
+fn extended_round_trip() {
+    for words in (3..=48).step_by(3) {
+        let keyphrase_type: KeyPhraseType =
+            KeyPhraseType::for_word_count_with_policy(words, LengthPolicy::EXTENDED).unwrap();
+
+        for _ in 0..100 {
+            let m1: KeyPhrase = KeyPhrase::new(keyphrase_type, Language::English);
+            let m2: KeyPhrase = KeyPhrase::from_phrase_with_policy(
+                m1.phrase(),
+                Language::English,
+                LengthPolicy::EXTENDED,
+            )
+            .unwrap();
+
+            assert_eq!(m1.phrase().split(' ').count(), words);
+            assert_eq!(m1.entropy(), m2.entropy());
+            assert_eq!(m1.indices(), m2.indices());
+        }
+    }
+}
+
+#[test]
+fn custom_policy() {
+    let pairing: LengthPolicy = LengthPolicy::new(32, 96).unwrap();
+
+    assert!(
+        KeyPhrase::from_phrase_with_policy("crop cash unable", Language::English, pairing).is_ok()
+    );
+
+    let phrase: &str = "park remain person kitchen mule spell knee armed position rail grid ankle";
+    assert!(KeyPhrase::from_phrase_with_policy(phrase, Language::English, pairing).is_err());
+}
+
+#[test]
+fn extended_index_rows_round_trip() {
+    for (_, phrase) in &[VECTORS[1], VECTORS[4]] {
+        let keyphrase: KeyPhrase =
+            KeyPhrase::from_phrase_with_policy(*phrase, Language::English, LengthPolicy::EXTENDED)
+                .unwrap();
+
+        let indices: Vec<u16> = keyphrase.indices();
+        let restored: KeyPhrase = KeyPhrase::from_indices_with_policy(
+            &indices,
+            Language::English,
+            LengthPolicy::EXTENDED,
+        )
+        .unwrap();
+        assert_eq!(restored.phrase(), *phrase);
+        assert!(KeyPhrase::from_indices(&indices, Language::English).is_err());
+
+        for format in &[
+            IndexFormat::OneBased,
+            IndexFormat::ZeroBased,
+            IndexFormat::Binary,
+        ] {
+            let rows: Vec<String> = keyphrase.to_index_rows(*format);
+            let restored: KeyPhrase = KeyPhrase::from_index_rows_with_policy(
+                &rows,
+                *format,
+                Language::English,
+                LengthPolicy::EXTENDED,
+            )
+            .unwrap();
+
+            assert_eq!(restored.phrase(), *phrase);
+        }
+    }
+}
+
+#[test]
+fn extended_find_candidates() {
+    let words: Vec<Option<&str>> = vec![
+        Some("abandon"),
+        Some("abandon"),
+        Some("abandon"),
+        Some("abandon"),
+        Some("abandon"),
+        None,
+    ];
+
+    let candidates: Vec<KeyPhrase> =
+        KeyPhrase::find_candidates_with_policy(&words, Language::English, LengthPolicy::EXTENDED)
+            .unwrap();
+
+    assert!(candidates
+        .iter()
+        .any(|keyphrase: &KeyPhrase| keyphrase.phrase() == VECTORS[1].1));
+    assert!(KeyPhrase::find_candidates(&words, Language::English).is_err());
+}
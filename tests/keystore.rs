@@ -0,0 +1,175 @@
+#![cfg(feature = "keystore")]
+
+use keyphrase::{
+    Error, ErrorKind, KeyPhrase, KeyPhraseType, Keystore, KeystoreParams, Language, LengthPolicy,
+};
+
+/// "abandon ... art" under the password "TREZOR", with `n = 4096`
+const KEYSTORE: &str = r#"{
+  "version": 1,
+  "language": "en",
+  "words": 24,
+  "crypto": {
+    "cipher": "aes-256-gcm",
+    "cipherparams": {
+      "nonce": "555555555555555555555555"
+    },
+    "ciphertext": "75ac43e9d29bfbb18378ba00321f60fe01ae149025f5c232454b046f482a278f8e9c1becf63e8766582ed80070f638be",
+    "kdf": "scrypt",
+    "kdfparams": {
+      "dklen": 32,
+      "n": 4096,
+      "r": 8,
+      "p": 1,
+      "salt": "aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa"
+    }
+  }
+}"#;
+
+const PHRASE: &str = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon art";
+
+fn cheap_params() -> KeystoreParams {
+    KeystoreParams::new(10, 8, 1).unwrap()
+}
+
+#[test]
+fn decrypt_fixed_vector() {
+    let keystore: Keystore = Keystore::from_json(KEYSTORE).unwrap();
+
+    assert_eq!(keystore.label(), None);
+    assert_eq!(keystore.language(), Language::English);
+    assert_eq!(keystore.keyphrase_type().word_count(), 24);
+    assert_eq!(keystore.params(), KeystoreParams::new(12, 8, 1).unwrap());
+
+    assert_eq!(keystore.decrypt("TREZOR").unwrap().phrase(), PHRASE);
+
+    // Written back exactly as it was read
+    assert_eq!(keystore.to_json(), KEYSTORE);
+}
+
+#[test]
+fn wrong_password() {
+    let keystore: Keystore = Keystore::from_json(KEYSTORE).unwrap();
+
+    let err: Error = keystore.decrypt("trezor").unwrap_err();
+    assert!(matches!(err.kind(), ErrorKind::KeystoreDecryption));
+}
+
+#[test]
+fn cleartext_fields_are_authenticated() {
+    let keyphrase = KeyPhrase::new(KeyPhraseType::Words12, Language::English);
+    let json: String = Keystore::encrypt(&keyphrase, "password", Some("hot"), cheap_params())
+        .unwrap()
+        .to_json();
+
+    for (from, to) in &[
+        ("\"label\": \"hot\"", "\"label\": \"cold\""),
+        ("\"language\": \"en\"", "\"language\": \"es\""),
+    ] {
+        let tampered: String = json.replace(from, to);
+        let keystore: Keystore = Keystore::from_json(&tampered).unwrap();
+
+        let err: Error = keystore.decrypt("password").unwrap_err();
+        assert!(matches!(err.kind(), ErrorKind::KeystoreDecryption));
+    }
+}
+
+#[test]
+fn round_trip_every_language() {
+    for lang in Language::all() {
+        let keyphrase = KeyPhrase::new(KeyPhraseType::Words18, lang.clone());
+        let keystore = Keystore::encrypt(&keyphrase, "pässwörd", None, cheap_params()).unwrap();
+
+        let restored: Keystore = Keystore::from_json(&keystore.to_json()).unwrap();
+
+        assert_eq!(restored.language(), *lang);
+        assert_eq!(
+            restored.decrypt("pässwörd").unwrap().phrase(),
+            keyphrase.phrase()
+        );
+    }
+}
+
+#[test]
+fn reencrypt() {
+    let keystore: Keystore = Keystore::from_json(KEYSTORE).unwrap();
+    let params = KeystoreParams::new(11, 8, 2).unwrap();
+
+    let upgraded: Keystore = keystore
+        .reencrypt("TREZOR", "new password", params)
+        .unwrap();
+
+    assert_eq!(upgraded.params(), params);
+    assert_eq!(upgraded.decrypt("new password").unwrap().phrase(), PHRASE);
+    assert!(upgraded.decrypt("TREZOR").is_err());
+
+    let err: Error = keystore
+        .reencrypt("wrong", "new password", params)
+        .unwrap_err();
+    assert!(matches!(err.kind(), ErrorKind::KeystoreDecryption));
+}
+
+#[test]
+fn reject_invalid_json() {
+    let err: Error =
+        Keystore::from_json(&KEYSTORE.replace("\"version\": 1", "\"version\": 2")).unwrap_err();
+    assert!(matches!(
+        err.kind(),
+        ErrorKind::UnsupportedKeystoreVersion(2)
+    ));
+
+    let err: Error =
+        Keystore::from_json(&KEYSTORE.replace("\"n\": 4096", "\"n\": 4095")).unwrap_err();
+    assert!(matches!(err.kind(), ErrorKind::InvalidKeystore(_)));
+
+    let err: Error =
+        Keystore::from_json(&KEYSTORE.replace("\"n\": 4096", "\"n\": 4194304")).unwrap_err();
+    assert!(matches!(
+        err.kind(),
+        ErrorKind::InvalidKeystoreParams(22, 8, 1)
+    ));
+
+    // A crafted block size would need 128 * 4294967295 * 4096 bytes to decrypt
+    let err: Error =
+        Keystore::from_json(&KEYSTORE.replace("\"r\": 8", "\"r\": 4294967295")).unwrap_err();
+    assert!(matches!(
+        err.kind(),
+        ErrorKind::InvalidKeystoreParams(12, 4294967295, 1)
+    ));
+
+    let err: Error =
+        Keystore::from_json(&KEYSTORE.replace("aes-256-gcm", "aes-128-ctr")).unwrap_err();
+    assert!(matches!(err.kind(), ErrorKind::InvalidKeystore(_)));
+
+    let err: Error =
+        Keystore::from_json(&KEYSTORE.replace("\"words\": 24", "\"words\": 12")).unwrap_err();
+    assert!(matches!(err.kind(), ErrorKind::InvalidKeystore(_)));
+
+    let err: Error = Keystore::from_json("{}").unwrap_err();
+    assert!(matches!(err.kind(), ErrorKind::InvalidKeystore(_)));
+}
+
+#[test]
+fn debug_hides_ciphertext() {
+    let keystore: Keystore = Keystore::from_json(KEYSTORE).unwrap();
+
+    assert!(!format!("{:?}", keystore).contains("75ac43"));
+}
+
+#[test]
+fn round_trip_extended_lengths() {
+    for words in &[3, 48] {
+        let keyphrase_type =
+            KeyPhraseType::for_word_count_with_policy(*words, LengthPolicy::EXTENDED).unwrap();
+        let keyphrase = KeyPhrase::new(keyphrase_type, Language::English);
+        let keystore = Keystore::encrypt(&keyphrase, "password", None, cheap_params()).unwrap();
+
+        let restored: Keystore = Keystore::from_json(&keystore.to_json()).unwrap();
+
+        assert_eq!(restored.keyphrase_type(), keyphrase_type);
+        assert_eq!(
+            restored.decrypt("password").unwrap().phrase(),
+            keyphrase.phrase()
+        );
+    }
+}
@@ -0,0 +1,102 @@
+use keyphrase::{Capitalization, Language, PassphraseGenerator, WordMap};
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+
+/// Pearson's chi-squared statistic of `counts` against a uniform distribution
+fn chi_squared(counts: &[usize]) -> f64 {
+    let total: usize = counts.iter().sum();
+    let expected: f64 = total as f64 / counts.len() as f64;
+
+    counts
+        .iter()
+        .map(|count: &usize| (*count as f64 - expected).powi(2) / expected)
+        .sum()
+}
+
+#[test]
+fn words_are_uniform() {
+    let generator = PassphraseGenerator::new(64, Language::English);
+    let wordmap: &WordMap = Language::English.wordmap();
+    let mut rng = StdRng::seed_from_u64(1);
+    let mut counts: Vec<usize> = vec![0; 2048];
+
+    for _ in 0..2048 {
+        for word in generator.generate_with_rng(&mut rng).split(' ') {
+            counts[u16::from(wordmap.get_bits(word).unwrap()) as usize] += 1;
+        }
+    }
+
+    // 2047 degrees of freedom, the mean is 2047 with a standard deviation of 64
+    assert!(chi_squared(&counts) < 2047.0 + 6.0 * 64.0);
+}
+
+#[test]
+fn digits_and_positions_are_uniform() {
+    // Three words and one digit, so 4 positions and 10 digits
+    let generator = PassphraseGenerator::new(3, Language::English).digits(1);
+    let mut rng = StdRng::seed_from_u64(2);
+    let mut digits: Vec<usize> = vec![0; 10];
+    let mut positions: Vec<usize> = vec![0; 4];
+
+    for _ in 0..100_000 {
+        let passphrase: String = generator.generate_with_rng(&mut rng);
+        let parts: Vec<&str> = passphrase.split(' ').collect();
+        let position: usize = parts
+            .iter()
+            .position(|part: &&str| part.chars().all(|c: char| c.is_ascii_digit()))
+            .unwrap();
+
+        positions[position] += 1;
+        digits[parts[position].parse::<usize>().unwrap()] += 1;
+    }
+
+    // 9 and 3 degrees of freedom, far above the 0.1% critical values of 27.9 and 16.3
+    assert!(chi_squared(&digits) < 27.9);
+    assert!(chi_squared(&positions) < 16.3);
+}
+
+#[test]
+fn random_capitalization() {
+    let generator = PassphraseGenerator::new(1000, Language::English)
+        .capitalization(Capitalization::Random)
+        .separator("-");
+    let mut rng = StdRng::seed_from_u64(3);
+
+    let passphrase: String = generator.generate_with_rng(&mut rng);
+    let capitalized: usize = passphrase
+        .split('-')
+        .filter(|word: &&str| word.starts_with(|c: char| c.is_uppercase()))
+        .count();
+
+    assert!((400..600).contains(&capitalized));
+    assert_eq!(generator.entropy_bits(), 12_000.0);
+}
+
+#[test]
+fn entropy_bits() {
+    let generator = PassphraseGenerator::new(5, Language::English).digits(3);
+
+    // 5 words of 11 bits, 3 digits and 6 positions for them
+    let expected: f64 = 55.0 + 3.0 * 10f64.log2() + 6f64.log2();
+
+    assert!((generator.entropy_bits() - expected).abs() < 1e-9);
+    assert_eq!(
+        PassphraseGenerator::new(0, Language::English).entropy_bits(),
+        0.0
+    );
+}
+
+#[test]
+fn same_seed_same_passphrase() {
+    let generator = PassphraseGenerator::new(8, Language::Spanish)
+        .separator("/")
+        .capitalization(Capitalization::Uppercase)
+        .digits(4);
+
+    let first: String = generator.generate_with_rng(&mut StdRng::seed_from_u64(4));
+    let second: String = generator.generate_with_rng(&mut StdRng::seed_from_u64(4));
+
+    assert_eq!(first, second);
+    assert_eq!(first.split('/').count(), 9);
+    assert_eq!(first, first.to_uppercase());
+}
@@ -0,0 +1,99 @@
+use std::io::Write;
+use std::process::{Command, Output, Stdio};
+
+fn run_recover(args: &[&str], input: &str) -> Output {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_keyphrase"))
+        .arg("recover")
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .expect("Can start the keyphrase binary");
+
+    child
+        .stdin
+        .take()
+        .unwrap()
+        .write_all(input.as_bytes())
+        .unwrap();
+
+    child.wait_with_output().unwrap()
+}
+
+#[test]
+fn recover_full_phrase() {
+    let phrase: &str = "park remain person kitchen mule spell knee armed position rail grid ankle";
+    let input: String = phrase.replace(' ', "\n") + "\n";
+
+    let output: Output = run_recover(&["--words", "12", "--reveal"], &input);
+    let stdout: String = String::from_utf8(output.stdout).unwrap();
+
+    assert!(output.status.success());
+    assert_eq!(stdout, format!("1 valid keyphrase found\n{}\n", phrase));
+}
+
+#[test]
+fn recover_prompts_for_word_count_and_autocompletes() {
+    let input: &str =
+        "12\npark\nrema\npers\nkitc\nmule\nspel\nknee\narme\nposi\nrail\ngrid\nankl\n";
+
+    let output: Output = run_recover(&["--reveal"], input);
+    let stdout: String = String::from_utf8(output.stdout).unwrap();
+
+    assert!(output.status.success());
+    assert!(stdout
+        .ends_with("park remain person kitchen mule spell knee armed position rail grid ankle\n"));
+}
+
+#[test]
+fn recover_flags_invalid_and_ambiguous_words() {
+    let input: &str =
+        "park\nremian\nremain\nper\nperson\nkitchen\nmule\nspell\nknee\narmed\nposition\nrail\ngrid\nankle\n";
+
+    let output: Output = run_recover(&["--words", "12"], input);
+    let stderr: String = String::from_utf8(output.stderr).unwrap();
+
+    assert!(output.status.success());
+    assert!(stderr.contains("invalid word\n  did you mean: region, remain, remind\n"));
+    assert!(stderr.contains("ambiguous: "));
+}
+
+#[test]
+fn recover_unknown_word() {
+    let phrase: &str = "park remain person kitchen mule spell knee armed position rail grid ankle";
+    let input: String = phrase.replace("kitchen", "?").replace(' ', "\n") + "\n";
+
+    let output: Output = run_recover(&["--words", "12", "--reveal"], &input);
+    let stdout: String = String::from_utf8(output.stdout).unwrap();
+
+    assert!(output.status.success());
+    assert!(stdout.lines().any(|line: &str| line == phrase));
+}
+
+#[test]
+fn recover_does_not_reveal_words_unless_asked() {
+    let phrase: &str = "park remain person kitchen mule spell knee armed position rail grid ankle";
+    let input: String = phrase.replace(' ', "\n") + "\n";
+
+    let output: Output = run_recover(&["--words", "12"], &input);
+    let stdout: String = String::from_utf8(output.stdout).unwrap();
+    let stderr: String = String::from_utf8(output.stderr).unwrap();
+
+    assert!(output.status.success());
+
+    for word in phrase.split(' ') {
+        assert!(!stdout.contains(word));
+        assert!(!stderr.contains(word));
+    }
+}
+
+#[test]
+fn recover_invalid_checksum() {
+    let input: &str =
+        "park\nremain\nperson\nkitchen\nmule\nspell\nknee\narmed\nposition\nrail\ngrid\nabandon\n";
+
+    let output: Output = run_recover(&["--words", "12"], input);
+
+    assert!(!output.status.success());
+}
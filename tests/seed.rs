@@ -0,0 +1,203 @@
+use keyphrase::{
+    Encoding, Error, ErrorKind, KeyPhrase, Language, Pbkdf2Hash, Seed, SeedKdf, SeedOptions,
+};
+
+const PHRASE: &str = "park remain person kitchen mule spell knee armed position rail grid ankle";
+
+fn keyphrase() -> KeyPhrase {
+    KeyPhrase::from_phrase(PHRASE, Language::English).unwrap()
+}
+
+#[test]
+fn default_seed_is_unchanged() {
+    let seed: Seed = Seed::new(&keyphrase(), "");
+
+    assert_eq!(
+        Encoding::Hex.encode(seed.as_bytes()),
+        "b8d559cfab61597e0afe3665e68b53b5c07c4b88c60cbfe5e8e3e5fbd24447bf025ab2528ecfa9b7779f05aa075d225f7cf7327ed5fb037c212658ffbe341db1"
+    );
+    assert_eq!(seed.options(), Some(SeedOptions::default()));
+
+    let seed: Seed = Seed::new(&keyphrase(), "TREZOR");
+
+    assert_eq!(
+        Encoding::Hex.encode(seed.as_bytes()),
+        "29539618ac46069c604250a25109702295aef6b6c6592aece17fc16020c623512e16d0de14f0cc6bc95a323928d9f786eadec5ef3b9d41d5dbf13e91c6d82855"
+    );
+}
+
+#[test]
+fn default_options_match_seed_new() {
+    let options = SeedOptions::new()
+        .pbkdf2(Pbkdf2Hash::Sha512, 2048)
+        .length(64);
+    let seed: Seed = Seed::new_with_options(&keyphrase(), "TREZOR", options).unwrap();
+
+    assert_eq!(options, SeedOptions::default());
+    assert_eq!(
+        seed.as_bytes(),
+        Seed::new(&keyphrase(), "TREZOR").as_bytes()
+    );
+}
+
+#[test]
+fn pbkdf2_sha256() {
+    let options = SeedOptions::new()
+        .pbkdf2(Pbkdf2Hash::Sha256, 4096)
+        .length(32);
+    let seed: Seed = Seed::new_with_options(&keyphrase(), "TREZOR", options).unwrap();
+
+    assert_eq!(
+        Encoding::Hex.encode(seed.as_bytes()),
+        "3992fe686291e2b3248ea6e2bcece0297700ff689518932aa7817f838bbf16f6"
+    );
+    assert!(matches!(
+        seed.options().unwrap().kdf(),
+        SeedKdf::Pbkdf2 {
+            hash: Pbkdf2Hash::Sha256,
+            rounds: 4096
+        }
+    ));
+}
+
+#[test]
+#[cfg(feature = "argon2id")]
+fn argon2id() {
+    let options = SeedOptions::new().argon2id(1024, 2, 1).length(32);
+    let seed: Seed = Seed::new_with_options(&keyphrase(), "TREZOR", options).unwrap();
+
+    assert_eq!(
+        Encoding::Hex.encode(seed.as_bytes()),
+        "2d3f569df3af78cc288911d6b1882c51d3aa38e458ea55c04f1f99963dd14aa8"
+    );
+
+    let err: Error =
+        Seed::new_with_options(&keyphrase(), "", SeedOptions::new().argon2id(1, 2, 1)).unwrap_err();
+    assert!(matches!(err.kind(), ErrorKind::InvalidSeedOptions(_)));
+}
+
+#[test]
+fn recorded_options_reproduce_the_seed() {
+    let options = SeedOptions::new().pbkdf2(Pbkdf2Hash::Sha256, 10).length(48);
+    let seed: Seed = Seed::new_with_options(&keyphrase(), "", options).unwrap();
+
+    let recorded: String = seed.options().unwrap().to_string();
+    let again: Seed = Seed::new_with_options(&keyphrase(), "", recorded.parse().unwrap()).unwrap();
+
+    assert_eq!(seed.as_bytes().len(), 48);
+    assert_eq!(seed.as_bytes(), again.as_bytes());
+}
+
+#[test]
+fn invalid_options() {
+    let err: Error =
+        Seed::new_with_options(&keyphrase(), "", SeedOptions::new().length(8)).unwrap_err();
+    assert!(matches!(err.kind(), ErrorKind::InvalidSeedLength(8)));
+
+    let options = SeedOptions::new().pbkdf2(Pbkdf2Hash::Sha512, 0);
+    let err: Error = Seed::new_with_options(&keyphrase(), "", options).unwrap_err();
+    assert!(matches!(err.kind(), ErrorKind::InvalidSeedOptions(_)));
+}
+
+#[test]
+fn domain_seed() {
+    let seed: Seed = Seed::new_with_domain(&keyphrase(), "earth-mainnet", "");
+
+    assert_eq!(
+        Encoding::Hex.encode(seed.as_bytes()),
+        "8bc39535c44ed9bbe2868792fde24c2053b43a8400cf2ef9ccdcd2065336255df8308a7510bec5e5cd04d9c88768a2c252c90156a01d0e9221ec8bf848e9b3ca"
+    );
+
+    let seed: Seed = Seed::new_with_domain(&keyphrase(), "earth-mainnet", "TREZOR");
+
+    assert_eq!(
+        Encoding::Hex.encode(seed.as_bytes()),
+        "fd5974ef9f359088dfe1f6ecf4dd9052433f37a205d66a488d62e753b123fb665401d9fb68b5da0010fffce821635da7c4a44996cd8c4229799aa3b44fcce10e"
+    );
+}
+
+#[test]
+fn domains_and_passwords_do_not_collide() {
+    let split = |domain: &str, password: &str| -> Vec<u8> {
+        Seed::new_with_domain(&keyphrase(), domain, password)
+            .as_bytes()
+            .to_vec()
+    };
+
+    assert_ne!(split("ab", "c"), split("a", "bc"));
+    assert_ne!(split("", "keyphrase"), split("keyphrase", ""));
+
+    // An empty domain is still separate from the plain seed
+    assert_eq!(
+        Encoding::Hex.encode(&split("", "")),
+        "88464fe44de84e2fb3da4ba30b01b0d0567a033727c6886a773c865caece27e68f8ea9e63855490cfd3ed96b6c631a76e8d93f444cd188ad2e814cb0031dce83"
+    );
+    assert_ne!(split("", ""), Seed::new(&keyphrase(), "").as_bytes());
+}
+
+#[test]
+fn domain_seed_with_options() {
+    let options = SeedOptions::new().length(32);
+    let seed: Seed =
+        Seed::new_with_domain_and_options(&keyphrase(), "earth-mainnet", "", options).unwrap();
+
+    assert_eq!(seed.options(), Some(options));
+    assert_eq!(
+        seed.as_bytes(),
+        &Seed::new_with_domain(&keyphrase(), "earth-mainnet", "").as_bytes()[..32]
+    );
+}
+
+#[test]
+fn seed_from_bytes_and_hex() {
+    let seed: Seed = Seed::new(&keyphrase(), "");
+
+    let from_bytes: Seed = Seed::from_bytes(seed.as_bytes()).unwrap();
+    let from_lower: Seed = Seed::from_hex(&format!("{:x}", seed)).unwrap();
+    let from_upper: Seed = format!("{:X}", seed).parse().unwrap();
+    let from_debug: Seed = format!("{:?}", seed).parse().unwrap();
+
+    for imported in &[from_bytes, from_lower, from_upper, from_debug] {
+        assert_eq!(imported.as_bytes(), seed.as_bytes());
+        assert_eq!(imported.options(), None);
+    }
+
+    assert_eq!(Seed::from_bytes(&[0; 16]).unwrap().as_bytes().len(), 16);
+}
+
+#[test]
+fn seed_from_invalid_bytes_and_hex() {
+    for len in &[0, 15, 65] {
+        let err: Error = Seed::from_bytes(&vec![0; *len]).unwrap_err();
+        assert!(matches!(err.kind(), ErrorKind::InvalidSeedLength(l) if l == len));
+    }
+
+    let err: Error = Seed::from_hex(&"ab".repeat(8)).unwrap_err();
+    assert!(matches!(err.kind(), ErrorKind::InvalidSeedLength(8)));
+
+    let err: Error = Seed::from_hex(&"a".repeat(63)).unwrap_err();
+    assert!(matches!(err.kind(), ErrorKind::InvalidHexLength(63)));
+
+    let err: Error = "g".repeat(64).parse::<Seed>().unwrap_err();
+    assert!(matches!(err.kind(), ErrorKind::InvalidHexCharacter('g')));
+}
+
+#[test]
+fn keyphrase_from_hex_entropy() {
+    let keyphrase: KeyPhrase =
+        KeyPhrase::from_hex_entropy("a056b28d3d8915a25ee05ea8761d9984", Language::English).unwrap();
+
+    assert_eq!(keyphrase.phrase(), PHRASE);
+    assert_eq!(
+        KeyPhrase::from_hex_entropy(&format!("{:#X}", keyphrase), Language::English)
+            .unwrap()
+            .phrase(),
+        PHRASE
+    );
+
+    let err: Error = KeyPhrase::from_hex_entropy("a056b2", Language::English).unwrap_err();
+    assert!(matches!(err.kind(), ErrorKind::InvalidKeysize(24)));
+
+    let err: Error = KeyPhrase::from_hex_entropy("a056b2 ", Language::English).unwrap_err();
+    assert!(matches!(err.kind(), ErrorKind::InvalidHexCharacter(' ')));
+}
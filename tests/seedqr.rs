@@ -0,0 +1,138 @@
+use keyphrase::{Encoding, Error, ErrorKind, KeyPhrase, Language};
+
+/// SeedQR test vectors: phrase, standard SeedQR digits and CompactSeedQR bytes
+const VECTORS: &[(&str, &str, &str)] = &[
+    (
+        "attack pizza motion avocado network gather crop fresh patrol unusual wild holiday candy pony ranch winter theme error hybrid van cereal salon goddess expire",
+        "011513251154012711900771041507421289190620080870026613431420201617920614089619290300152408010643",
+        "0e74b64107f94cc0ccfae6a13dcbec3662154fec67e0e00999c07892597d190a",
+    ),
+    (
+        "atom solve joy ugly ankle message setup typical bean era cactus various odor refuse element afraid meadow quick medal plate wisdom swap noble shallow",
+        "011416550964188800731119157218870156061002561932122514430573003611011405110613292018175411971576",
+        "0e59dde276009317f1275f1389888078c99368d1e82489b5f629531fc5b6a56e",
+    ),
+    (
+        "sound federal bonus bleak light raise false engage round stock update render quote truck quality fringe palace foot recipe labor glow tortoise potato still",
+        "166206750203018810361417065805941507171219081456140818651401074412730727143709940798183613501710",
+        "cfca8c658bc81962549252bc7ac3ba5b0b01d26bcae89f2b5ecebe263dcb2a36",
+    ),
+    (
+        "forum undo fragile fade shy sign arrest garment culture tube off merit",
+        "073318950739065415961602009907670428187212261116",
+        "5bbd9d71a8ec7990831aff359d426545",
+    ),
+];
+
+#[test]
+fn seedqr_vectors() {
+    for (phrase, digits, compact) in VECTORS {
+        let keyphrase = KeyPhrase::from_phrase(*phrase, Language::English).unwrap();
+
+        assert_eq!(&keyphrase.to_seedqr_digits(), digits);
+        assert_eq!(
+            &Encoding::Hex.encode(&keyphrase.to_compact_seedqr_bytes()),
+            compact
+        );
+
+        let decoded = KeyPhrase::from_seedqr_digits(digits, Language::English).unwrap();
+        assert_eq!(decoded.phrase(), *phrase);
+
+        let decoded = KeyPhrase::from_compact_seedqr_bytes(
+            &keyphrase.to_compact_seedqr_bytes(),
+            Language::English,
+        )
+        .unwrap();
+        assert_eq!(decoded.phrase(), *phrase);
+    }
+}
+
+#[test]
+fn seedqr_other_language() {
+    let (phrase, digits, _) = VECTORS[3];
+    let keyphrase = KeyPhrase::from_phrase(phrase, Language::English).unwrap();
+
+    let spanish = KeyPhrase::from_seedqr_digits(digits, Language::Spanish).unwrap();
+
+    assert_eq!(spanish.entropy(), keyphrase.entropy());
+    assert_eq!(spanish.to_seedqr_digits(), digits);
+}
+
+#[test]
+fn seedqr_invalid_digits() {
+    let (_, digits, _) = VECTORS[3];
+
+    let error: Error = KeyPhrase::from_seedqr_digits(&digits[1..], Language::English).unwrap_err();
+    assert!(matches!(error.kind(), ErrorKind::InvalidSeedQrLength(47)));
+
+    let error: Error =
+        KeyPhrase::from_seedqr_digits(&digits.replace('9', "x"), Language::English).unwrap_err();
+    assert!(matches!(
+        error.kind(),
+        ErrorKind::InvalidSeedQrCharacter('x')
+    ));
+
+    // Index 2048 is past the end of the word list
+    let error: Error =
+        KeyPhrase::from_seedqr_digits(&digits.replacen("0733", "2048", 1), Language::English)
+            .unwrap_err();
+    assert!(matches!(error.kind(), ErrorKind::InvalidWordIndex(2048)));
+
+    // Last two words swapped
+    let swapped: String = format!("{}{}{}", &digits[..40], &digits[44..], &digits[40..44]);
+    let error: Error = KeyPhrase::from_seedqr_digits(&swapped, Language::English).unwrap_err();
+    assert!(matches!(error.kind(), ErrorKind::InvalidChecksum));
+
+    let error: Error =
+        KeyPhrase::from_compact_seedqr_bytes(&[0; 15], Language::English).unwrap_err();
+    assert!(matches!(error.kind(), ErrorKind::InvalidKeysize(120)));
+}
+
+#[cfg(feature = "qr")]
+#[test]
+fn seedqr_matrix_sizes() {
+    let widths: Vec<(usize, usize)> = VECTORS
+        .iter()
+        .map(|(phrase, _, _)| {
+            let keyphrase = KeyPhrase::from_phrase(*phrase, Language::English).unwrap();
+
+            (
+                keyphrase.to_seedqr().width(),
+                keyphrase.to_compact_seedqr().width(),
+            )
+        })
+        .collect();
+
+    assert_eq!(widths, vec![(29, 25), (29, 25), (29, 25), (25, 21)]);
+}
+
+#[cfg(feature = "qr")]
+#[test]
+fn seedqr_render() {
+    let (phrase, _, _) = VECTORS[3];
+    let qr = KeyPhrase::from_phrase(phrase, Language::English)
+        .unwrap()
+        .to_compact_seedqr();
+
+    // Top left finder pattern, a dark ring around a light ring around a dark square
+    for i in 0..7 {
+        assert!(qr.is_dark(i, 0) && qr.is_dark(0, i) && qr.is_dark(i, 6) && qr.is_dark(6, i));
+    }
+    assert!(!qr.is_dark(1, 1) && qr.is_dark(2, 2) && qr.is_dark(4, 4));
+
+    let text: String = qr.to_text();
+    let lines: Vec<&str> = text.lines().collect();
+
+    // 21 modules and a quiet zone of 4 on each side, two characters per module
+    assert_eq!(lines.len(), 29);
+    assert!(lines.iter().all(|line: &&str| line.chars().count() == 58));
+    assert_eq!(lines[4].chars().nth(8), Some('█'));
+    assert_eq!(lines[3].trim(), "");
+
+    let svg: String = qr.to_svg(10);
+
+    assert!(svg.starts_with("<svg xmlns=\"http://www.w3.org/2000/svg\""));
+    assert!(svg.contains("width=\"290\" height=\"290\" viewBox=\"0 0 29 29\""));
+    assert!(svg.contains("<path d=\"M4,4h7v1h-7z"));
+    assert!(svg.trim_end().ends_with("</svg>"));
+}
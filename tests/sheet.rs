@@ -0,0 +1,145 @@
+use keyphrase::{BackupSheet, IndexFormat, KeyPhrase, KeyPhraseType, Language, LengthPolicy};
+use std::env;
+use std::fs;
+use std::io::Write;
+use std::path::PathBuf;
+use std::process::{Command, Output, Stdio};
+
+const PHRASE: &str = "park remain person kitchen mule spell knee armed position rail grid ankle";
+
+/// Compare against `tests/snapshots/<name>`, or rewrite it when `UPDATE_SNAPSHOTS` is set
+fn assert_snapshot(name: &str, actual: &str) {
+    let path: PathBuf = [env!("CARGO_MANIFEST_DIR"), "tests", "snapshots", name]
+        .iter()
+        .collect();
+
+    if env::var_os("UPDATE_SNAPSHOTS").is_some() {
+        fs::write(&path, actual).expect("Can write the snapshot");
+        return;
+    }
+
+    let expected: String = fs::read_to_string(&path).expect("Snapshot exists");
+
+    assert_eq!(actual, expected, "snapshot {} differs", name);
+}
+
+fn sheet() -> BackupSheet {
+    let keyphrase = KeyPhrase::from_phrase(PHRASE, Language::English).unwrap();
+
+    BackupSheet::new(&keyphrase).fingerprint([0xd9, 0xb0, 0xc5, 0x0a])
+}
+
+#[test]
+fn sheet_text() {
+    assert_snapshot("sheet_12_words.txt", &sheet().to_text());
+}
+
+#[test]
+fn sheet_svg() {
+    assert_snapshot("sheet_12_words.svg", &sheet().to_svg());
+}
+
+#[test]
+fn sheet_binary_indices() {
+    let text: String = sheet().index_format(IndexFormat::Binary).to_text();
+
+    assert_snapshot("sheet_12_words_binary.txt", &text);
+}
+
+#[test]
+fn sheet_native_language() {
+    let keyphrase = KeyPhrase::from_phrase(PHRASE, Language::English)
+        .unwrap()
+        .to_language(Language::Spanish);
+
+    assert_snapshot(
+        "sheet_12_words_spanish.txt",
+        &BackupSheet::new(&keyphrase).to_text(),
+    );
+}
+
+#[test]
+fn blank_text() {
+    let sheet = BackupSheet::blank(KeyPhraseType::Words24, Language::English);
+
+    assert_snapshot("sheet_blank_24_words.txt", &sheet.to_text());
+}
+
+#[test]
+fn blank_svg() {
+    let sheet = BackupSheet::blank(KeyPhraseType::Words24, Language::English);
+
+    assert_snapshot("sheet_blank_24_words.svg", &sheet.to_svg());
+}
+
+#[test]
+fn sheet_48_words_svg() {
+    let entropy: Vec<u8> = (0..64).collect();
+    let keyphrase =
+        KeyPhrase::from_entropy_with_policy(&entropy, Language::English, LengthPolicy::EXTENDED)
+            .unwrap();
+
+    assert_snapshot("sheet_48_words.svg", &BackupSheet::new(&keyphrase).to_svg());
+}
+
+#[test]
+fn blank_48_words_svg() {
+    let keyphrase_type: KeyPhraseType =
+        KeyPhraseType::for_word_count_with_policy(48, LengthPolicy::EXTENDED).unwrap();
+    let sheet = BackupSheet::blank(keyphrase_type, Language::English);
+
+    assert_snapshot("sheet_blank_48_words.svg", &sheet.to_svg());
+}
+
+#[test]
+fn blank_48_words_checksum() {
+    let keyphrase_type: KeyPhraseType =
+        KeyPhraseType::for_word_count_with_policy(48, LengthPolicy::EXTENDED).unwrap();
+    let text: String = BackupSheet::blank(keyphrase_type, Language::English).to_text();
+
+    assert!(text.contains("Words:        48 words (512bits)"));
+    assert!(text.contains("The last 16 bits of words 47 and 48 are the checksum"));
+}
+
+#[test]
+fn sheet_hides_words_from_debug() {
+    let debug: String = format!("{:?}", sheet());
+
+    assert!(!debug.contains("park"));
+}
+
+#[test]
+fn sheet_command() {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_keyphrase"))
+        .args(["sheet", "--index", "zero-based"])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
+        .expect("Can start the keyphrase binary");
+
+    writeln!(child.stdin.take().unwrap(), "{}", PHRASE).unwrap();
+
+    let output: Output = child.wait_with_output().unwrap();
+    let text: String = String::from_utf8(output.stdout).unwrap();
+
+    assert!(output.status.success());
+    assert!(text.contains(" 1  park      1282   [ ]"));
+
+    #[cfg(feature = "bip32")]
+    assert!(text.contains("Fingerprint:  d9b0c50a"));
+}
+
+#[test]
+fn blank_command() {
+    let output: Output = Command::new(env!("CARGO_BIN_EXE_keyphrase"))
+        .args(["sheet", "--blank", "--words", "24", "--format", "svg"])
+        .stdin(Stdio::null())
+        .output()
+        .expect("Can start the keyphrase binary");
+
+    assert!(output.status.success());
+    assert_snapshot(
+        "sheet_blank_24_words.svg",
+        &String::from_utf8(output.stdout).unwrap(),
+    );
+}
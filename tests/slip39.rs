@@ -0,0 +1,167 @@
+use keyphrase::{
+    Encoding, Error, ErrorKind, KeyPhrase, KeyPhraseType, Language, Share, ShareGroup,
+};
+
+/// Passphrase of the Trezor SLIP-39 test vectors
+const PASSPHRASE: &str = "TREZOR";
+
+fn parse(phrases: &[&str]) -> Vec<Share> {
+    phrases
+        .iter()
+        .map(|phrase: &&str| phrase.parse().expect("Valid share"))
+        .collect()
+}
+
+fn check_vector(phrases: &[&str], master_secret: &str) {
+    let shares: Vec<Share> = parse(phrases);
+
+    assert_eq!(
+        Encoding::Hex.encode(&Share::combine(&shares, PASSPHRASE).unwrap()),
+        master_secret
+    );
+
+    for (share, phrase) in shares.iter().zip(phrases) {
+        assert_eq!(&share.to_string(), phrase);
+    }
+}
+
+#[test]
+fn slip39_no_sharing_128_bits() {
+    check_vector(
+        &["duckling enlarge academic academic agency result length solution fridge kidney coal piece deal husband erode duke ajar critical decision keyboard"],
+        "bb54aac4b89dc868ba37d9cc21b2cece",
+    );
+}
+
+#[test]
+fn slip39_invalid_checksum() {
+    let error: Error = "duckling enlarge academic academic agency result length solution fridge kidney coal piece deal husband erode duke ajar critical decision kidney"
+        .parse::<Share>()
+        .unwrap_err();
+
+    assert!(matches!(error.kind(), ErrorKind::InvalidShareChecksum));
+}
+
+#[test]
+fn slip39_basic_sharing_2_of_3_128_bits() {
+    check_vector(
+        &[
+            "shadow pistol academic always adequate wildlife fancy gross oasis cylinder mustang wrist rescue view short owner flip making coding armed",
+            "shadow pistol academic acid actress prayer class unknown daughter sweater depict flip twice unkind craft early superior advocate guest smoking",
+        ],
+        "b43ceb7e57a0ea8766221624d01b0864",
+    );
+}
+
+#[test]
+fn slip39_basic_sharing_not_enough_shares() {
+    let shares: Vec<Share> = parse(&["shadow pistol academic always adequate wildlife fancy gross oasis cylinder mustang wrist rescue view short owner flip making coding armed"]);
+    let error: Error = Share::combine(&shares, PASSPHRASE).unwrap_err();
+
+    assert!(matches!(error.kind(), ErrorKind::NotEnoughShares(2, 1)));
+}
+
+#[test]
+fn slip39_group_sharing_128_bits() {
+    check_vector(
+        &[
+            "eraser senior beard romp adorn nuclear spill corner cradle style ancient family general leader ambition exchange unusual garlic promise voice",
+            "eraser senior ceramic snake clay various huge numb argue hesitate auction category timber browser greatest hanger petition script leaf pickup",
+            "eraser senior ceramic shaft dynamic become junior wrist silver peasant force math alto coal amazing segment yelp velvet image paces",
+            "eraser senior ceramic round column hawk trust auction smug shame alive greatest sheriff living perfect corner chest sled fumes adequate",
+            "eraser senior decision smug corner ruin rescue cubic angel tackle skin skunk program roster trash rumor slush angel flea amazing",
+        ],
+        "7c3397a292a5941682d7a4ae2d898d11",
+    );
+}
+
+#[test]
+fn slip39_no_sharing_256_bits() {
+    check_vector(
+        &["theory painting academic academic armed sweater year military elder discuss acne wildlife boring employer fused large satoshi bundle carbon diagnose anatomy hamster leaves tracks paces beyond phantom capital marvel lips brave detect luck"],
+        "989baf9dcaad5b10ca33dfd8cc75e42477025dce88ae83e75a230086a0e00e92",
+    );
+}
+
+#[test]
+fn slip39_basic_sharing_2_of_3_256_bits() {
+    check_vector(
+        &[
+            "humidity disease academic always aluminum jewelry energy woman receiver strategy amuse duckling lying evidence network walnut tactics forget hairy rebound impulse brother survive clothes stadium mailman rival ocean reward venture always armed unwrap",
+            "humidity disease academic agency actress jacket gross physics cylinder solution fake mortgage benefit public busy prepare sharp friar change work slow purchase ruler again tricycle involve viral wireless mixture anatomy desert cargo upgrade",
+        ],
+        "c938b319067687e990e05e0da0ecce1278f75ff58d9853f19dcaeed5de104aae",
+    );
+}
+
+#[test]
+fn slip39_extendable_no_sharing_128_bits() {
+    check_vector(
+        &["testify swimming academic academic column loyalty smear include exotic bedroom exotic wrist lobe cover grief golden smart junior estimate learn"],
+        "1679b4516e0ee5954351d288a838f45e",
+    );
+}
+
+#[test]
+fn slip39_mismatched_shares() {
+    let shares: Vec<Share> = parse(&[
+        "shadow pistol academic always adequate wildlife fancy gross oasis cylinder mustang wrist rescue view short owner flip making coding armed",
+        "humidity disease academic agency actress jacket gross physics cylinder solution fake mortgage benefit public busy prepare sharp friar change work slow purchase ruler again tricycle involve viral wireless mixture anatomy desert cargo upgrade",
+    ]);
+    let error: Error = Share::combine(&shares, PASSPHRASE).unwrap_err();
+
+    assert!(matches!(error.kind(), ErrorKind::MismatchedShares));
+}
+
+#[test]
+fn slip39_keyphrase_round_trip() {
+    let groups: &[ShareGroup] = &[
+        ShareGroup::new(2, 3),
+        ShareGroup::new(1, 1),
+        ShareGroup::new(3, 5),
+    ];
+
+    for mtype in &[KeyPhraseType::Words12, KeyPhraseType::Words24] {
+        let keyphrase: KeyPhrase = KeyPhrase::new(*mtype, Language::English);
+        let shares: Vec<Vec<Share>> = keyphrase.to_shares("secret", 2, groups).unwrap();
+
+        assert_eq!(shares.len(), 3);
+        assert_eq!(shares[0].len(), 3);
+        assert_eq!(shares[2].len(), 5);
+
+        // Two members of the first group and three of the last
+        let picked: Vec<Share> = parse(&[
+            &shares[0][2].to_string(),
+            &shares[2][4].to_string(),
+            &shares[0][0].to_string(),
+            &shares[2][1].to_string(),
+            &shares[2][3].to_string(),
+        ]);
+
+        let recovered: KeyPhrase =
+            KeyPhrase::from_shares(&picked, "secret", Language::English).unwrap();
+        assert_eq!(recovered.phrase(), keyphrase.phrase());
+
+        // Any passphrase decrypts, but a wrong one gives a different secret
+        let other: Vec<u8> = Share::combine(&picked, "other").unwrap();
+        assert_ne!(other, keyphrase.entropy());
+
+        // The single member of the second group and one of the last aren't enough
+        let error: Error =
+            Share::combine(&[shares[1][0].clone(), shares[2][0].clone()], "secret").unwrap_err();
+        assert!(matches!(error.kind(), ErrorKind::NotEnoughShares(3, 1)));
+    }
+}
+
+#[test]
+fn slip39_rejects_invalid_groups() {
+    let secret: &[u8] = &[7; 16];
+
+    assert!(Share::split(secret, "", 1, &[ShareGroup::new(1, 2)]).is_err());
+    assert!(Share::split(secret, "", 2, &[ShareGroup::new(1, 1)]).is_err());
+    assert!(Share::split(secret, "", 0, &[ShareGroup::new(1, 1)]).is_err());
+    assert!(Share::split(secret, "", 1, &[ShareGroup::new(3, 2)]).is_err());
+    assert!(Share::split(secret, "", 1, &[ShareGroup::new(2, 17)]).is_err());
+    assert!(Share::split(&[7; 15], "", 1, &[ShareGroup::new(1, 1)]).is_err());
+    assert!(Share::split(&[7; 18], "", 1, &[ShareGroup::new(1, 1)]).is_ok());
+}
@@ -11,9 +11,9 @@ fn validate_language(lang: Language) {
 
     for mtype in types {
         for _ in 0..1000 {
-            let m1: KeyPhrase = KeyPhrase::new(*mtype, lang);
+            let m1: KeyPhrase = KeyPhrase::new(*mtype, lang.clone());
             let m2: KeyPhrase =
-                KeyPhrase::from_phrase(m1.phrase(), lang).expect("Can create a KeyPhrase");
+                KeyPhrase::from_phrase(m1.phrase(), lang.clone()).expect("Can create a KeyPhrase");
 
             assert_eq!(m1.entropy(), m2.entropy());
         }
@@ -55,6 +55,20 @@ fn validate_24_english() {
     let _ = KeyPhrase::from_phrase(phrase, Language::English).expect("Can create a KeyPhrase");
 }
 
+#[test]
+fn validate_12_czech() {
+    let phrase: &str = "pomyslet rozruch posudek nositel pamflet tehdy novota boubel prolomit revize matice blatouch";
+
+    let _ = KeyPhrase::from_phrase(phrase, Language::Czech).expect("Can create a KeyPhrase");
+}
+
+#[test]
+fn validate_12_portuguese() {
+    let phrase: &str = "mirtilo parafina motim herdar limitar retalho hibernar alertar nobreza ossada feirante agiota";
+
+    let _ = KeyPhrase::from_phrase(phrase, Language::Portuguese).expect("Can create a KeyPhrase");
+}
+
 #[test]
 fn validate_12_english_uppercase() {
     let invalid_phrase: &str =
@@ -78,6 +92,11 @@ fn validate_chinese_traditional() {
     validate_language(Language::ChineseTraditional);
 }
 
+#[test]
+fn validate_czech() {
+    validate_language(Language::Czech);
+}
+
 #[test]
 fn validate_french() {
     validate_language(Language::French);
@@ -98,6 +117,11 @@ fn validate_korean() {
     validate_language(Language::Korean);
 }
 
+#[test]
+fn validate_portuguese() {
+    validate_language(Language::Portuguese);
+}
+
 #[test]
 fn validate_spanish() {
     validate_language(Language::Spanish);